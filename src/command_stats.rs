@@ -0,0 +1,59 @@
+/// Aggregate slash command invocation counts
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Log a usage summary after this many invocations
+pub const SUMMARY_EVERY: u64 = 100;
+
+/// Per-command invocation counters. The command set is fixed at startup, so the
+/// counters are plain atomics and recording never takes a lock.
+pub struct CommandStats {
+    counts: HashMap<String, AtomicU64>,
+    /// Invocations of names that aren't registered commands
+    other: AtomicU64,
+    total: AtomicU64,
+}
+
+impl CommandStats {
+    pub fn new<'a>(commands: impl IntoIterator<Item = &'a str>) -> Self {
+        let counts = commands.into_iter().map(|name| (name.to_string(), AtomicU64::new(0))).collect();
+        Self { counts, other: AtomicU64::new(0), total: AtomicU64::new(0) }
+    }
+
+    /// Count one invocation of `name`, returning the total across all commands
+    pub fn record(&self, name: &str) -> u64 {
+        self.counts.get(name).unwrap_or(&self.other).fetch_add(1, Ordering::Relaxed);
+        self.total.fetch_add(1, Ordering::Relaxed) + 1
+    }
+
+    /// Used commands, most invoked first, e.g. "rank=12, guilds=4"
+    pub fn summary(&self) -> String {
+        let mut counts: Vec<(&str, u64)> = self
+            .counts
+            .iter()
+            .map(|(name, count)| (name.as_str(), count.load(Ordering::Relaxed)))
+            .chain(std::iter::once(("other", self.other.load(Ordering::Relaxed))))
+            .filter(|(_, count)| *count > 0)
+            .collect();
+        counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        counts.iter().map(|(name, count)| format!("{}={}", name, count)).collect::<Vec<_>>().join(", ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_and_summarize() {
+        let stats = CommandStats::new(["rank", "guilds", "help"]);
+
+        assert_eq!(stats.record("rank"), 1);
+        assert_eq!(stats.record("guilds"), 2);
+        assert_eq!(stats.record("rank"), 3);
+        assert_eq!(stats.record("retired"), 4);
+
+        // Unused commands are left out; unregistered names are grouped as "other"
+        assert_eq!(stats.summary(), "rank=2, guilds=1, other=1");
+    }
+}
@@ -1,138 +1,628 @@
-use serenity::builder::CreateApplicationCommand;
+use serenity::builder::{CreateApplicationCommand, CreateEmbed};
 use serenity::model::application::interaction::application_command::ApplicationCommandInteraction;
+use serenity::model::application::interaction::autocomplete::AutocompleteInteraction;
 use serenity::model::application::command::CommandOptionType;
-use crate::config::AppConfig;
-use crate::database::{Database, DbMember};
-use crate::guild_data::{fetch_all_guild_data, sort_guilds, format_guild_list};
-use crate::raider_io::PlayerData;
-use crate::types::{RaidTier, PlayerName, RealmName, GuildName, MythicPlusScore};
+use serenity::model::permissions::Permissions;
+use crate::config::{AppConfig, DiscordConfig};
+use crate::database::{Database, DbMember, GuildImportSummary};
+use crate::guild_data::{fetch_all_guild_data, filter_by_difficulty, sort_guilds, format_best_progress, format_guild_list, format_standings_section, group_standings};
+use crate::locale::{self, Locale, t, t_args};
+use crate::raider_io::{GuildData, PlayerData, RaiderIOClient};
+use crate::table::{Column, TableLayout};
+use crate::responder::DISCORD_MESSAGE_LIMIT;
+use crate::types::{class_spec_names, CombatRole, RaidDifficulty, RaidTier, Role, RealmDisplayNames, WowClass, PlayerName, RealmName, GuildUrl, MythicPlusScore};
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::time::Duration;
+use tracing::{debug, error};
 
-pub fn guilds_command(command: &mut CreateApplicationCommand) -> &mut CreateApplicationCommand {
-    command
-        .name("guilds")
-        .description("Guilds Raid Rank")
-        .create_option(|option| {
-            option
-                .name("season")
-                .description("1/2/3")
-                .kind(CommandOptionType::Integer)
-                .required(false)
-        })
-        .create_option(|option| {
-            option
-                .name("limit")
-                .description("Number of guilds to display (or 'all' for full list)")
-                .kind(CommandOptionType::String)
-                .required(false)
-        })
+/// Slash command option metadata
+pub struct OptionMeta {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub kind: CommandOptionType,
+    pub required: bool,
+    /// Suggestions are served by `autocomplete_choices` as the user types
+    pub autocomplete: bool,
 }
 
-pub fn rank_command(command: &mut CreateApplicationCommand) -> &mut CreateApplicationCommand {
-    command
-        .name("rank")
-        .description("Guilds Mythic+ Rank")
-        .create_option(|option| {
-            option
-                .name("top")
-                .description("1-50")
-                .kind(CommandOptionType::Integer)
-                .required(false)
-        })
-        .create_option(|option| {
-            option
-                .name("guilds")
-                .description("all/Guild Name/... multiple guilds can be entered through ','")
-                .kind(CommandOptionType::String)
-                .required(false)
-        })
-        .create_option(|option| {
-            option
-                .name("classes")
-                .description("all/death knight/death knight:3/... ':3' means you want to specify the spec")
-                .kind(CommandOptionType::String)
-                .required(false)
-        })
-        .create_option(|option| {
-            option
-                .name("role")
-                .description("all/dps/healer/tank")
-                .kind(CommandOptionType::String)
-                .required(false)
-        })
-        .create_option(|option| {
-            option
-                .name("rio")
-                .description("0-3500")
-                .kind(CommandOptionType::Integer)
-                .required(false)
-        })
+/// The code that answers a command. `main.rs` dispatches on this with an exhaustive
+/// match, so a command can't be registered without something to answer it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommandHandler {
+    Guilds,
+    TopGuild,
+    Standings,
+    Rank,
+    Player,
+    Whois,
+    Search,
+    ClassTop,
+    TrackedGuilds,
+    ClassDistribution,
+    RefreshGuild,
+    Import,
+    Maintenance,
+    LastUpdated,
+    AboutUs,
+    Rules,
+    Help,
 }
 
+/// Slash command metadata shared by command registration, dispatch and /help
+pub struct CommandMeta {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub category: &'static str,
+    /// Only visible to and usable by server administrators
+    pub admin_only: bool,
+    pub options: &'static [OptionMeta],
+    pub handler: CommandHandler,
+}
+
+/// Every slash command the bot registers, in /help display order
+pub const COMMANDS: &[CommandMeta] = &[
+    CommandMeta {
+        name: "guilds",
+        description: "Guilds Raid Rank",
+        category: "Raids",
+        admin_only: false,
+        options: &[
+            OptionMeta {
+                name: "season",
+                description: "1/2/3/previous",
+                kind: CommandOptionType::String,
+                required: false,
+                autocomplete: false,
+            },
+            OptionMeta {
+                name: "limit",
+                description: "Number of guilds to display (or 'all' for full list)",
+                kind: CommandOptionType::String,
+                required: false,
+                autocomplete: false,
+            },
+            OptionMeta {
+                name: "difficulty",
+                description: "all/mythic/heroic/normal",
+                kind: CommandOptionType::String,
+                required: false,
+                autocomplete: false,
+            },
+        ],
+        handler: CommandHandler::Guilds,
+    },
+    CommandMeta {
+        name: "topguild",
+        description: "The guild leading raid progression",
+        category: "Raids",
+        admin_only: false,
+        options: &[OptionMeta {
+            name: "season",
+            description: "1/2/3/previous",
+            kind: CommandOptionType::String,
+            required: false,
+            autocomplete: false,
+        }],
+        handler: CommandHandler::TopGuild,
+    },
+    CommandMeta {
+        name: "standings",
+        description: "Guild raid progression grouped by difficulty",
+        category: "Raids",
+        admin_only: false,
+        options: &[OptionMeta {
+            name: "season",
+            description: "1/2/3/previous",
+            kind: CommandOptionType::String,
+            required: false,
+            autocomplete: false,
+        }],
+        handler: CommandHandler::Standings,
+    },
+    CommandMeta {
+        name: "rank",
+        description: "Guilds Mythic+ Rank",
+        category: "Mythic+",
+        admin_only: false,
+        options: &[
+            OptionMeta {
+                name: "top",
+                description: "1-50",
+                kind: CommandOptionType::Integer,
+                required: false,
+                autocomplete: false,
+            },
+            OptionMeta {
+                name: "guilds",
+                description: "all/Guild Name/Guild Name@Realm/... separate multiple guilds with ',', ';' or '|'",
+                kind: CommandOptionType::String,
+                required: false,
+                autocomplete: false,
+            },
+            OptionMeta {
+                name: "classes",
+                description: "all/mage,druid/death knight:frost/... ':frost' or ':2' selects the spec of one class",
+                kind: CommandOptionType::String,
+                required: false,
+                autocomplete: true,
+            },
+            OptionMeta {
+                name: "role",
+                description: "all/dps/healer/tank",
+                kind: CommandOptionType::String,
+                required: false,
+                autocomplete: false,
+            },
+            OptionMeta {
+                name: "rio",
+                description: "0-3500",
+                kind: CommandOptionType::Integer,
+                required: false,
+                autocomplete: false,
+            },
+            OptionMeta {
+                name: "rio_max",
+                description: "Upper RIO bound (rio-3500), e.g. rio 2500 + rio_max 3000",
+                kind: CommandOptionType::Integer,
+                required: false,
+                autocomplete: false,
+            },
+            OptionMeta {
+                name: "unguilded",
+                description: "Only players without a guild (ignores guilds)",
+                kind: CommandOptionType::Boolean,
+                required: false,
+                autocomplete: false,
+            },
+            OptionMeta {
+                name: "unique_players",
+                description: "Keep only the best character per name (same-name alts on other realms)",
+                kind: CommandOptionType::Boolean,
+                required: false,
+                autocomplete: false,
+            },
+            OptionMeta {
+                name: "realm",
+                description: "Realms to include, separated by ',', ';' or '|' (e.g. Tarren Mill,Silvermoon)",
+                kind: CommandOptionType::String,
+                required: false,
+                autocomplete: false,
+            },
+        ],
+        handler: CommandHandler::Rank,
+    },
+    CommandMeta {
+        name: "player",
+        description: "Player Mythic+ profile",
+        category: "Mythic+",
+        admin_only: false,
+        options: &[
+            OptionMeta {
+                name: "name",
+                description: "Character name",
+                kind: CommandOptionType::String,
+                required: true,
+                autocomplete: false,
+            },
+            OptionMeta {
+                name: "realm",
+                description: "Realm, e.g. Tarren Mill",
+                kind: CommandOptionType::String,
+                required: true,
+                autocomplete: false,
+            },
+            OptionMeta {
+                name: "live",
+                description: "Fetch fresh data from raider.io instead of the cached roster",
+                kind: CommandOptionType::Boolean,
+                required: false,
+                autocomplete: false,
+            },
+        ],
+        handler: CommandHandler::Player,
+    },
+    CommandMeta {
+        name: "whois",
+        description: "Find tracked characters with a name on any realm",
+        category: "Mythic+",
+        admin_only: false,
+        options: &[OptionMeta {
+            name: "name",
+            description: "Character name",
+            kind: CommandOptionType::String,
+            required: true,
+            autocomplete: false,
+        }],
+        handler: CommandHandler::Whois,
+    },
+    CommandMeta {
+        name: "search",
+        description: "Find tracked characters whose name contains some text",
+        category: "Mythic+",
+        admin_only: false,
+        options: &[OptionMeta {
+            name: "query",
+            description: "Part of a character name (at least 2 letters)",
+            kind: CommandOptionType::String,
+            required: true,
+            autocomplete: false,
+        }],
+        handler: CommandHandler::Search,
+    },
+    CommandMeta {
+        name: "classtop",
+        description: "The highest-RIO tracked player of every class",
+        category: "Mythic+",
+        admin_only: false,
+        options: &[],
+        handler: CommandHandler::ClassTop,
+    },
+    CommandMeta {
+        name: "trackedguilds",
+        description: "Every guild the bot tracks, grouped by realm",
+        category: "Raids",
+        admin_only: false,
+        options: &[],
+        handler: CommandHandler::TrackedGuilds,
+    },
+    CommandMeta {
+        name: "classdistribution",
+        description: "Class and role makeup of a guild's tracked roster",
+        category: "Raids",
+        admin_only: false,
+        options: &[OptionMeta {
+            name: "guild",
+            description: "Guild name",
+            kind: CommandOptionType::String,
+            required: true,
+            autocomplete: false,
+        }],
+        handler: CommandHandler::ClassDistribution,
+    },
+    CommandMeta {
+        name: "refreshguild",
+        description: "Refresh one guild's roster and RIO scores now",
+        category: "Admin",
+        admin_only: true,
+        options: &[
+            OptionMeta {
+                name: "name",
+                description: "Guild name",
+                kind: CommandOptionType::String,
+                required: true,
+                autocomplete: false,
+            },
+            OptionMeta {
+                name: "realm",
+                description: "Realm, e.g. Tarren Mill",
+                kind: CommandOptionType::String,
+                required: true,
+                autocomplete: false,
+            },
+        ],
+        handler: CommandHandler::RefreshGuild,
+    },
+    CommandMeta {
+        name: "import",
+        description: "Start tracking guilds from a pasted list",
+        category: "Admin",
+        admin_only: true,
+        options: &[OptionMeta {
+            name: "guilds",
+            description: "One guild per line or separated by ';': realm=...&name=... or Realm/Guild",
+            kind: CommandOptionType::String,
+            required: true,
+            autocomplete: false,
+        }],
+        handler: CommandHandler::Import,
+    },
+    CommandMeta {
+        name: "maintenance",
+        description: "Compact the database and refresh its query statistics",
+        category: "Admin",
+        admin_only: true,
+        options: &[],
+        handler: CommandHandler::Maintenance,
+    },
+    CommandMeta {
+        name: "lastupdated",
+        description: "When player data was last refreshed",
+        category: "General",
+        admin_only: false,
+        options: &[],
+        handler: CommandHandler::LastUpdated,
+    },
+    CommandMeta {
+        name: "about_us",
+        description: "About us",
+        category: "General",
+        admin_only: false,
+        options: &[],
+        handler: CommandHandler::AboutUs,
+    },
+    CommandMeta {
+        name: "rules",
+        description: "Rules",
+        category: "General",
+        admin_only: false,
+        options: &[],
+        handler: CommandHandler::Rules,
+    },
+    CommandMeta {
+        name: "help",
+        description: "Get information about available commands",
+        category: "General",
+        admin_only: false,
+        options: &[],
+        handler: CommandHandler::Help,
+    },
+];
 
-pub fn about_us_command(command: &mut CreateApplicationCommand) -> &mut CreateApplicationCommand {
-    command.name("about_us").description("About us")
+/// Registry entry for a command name, `None` for names the bot doesn't register
+pub fn find_command(name: &str) -> Option<&'static CommandMeta> {
+    COMMANDS.iter().find(|meta| meta.name == name)
 }
 
-pub fn rules_command(command: &mut CreateApplicationCommand) -> &mut CreateApplicationCommand {
-    command.name("rules").description("Rules")
+/// Build a slash command registration from its metadata
+pub fn register_command<'a>(
+    command: &'a mut CreateApplicationCommand,
+    meta: &CommandMeta,
+) -> &'a mut CreateApplicationCommand {
+    command.name(meta.name).description(meta.description);
+    if meta.admin_only {
+        command
+            .default_member_permissions(Permissions::ADMINISTRATOR)
+            .dm_permission(false);
+    }
+    for option_meta in meta.options {
+        command.create_option(|option| {
+            option
+                .name(option_meta.name)
+                .description(option_meta.description)
+                .kind(option_meta.kind)
+                .required(option_meta.required)
+                .set_autocomplete(option_meta.autocomplete)
+        });
+    }
+    command
 }
 
-pub fn help_command(command: &mut CreateApplicationCommand) -> &mut CreateApplicationCommand {
-    command.name("help").description("Get information about available commands")
+/// Pick the response language: configured locale first, then the user's Discord locale
+pub fn response_locale(command: &ApplicationCommandInteraction, config: &AppConfig) -> Locale {
+    config
+        .discord
+        .locale
+        .as_deref()
+        .and_then(Locale::from_code)
+        .or_else(|| Locale::from_code(&command.locale))
+        .unwrap_or_default()
 }
 
-pub async fn handle_guilds_command(command: &ApplicationCommandInteraction, config: &AppConfig) -> String {
-    let season = command
+/// Deadline for deferred command handlers. Interaction tokens expire after 15 minutes,
+/// so this leaves time to send the timeout notice.
+pub const COMMAND_TIMEOUT_SECS: u64 = 14 * 60;
+
+/// Run a command handler, giving up after `secs` so the interaction still gets an
+/// answer; `None` means the handler timed out
+pub async fn with_command_timeout<T>(handler: impl Future<Output = T>, secs: u64) -> Option<T> {
+    tokio::time::timeout(Duration::from_secs(secs), handler).await.ok()
+}
+
+pub async fn handle_guilds_command(command: &ApplicationCommandInteraction, config: &AppConfig, database: &Database, client: &RaiderIOClient, locale: Locale) -> Vec<String> {
+    let Some((season, tier)) = season_option(command, config) else {
+        return vec![t("guilds.invalid_season", locale)];
+    };
+
+    let limit_str = command
         .data
         .options
         .iter()
-        .find(|opt| opt.name == "season")
-        .and_then(|opt| opt.value.as_ref().and_then(|v| v.as_i64()))
-        .unwrap_or(config.raider_io.default_season as i64) as u8;
+        .find(|opt| opt.name == "limit")
+        .and_then(|opt| opt.value.as_ref().and_then(|v| v.as_str()));
 
-    let limit_str = command
+    let limit = match parse_guild_limit(limit_str, config.data.default_guild_limit, config.data.max_guild_limit, locale) {
+        Ok(limit) => limit,
+        Err(message) => return vec![message],
+    };
+
+    let difficulty_str = command
         .data
         .options
         .iter()
-        .find(|opt| opt.name == "limit")
-        .and_then(|opt| opt.value.as_ref().and_then(|v| v.as_str()))
-        .unwrap_or("10");
+        .find(|opt| opt.name == "difficulty")
+        .and_then(|opt| opt.value.as_ref().and_then(|v| v.as_str()));
 
-    let limit: Option<usize> = if limit_str == "all" {
-        None
-    } else {
-        limit_str.parse().ok()
+    let difficulty = match parse_difficulty_filter(difficulty_str, locale) {
+        Ok(difficulty) => difficulty,
+        Err(message) => return vec![message],
     };
 
-    match fetch_all_guild_data(RaidTier::from(season), config).await {
-        Ok(guilds) => {
-            if guilds.is_empty() {
-                format!("At the moment, there are no guilds with progression in season {}.", season)
+    match fetch_all_guild_data(tier, config, database, client).await {
+        Ok(fetch) => {
+            let mut messages = if fetch.guilds.iter().all(|guild| guild.progress.is_none()) {
+                vec![t_args("guilds.no_progression", locale, &[("season", &season)])]
             } else {
-                let sorted_guilds = sort_guilds(guilds);
-                format_guild_list(&sorted_guilds, limit, limit.is_none())
+                let sorted_guilds = sort_guilds(fetch.guilds);
+                match difficulty {
+                    Some(difficulty) => match filter_by_difficulty(sorted_guilds, difficulty) {
+                        filtered if filtered.is_empty() => {
+                            vec![t_args("guilds.no_difficulty", locale, &[("difficulty", &difficulty), ("season", &season)])]
+                        }
+                        filtered => format_guild_list(&filtered, limit, limit.is_none()),
+                    },
+                    None => format_guild_list(&sorted_guilds, limit, limit.is_none()),
+                }
+            };
+            if fetch.timed_out {
+                let note = t_args("guilds.partial", locale, &[("secs", &config.data.guilds_command_timeout_secs)]);
+                if let Some(last) = messages.last_mut() {
+                    last.push_str("\n\n");
+                    last.push_str(&note);
+                }
             }
+            messages
         }
         Err(e) => {
-            eprintln!("Error fetching guild data: {}", e);
-            format!("An error occurred while fetching guild data: {}. Please check that uaguildlist.txt exists and contains valid guild URLs.", e)
+            error!(error = %e, "Error fetching guild data");
+            vec![t_args("guilds.fetch_error", locale, &[("error", &e)])]
+        }
+    }
+}
+
+/// Discord's limit for a single embed field value
+const EMBED_FIELD_MAX_LEN: usize = 1024;
+
+/// `/standings`: an embed with one field per difficulty, or an error message
+pub async fn handle_standings_command(command: &ApplicationCommandInteraction, config: &AppConfig, database: &Database, client: &RaiderIOClient, locale: Locale) -> std::result::Result<CreateEmbed, String> {
+    let Some((season, tier)) = season_option(command, config) else {
+        return Err(t("guilds.invalid_season", locale));
+    };
+
+    let fetch = fetch_all_guild_data(tier, config, database, client)
+        .await
+        .map_err(|e| t_args("guilds.fetch_error", locale, &[("error", &e)]))?;
+    if fetch.guilds.iter().all(|guild| guild.progress.is_none()) {
+        return Err(t_args("guilds.no_progression", locale, &[("season", &season)]));
+    }
+
+    let mut embed = CreateEmbed::default();
+    embed.title(t_args("standings.title", locale, &[("season", &season)]));
+    for section in group_standings(fetch.guilds) {
+        embed.field(
+            format!("{} ({})", section.title, section.guilds.len()),
+            format_standings_section(&section.guilds, EMBED_FIELD_MAX_LEN),
+            false,
+        );
+    }
+    if fetch.timed_out {
+        // Footers don't render markdown, so drop the note's italics
+        let note = t_args("guilds.partial", locale, &[("secs", &config.data.guilds_command_timeout_secs)]);
+        embed.footer(|footer| footer.text(note.trim_matches('_')));
+    }
+    Ok(embed)
+}
+
+/// Read the `season` option (falling back to `raider_io.default_season`) as the season
+/// number and its raid tier; `None` when it isn't a known tier
+fn season_option(command: &ApplicationCommandInteraction, config: &AppConfig) -> Option<(i64, RaidTier)> {
+    let season = command
+        .data
+        .options
+        .iter()
+        .find(|opt| opt.name == "season")
+        .and_then(|opt| opt.value.as_ref())
+        .map(|value| value.as_str().map(str::to_string).unwrap_or_else(|| value.to_string()));
+
+    parse_season(season.as_deref(), config.raider_io.default_season)
+}
+
+/// Resolve a season option: a tier number, or "previous" for the tier before the default
+fn parse_season(input: Option<&str>, default_season: u8) -> Option<(i64, RaidTier)> {
+    let tier = match input.map(str::trim) {
+        None | Some("") => RaidTier::try_new(default_season).ok()?,
+        Some(input) if input.eq_ignore_ascii_case("previous") => RaidTier::try_new(default_season).ok()?.previous()?,
+        Some(input) => RaidTier::try_new(input.parse().ok()?).ok()?,
+    };
+    Some((tier.value() as i64, tier))
+}
+
+pub async fn handle_topguild_command(command: &ApplicationCommandInteraction, config: &AppConfig, database: &Database, client: &RaiderIOClient, locale: Locale) -> String {
+    let Some((season, tier)) = season_option(command, config) else {
+        return t("guilds.invalid_season", locale);
+    };
+
+    match fetch_all_guild_data(tier, config, database, client).await {
+        Ok(fetch) => {
+            let Some(guild) = top_guild(fetch.guilds) else {
+                return t_args("guilds.no_progression", locale, &[("season", &season)]);
+            };
+            let mut response = format_top_guild(&guild, season, locale);
+            if fetch.timed_out {
+                response.push_str("\n\n");
+                response.push_str(&t_args("guilds.partial", locale, &[("secs", &config.data.guilds_command_timeout_secs)]));
+            }
+            response
         }
+        Err(e) => t_args("guilds.fetch_error", locale, &[("error", &e)]),
+    }
+}
+
+/// The best-progressed guild, using the same order as /guilds; `None` if no guild has started
+fn top_guild(guilds: Vec<GuildData>) -> Option<GuildData> {
+    sort_guilds(guilds).into_iter().find(|guild| guild.progress.is_some())
+}
+
+fn format_top_guild(guild: &GuildData, season: i64, locale: Locale) -> String {
+    let world_rank = match &guild.rank {
+        Some(rank) => format!("#{}", rank.value()),
+        None => "Unranked".to_string(),
+    };
+    let mut details = t_args(
+        "topguild.details",
+        locale,
+        &[
+            ("season", &season),
+            ("name", &guild.name),
+            ("realm", &guild.realm.display_name()),
+            ("progress", &guild.progress_label()),
+            ("rank", &world_rank),
+            ("best", &format_best_progress(guild)),
+        ],
+    );
+    if let Some(boss) = &guild.current_boss {
+        details.push('\n');
+        details.push_str(&t_args(
+            "topguild.current_boss",
+            locale,
+            &[("boss", boss), ("percent", &format!("{:.1}", guild.best_percent))],
+        ));
+    }
+    details
+}
+
+/// Parse the /guilds `difficulty` option; unset or "all" keeps every difficulty
+fn parse_difficulty_filter(input: Option<&str>, locale: Locale) -> Result<Option<RaidDifficulty>, String> {
+    match input.map(str::trim) {
+        None | Some("") => Ok(None),
+        Some(value) if value.eq_ignore_ascii_case("all") => Ok(None),
+        Some(value) => value
+            .parse()
+            .map(Some)
+            .map_err(|_| t_args("guilds.unknown_difficulty", locale, &[("difficulty", &value)])),
+    }
+}
+
+/// Parse the /guilds `limit` option: a number (clamped to `max`) or "all" (`None`)
+fn parse_guild_limit(input: Option<&str>, default: usize, max: usize, locale: Locale) -> Result<Option<usize>, String> {
+    let Some(input) = input.map(str::trim) else {
+        return Ok(Some(default));
+    };
+
+    if input.eq_ignore_ascii_case("all") {
+        return Ok(None);
+    }
+
+    match input.parse::<usize>() {
+        Ok(0) => Err(t("guilds.limit_too_small", locale)),
+        Ok(limit) => Ok(Some(limit.min(max))),
+        Err(_) => Err(t_args("guilds.limit_invalid", locale, &[("limit", &input), ("max", &max)])),
     }
 }
 
-pub async fn handle_rank_command(command: &ApplicationCommandInteraction, database: &Database) -> String {
-    let messages = handle_rank_command_multi(command, database).await;
+pub async fn handle_rank_command(command: &ApplicationCommandInteraction, database: &Database, locale: Locale) -> String {
+    let messages = handle_rank_command_multi(command, database, locale).await;
     messages.into_iter().next().unwrap_or_else(|| "No results to display.".to_string())
 }
 
-pub async fn handle_rank_command_multi(command: &ApplicationCommandInteraction, database: &Database) -> Vec<String> {
+pub async fn handle_rank_command_multi(command: &ApplicationCommandInteraction, database: &Database, locale: Locale) -> Vec<String> {
     let top = command
         .data
         .options
         .iter()
         .find(|opt| opt.name == "top")
         .and_then(|opt| opt.value.as_ref().and_then(|v| v.as_i64()))
-        .unwrap_or(10) as usize;
+        .unwrap_or(10);
 
     let guilds = command
         .data
@@ -158,308 +648,1814 @@ pub async fn handle_rank_command_multi(command: &ApplicationCommandInteraction,
         .and_then(|opt| opt.value.as_ref().and_then(|v| v.as_str()))
         .unwrap_or("all");
 
+    let unguilded = command
+        .data
+        .options
+        .iter()
+        .find(|opt| opt.name == "unguilded")
+        .and_then(|opt| opt.value.as_ref().and_then(|v| v.as_bool()))
+        .unwrap_or(false);
+    let guilds = if unguilded { "unguilded" } else { guilds };
+
+    let unique_players = command
+        .data
+        .options
+        .iter()
+        .find(|opt| opt.name == "unique_players")
+        .and_then(|opt| opt.value.as_ref().and_then(|v| v.as_bool()))
+        .unwrap_or(false);
+
+    let realms = command
+        .data
+        .options
+        .iter()
+        .find(|opt| opt.name == "realm")
+        .and_then(|opt| opt.value.as_ref().and_then(|v| v.as_str()))
+        .map(parse_realm_filter)
+        .unwrap_or_default();
+
     let rio = command
         .data
         .options
         .iter()
         .find(|opt| opt.name == "rio")
         .and_then(|opt| opt.value.as_ref().and_then(|v| v.as_i64()))
-        .unwrap_or(2000) as u32;
+        .unwrap_or(2000);
 
-    if !(1..=50).contains(&top) {
-        return vec!["Error: The value of top must be between 1 and 50 inclusive.".to_string()];
-    }
+    let rio_max = command
+        .data
+        .options
+        .iter()
+        .find(|opt| opt.name == "rio_max")
+        .and_then(|opt| opt.value.as_ref().and_then(|v| v.as_i64()));
 
-    if rio > 3500 {
-        return vec!["Error: The value of rio must be between 0 and 3500 inclusive.".to_string()];
-    }
+    let (top, rio, rio_max) = match rank_bounds(top, rio, rio_max, locale) {
+        Ok(bounds) => bounds,
+        Err(message) => return vec![message],
+    };
 
     // Validate class and role like Python version
-    let (class_filter, spec_number) = parse_class_spec(classes);
-    
-    if !validate_class(&class_filter) {
-        return vec![format!("Class '{}' does not exist. Use the valid classes: all, death knight, demon hunter, druid, evoker, hunter, mage, monk, paladin, priest, rogue, shaman, warlock, warrior.", class_filter)];
-    }
+    let class_selection = match ClassSelection::parse(classes) {
+        Ok(selection) => selection,
+        Err(invalid) => {
+            return vec![t_args(
+                "rank.unknown_class",
+                locale,
+                &[("class", &invalid.join("', '")), ("valid", &class_option_names().join(", "))],
+            )];
+        }
+    };
     
     if !validate_role(role) {
-        return vec![format!("Role '{}' does not exist. Use the valid roles: all, dps, healer, tank.", role)];
+        return vec![t_args("rank.unknown_role", locale, &[("role", &role)])];
     }
 
-    // Get members from database
-    match database.get_all_members().await {
-        Ok(db_members) => {
-            let mut players: Vec<PlayerData> = db_members.iter().map(db_member_to_player_data).collect();
-            println!("Loaded {} players from database", players.len());
-            println!("Filtering: class='{}', role='{}', guilds='{}', rio>{}", class_filter, role, guilds, rio);
-            
-            // Filter by guild
-            if guilds != "all" {
-                let guild_list: Vec<String> = guilds
-                    .split(',')
-                    .map(|s| s.trim().to_lowercase())
-                    .collect();
-                players.retain(|p| {
-                    if guild_list.contains(&"none".to_string()) {
-                        p.guild.is_none()
-                    } else {
-                        p.guild
-                            .as_ref()
-                            .map(|g| guild_list.contains(&g.to_lowercase()))
-                            .unwrap_or(false)
-                    }
-                });
-            }
+    let filters = RankFilters {
+        top,
+        guilds: guilds.to_string(),
+        unguilded,
+        classes: class_selection,
+        role: role.to_lowercase(),
+        rio,
+        rio_max,
+        unique_players,
+        realms,
+    };
 
-            // Filter by class
-            if class_filter != "all" {
-                let before_count = players.len();
-                players.retain(|p| {
-                    p.class
-                        .as_ref()
-                        .map(|c| c.to_lowercase() == class_filter.to_lowercase())
-                        .unwrap_or(false)
-                });
-                println!("After class filter '{}': {} players (was {})", class_filter, players.len(), before_count);
-            }
+    rank_from_database(database, &filters, locale).await
+}
 
-            // Sort and filter by role/spec (following Python logic exactly)
-            if let Some(spec) = spec_number {
-                // Spec-based filtering
-                players.sort_by(|a, b| {
-                    let a_score = get_spec_score(a, spec - 1);
-                    let b_score = get_spec_score(b, spec - 1);
-                    b_score.partial_cmp(&a_score).unwrap_or(std::cmp::Ordering::Equal)
-                });
-                players.retain(|p| get_spec_score(p, spec - 1) > rio as f64);
-            } else {
-                // Role-based filtering - sort by role-specific RIO
-                if role != "all" {
-                    players.sort_by(|a, b| {
-                        let a_score = get_role_score(a, role);
-                        let b_score = get_role_score(b, role);
-                        b_score.partial_cmp(&a_score).unwrap_or(std::cmp::Ordering::Equal)
-                    });
-                } else {
-                    players.sort_by(|a, b| {
-                        let a_score = a.rio_all.value();
-                        let b_score = b.rio_all.value();
-                        b_score.partial_cmp(&a_score).unwrap_or(std::cmp::Ordering::Equal)
-                    });
-                }
-                
-                // Filter by role-specific RIO (exactly like Python)
-                let before_count = players.len();
-                if role != "all" {
-                    players.retain(|p| get_role_score(p, role) > rio as f64);
-                } else {
-                    players.retain(|p| p.rio_all.value() > rio as f64);
-                }
-                println!("After RIO filter (>{} for role '{}'): {} players (was {})", rio, role, players.len(), before_count);
-            }
+/// Check the raw /rank integers before narrowing them, so out-of-range values are
+/// rejected instead of wrapping around
+fn rank_bounds(top: i64, rio: i64, rio_max: Option<i64>, locale: Locale) -> Result<(usize, u32, Option<u32>), String> {
+    if !(1..=50).contains(&top) {
+        return Err(t("rank.top_range", locale));
+    }
+    if !(0..=3500).contains(&rio) {
+        return Err(t("rank.rio_range", locale));
+    }
+    let rio_max = match rio_max {
+        Some(max) if !(rio..=3500).contains(&max) => return Err(t("rank.rio_max_range", locale)),
+        Some(max) => Some(max as u32),
+        None => None,
+    };
+    Ok((top as usize, rio as u32, rio_max))
+}
+
+/// Rank the stored members, telling an empty database apart from filters that match nobody
+async fn rank_from_database(database: &Database, filters: &RankFilters, locale: Locale) -> Vec<String> {
+    match database.member_count().await {
+        Ok(0) => return vec![t("rank.no_members", locale)],
+        Ok(_) => {}
+        Err(e) => return vec![t_args("rank.no_data", locale, &[("error", &e)])],
+    }
 
-            players.truncate(top);
+    match database.get_all_members().await {
+        Ok(db_members) => {
+            let member_count = db_members.len();
+            let ranked = rank_players(db_members, filters);
+            debug!(
+                ranked = ranked.len(),
+                members = member_count,
+                class = %filters.classes.display(),
+                role = %filters.role,
+                guilds = %filters.guilds_label(),
+                realms = %filters.realms_label(),
+                rio = %filters.rio_band(),
+                "Ranked players"
+            );
 
-            if players.is_empty() {
-                return vec!["No players found matching the criteria.".to_string()];
+            if ranked.is_empty() {
+                return vec![t("rank.no_players", locale)];
             }
 
-            // Build multiple message chunks to handle Discord's 2000 character limit
-            let header = format!(
-                "**Player Rankings (Top {} | Classes: {} | Guilds: {} | Role: {} | RIO > {}):**",
-                top, classes, guilds, role, rio
-            );
+            let mut messages = format_rank_messages(&ranked, filters, locale);
+            let discord_limit = DISCORD_MESSAGE_LIMIT;
 
-            let table_header = "```\nRank Player                       Guild                              Server               Class/Spec               RIO Score\n──── ───────────────────────────── ────────────────────────────────── ──────────────────── ──────────────────────── ─────────\n";
-            let table_footer = "```";
-            
-            let total_players = players.len();
-            let discord_limit = 2000;
-            let estimated_row_size = 150;
-            let base_message_size = header.len() + table_header.len() + table_footer.len() + 100; // Increased safety margin
-            let calculated_max_rows = ((discord_limit - base_message_size) / estimated_row_size).max(1);
-            
-            // Ensure top 10 always fits in one message, but allow more for smaller requests
-            let max_rows_per_message = if total_players <= 10 {
-                total_players // Force all players into one message for top 10 or less
-            } else {
-                calculated_max_rows.max(10) // Ensure at least 10 rows per message for larger requests
-            };
-            
-            let mut messages = Vec::new();
-            
-            for chunk_start in (0..total_players).step_by(max_rows_per_message) {
-                let chunk_end = (chunk_start + max_rows_per_message).min(total_players);
-                let chunk_players = &players[chunk_start..chunk_end];
-                
-                let mut message = if chunk_start == 0 {
-                    format!("{}\n", header) // Only include header in first message
-                } else {
-                    format!("**Player Rankings (continued - {} to {}):**\n", chunk_start + 1, chunk_end)
-                };
-                
-                message.push_str(table_header);
-                
-                for (i, player) in chunk_players.iter().enumerate() {
-                    let global_index = chunk_start + i;
-                    let (display_role, score) = if let Some(spec) = spec_number {
-                        // For spec-based, show the role but use spec score
-                        (role.to_string(), get_spec_score(player, spec - 1))
-                    } else if role != "all" {
-                        // For role-specific, show role and use role score
-                        (role.to_string(), get_role_score(player, role))
-                    } else {
-                        // For "all", show "all" and use rio_all
-                        ("all".to_string(), player.rio_all.value())
-                    };
-
-                    let rank_num = format!("#{}", global_index + 1);
-                    let player_name = truncate_and_pad(&player.name.to_string(), 31);
-                    let guild_name = truncate_and_pad(&player.guild.as_deref().unwrap_or("No Guild"), 34);
-                    let server = truncate_and_pad(&player.realm.display_name(), 20);
-                    
-                    let class_spec = format!(
-                        "{} {}",
-                        player.active_spec_name.as_deref().unwrap_or("Unknown"),
-                        player.class.as_deref().unwrap_or("Unknown")
-                    );
-                    let class_spec_str = truncate_and_pad(&class_spec, 24);
-                    
-                    let score_display = if display_role == "all" {
-                        format!("{:.1} (Overall)", score)
-                    } else {
-                        format!("{:.1} ({})", score, display_role.to_uppercase())
-                    };
-
-                    message.push_str(&format!(
-                        "{:<4} {:<31} {:<34} {:<20} {:<24} {}\n",
-                        rank_num,
-                        player_name,
-                        guild_name,
-                        server,
-                        class_spec_str,
-                        score_display
-                    ));
+            if let Ok(Some(last_updated)) = database.members_last_updated().await {
+                let note = t_args(
+                    "rank.last_updated",
+                    locale,
+                    &[("ago", &locale::time_ago(chrono::Utc::now() - last_updated, locale))],
+                );
+                match messages.last_mut() {
+                    Some(last) if last.len() + note.len() < discord_limit => {
+                        last.push('\n');
+                        last.push_str(&note);
+                    }
+                    _ => messages.push(note),
                 }
-                
-                message.push_str(table_footer);
-                messages.push(message);
             }
             
             messages
         }
         Err(e) => {
-            vec![format!("No data to process: {}. Check that the database contains member data.", e)]
+            vec![t_args("rank.no_data", locale, &[("error", &e)])]
         }
     }
 }
 
+/// Validated `/rank` options
+#[derive(Debug)]
+struct RankFilters {
+    top: usize,
+    /// Guild filters separated by any of `LIST_SEPARATORS`, or "all"
+    guilds: String,
+    /// Only players without a guild; takes precedence over `guilds`
+    unguilded: bool,
+    classes: ClassSelection,
+    /// "all", "dps", "healer" or "tank"
+    role: String,
+    rio: u32,
+    rio_max: Option<u32>,
+    /// Keep only the best-scoring character of each name
+    unique_players: bool,
+    /// Only players on these realms (empty = all)
+    realms: Vec<RealmName>,
+}
 
-pub async fn handle_about_us_command() -> String {
-    "https://www.wowprogress.com/guild/eu/tarren-mill/Thorned+Horde".to_string()
+impl RankFilters {
+    fn guilds_label(&self) -> &str {
+        if self.unguilded { "unguilded" } else { &self.guilds }
+    }
+
+    fn realms_label(&self) -> String {
+        if self.realms.is_empty() {
+            return "all".to_string();
+        }
+        self.realms.iter().map(RealmName::display_name).collect::<Vec<_>>().join(", ")
+    }
+
+    fn rio_band(&self) -> String {
+        match self.rio_max {
+            Some(max) => format!("{}-{}", self.rio, max),
+            None => format!("> {}", self.rio),
+        }
+    }
+
+    /// The score players are ranked by: the selected spec, the role score, or overall RIO
+    fn score(&self, player: &PlayerData) -> f64 {
+        match (self.classes.spec, self.role.as_str()) {
+            // Specs are one-based; a zero never gets past parsing, but don't underflow on it
+            (Some(spec), _) => spec.checked_sub(1).map_or(0.0, |index| get_spec_score(player, index)),
+            (None, "all") => player.rio_all.value(),
+            (None, role) => get_role_score(player, role),
+        }
+    }
 }
 
-pub async fn handle_rules_command(config: &AppConfig) -> String {
-    if let (Some(server_id), Some(channel_id)) = (&config.discord.server_id, &config.discord.rules_channel_id) {
-        format!("Please check the rules in our dedicated channel: https://discord.com/channels/{}/{}", server_id, channel_id)
+/// One row of `/rank` output
+#[derive(Debug, Clone)]
+struct RankedPlayer {
+    /// One-based position
+    rank: usize,
+    player: PlayerData,
+    /// The score the player was ranked by
+    score: f64,
+    /// What `score` measures: "all", a role, or the spec name
+    score_label: String,
+}
+
+/// Filter, sort and truncate members for `/rank`
+fn rank_players(members: Vec<DbMember>, filters: &RankFilters) -> Vec<RankedPlayer> {
+    let mut players: Vec<PlayerData> = members.iter().map(PlayerData::from).collect();
+
+    if filters.unguilded {
+        retain_unguilded(&mut players);
+    } else if filters.guilds != "all" {
+        filter_players_by_guild(&mut players, &filters.guilds);
+    }
+
+    if !filters.realms.is_empty() {
+        players.retain(|p| filters.realms.contains(&p.realm));
+    }
+
+    if !filters.classes.classes.is_empty() {
+        filters.classes.retain_matching(&mut players);
+    }
+
+    players.sort_by(|a, b| {
+        filters.score(b).partial_cmp(&filters.score(a)).unwrap_or(std::cmp::Ordering::Equal)
+    });
+    if filters.unique_players {
+        retain_best_per_name(&mut players);
+    }
+    retain_rio_band(&mut players, filters.rio, filters.rio_max, |p| filters.score(p));
+    players.truncate(filters.top);
+
+    players
+        .into_iter()
+        .enumerate()
+        .map(|(i, player)| {
+            let score_label = match filters.classes.spec {
+                // For spec-based, label the score with the stored spec name
+                Some(spec) => spec
+                    .checked_sub(1)
+                    .and_then(|index| get_spec_name(&player, index))
+                    .map(str::to_string)
+                    .unwrap_or_else(|| format!("spec {}", spec)),
+                None => filters.role.clone(),
+            };
+            RankedPlayer { rank: i + 1, score: filters.score(&player), player, score_label }
+        })
+        .collect()
+}
+
+/// /rank table columns and the widest each may grow
+const RANK_TABLE: &[Column] = &[
+    Column::new("Rank", 4),
+    Column::new("Player", 31),
+    Column::new("Guild", 34),
+    Column::new("Server", 20),
+    Column::new("Class/Spec", 24),
+    Column::new("RIO Score", 30),
+];
+
+/// Render ranked players as one or more table messages within Discord's 2000 character limit
+fn format_rank_messages(ranked: &[RankedPlayer], filters: &RankFilters, locale: Locale) -> Vec<String> {
+    let header = t_args(
+        "rank.header",
+        locale,
+        &[
+            ("top", &filters.top),
+            ("classes", &filters.classes.display()),
+            ("guilds", &filters.guilds_label()),
+            ("realms", &filters.realms_label()),
+            ("role", &filters.role),
+            ("rio", &filters.rio_band()),
+        ],
+    );
+
+    let mut realm_names = RealmDisplayNames::default();
+    let cells: Vec<Vec<String>> = ranked.iter().map(|entry| {
+        let player = &entry.player;
+        let class_spec = format!(
+            "{} {}",
+            player.active_spec_name.as_deref().unwrap_or("Unknown"),
+            player.class.as_deref().unwrap_or("Unknown")
+        );
+        let score = MythicPlusScore::new(entry.score).display_rounded();
+        let score_display = if filters.classes.spec.is_some() {
+            format!("{}: {}", entry.score_label, score)
+        } else if entry.score_label == "all" {
+            format!("{} (Overall)", score)
+        } else {
+            format!("{} ({})", score, entry.score_label.to_uppercase())
+        };
+        vec![
+            format!("#{}", entry.rank),
+            player.name.to_string(),
+            player.guild.as_deref().unwrap_or("No Guild").to_string(),
+            realm_names.get(&player.realm).to_string(),
+            class_spec,
+            score_display,
+        ]
+    }).collect();
+    let layout = TableLayout::fit(RANK_TABLE, &cells);
+    let table_header = format!("```\n{}", layout.header());
+    let table_footer = "```";
+    
+    let total_players = ranked.len();
+    let discord_limit = DISCORD_MESSAGE_LIMIT;
+    let estimated_row_size = 150;
+    let base_message_size = header.len() + table_header.len() + table_footer.len() + 100; // Increased safety margin
+    let calculated_max_rows = ((discord_limit - base_message_size) / estimated_row_size).max(1);
+    
+    // Ensure top 10 always fits in one message, but allow more for smaller requests
+    let max_rows_per_message = if total_players <= 10 {
+        total_players // Force all players into one message for top 10 or less
     } else {
-        "Rules channel not configured. Please contact an administrator.".to_string()
+        calculated_max_rows.max(10) // Ensure at least 10 rows per message for larger requests
+    };
+    
+    let mut messages = Vec::new();
+    
+    let rows_per_message = max_rows_per_message.max(1);
+    for (chunk, rows) in ranked.chunks(rows_per_message).zip(cells.chunks(rows_per_message)) {
+        let chunk_start = chunk[0].rank - 1;
+        let chunk_end = chunk_start + chunk.len();
+        
+        let mut message = if chunk_start == 0 {
+            format!("{}\n", header) // Only include header in first message
+        } else {
+            format!("{}\n", t_args("rank.continued", locale, &[("from", &(chunk_start + 1)), ("to", &chunk_end)]))
+        };
+        
+        message.push_str(&table_header);
+        for row in rows {
+            message.push_str(&layout.row(row));
+        }
+        
+        message.push_str(table_footer);
+        messages.push(message);
     }
+
+    messages
 }
 
-pub async fn handle_help_command() -> String {
-    r#"**Available Commands:**
 
-/guilds - Get guild raid ranks in the current addon.
-       -season: Season number (1, 2, or 3, default is configurable).
+pub async fn handle_lastupdated_command(database: &Database, locale: Locale) -> String {
+    match database.members_last_updated().await {
+        Ok(Some(last_updated)) => t_args(
+            "lastupdated.refreshed",
+            locale,
+            &[
+                ("ago", &locale::time_ago(chrono::Utc::now() - last_updated, locale)),
+                ("time", &last_updated.format("%Y-%m-%d %H:%M UTC")),
+            ],
+        ),
+        Ok(None) => t("lastupdated.never", locale),
+        Err(e) => t_args("rank.no_data", locale, &[("error", &e)]),
+    }
+}
 
-/rank - Get player ranks in the current M+ season.            
-       -top: Number of top players to display (1-50, default is 10).
-       -guilds: Guilds to filter (all, guild names separated by ',').
-       -classes: Player classes to filter (all or specific class).
-       -role: Player role to filter (all, dps, healer, tank, or class:spec number).
-       -rio: Minimum RIO score to display (0-3500, default is 2000).
+pub async fn handle_player_command(command: &ApplicationCommandInteraction, database: &Database, client: &RaiderIOClient) -> String {
+    let name = command
+        .data
+        .options
+        .iter()
+        .find(|opt| opt.name == "name")
+        .and_then(|opt| opt.value.as_ref().and_then(|v| v.as_str()))
+        .unwrap_or("")
+        .trim();
 
+    let realm_input = command
+        .data
+        .options
+        .iter()
+        .find(|opt| opt.name == "realm")
+        .and_then(|opt| opt.value.as_ref().and_then(|v| v.as_str()))
+        .unwrap_or("");
 
-/about_us - Learn more about us.
+    let live = command
+        .data
+        .options
+        .iter()
+        .find(|opt| opt.name == "live")
+        .and_then(|opt| opt.value.as_ref().and_then(|v| v.as_bool()))
+        .unwrap_or(false);
 
-/rules - Rules.
+    if name.is_empty() {
+        return "Error: Please provide a character name.".to_string();
+    }
 
-/help - Get information about available commands.
+    let known_realms = match database.distinct_realms().await {
+        Ok(realms) => realms,
+        Err(e) => return format!("An error occurred while loading realms: {}", e),
+    };
 
-Source code - https://github.com/CemXokenc/uawowguilds."#.to_string()
-}
+    let realm = match resolve_realm(realm_input, &known_realms) {
+        Ok(realm) => realm,
+        Err(message) => return message,
+    };
+    let player_name = PlayerName::from(name);
 
-fn parse_class_spec(classes: &str) -> (String, Option<u8>) {
-    if classes.contains(':') {
-        let parts: Vec<&str> = classes.split(':').collect();
-        if parts.len() == 2 {
-            if let Ok(spec_num) = parts[1].parse::<u8>() {
-                if (1..=4).contains(&spec_num) {
-                    return (parts[0].to_string(), Some(spec_num));
-                }
+    // Prefer the roster collected by the parser unless a live lookup was requested
+    if !live {
+        match database.get_member(player_name.as_str(), realm.as_str()).await {
+            Ok(Some(member)) => {
+                return format!(
+                    "{}\n_Cached data as of {}. Use `live: true` for a fresh lookup._",
+                    format_player_profile(&PlayerData::from(&member)),
+                    member.updated_at.format("%Y-%m-%d %H:%M UTC")
+                );
             }
+            Ok(None) => {}
+            Err(e) => error!(error = %e, "Error loading cached player data"),
         }
     }
-    (classes.to_string(), None)
+
+    match client.fetch_player_data(&realm, &player_name, None, None).await {
+        Ok(Some(player)) => format_player_profile(&player),
+        Ok(None) => format!("Player {} was not found on {}.", player_name, realm.display_name()),
+        Err(e) => format!("An error occurred while fetching player data: {}", e),
+    }
 }
 
-fn validate_class(class_name: &str) -> bool {
-    let valid_classes = [
-        "all", "death knight", "demon hunter", "druid", "evoker", 
-        "hunter", "mage", "monk", "paladin", "priest", "rogue", 
-        "shaman", "warlock", "warrior"
-    ];
-    valid_classes.contains(&class_name.to_lowercase().as_str())
+pub async fn handle_whois_command(command: &ApplicationCommandInteraction, database: &Database) -> String {
+    let name = command
+        .data
+        .options
+        .iter()
+        .find(|opt| opt.name == "name")
+        .and_then(|opt| opt.value.as_ref().and_then(|v| v.as_str()))
+        .unwrap_or("")
+        .trim();
+
+    if name.is_empty() {
+        return "Error: Please provide a character name.".to_string();
+    }
+
+    match database.find_members_by_name(name).await {
+        Ok(members) => format_whois(&PlayerName::from(name), &members),
+        Err(e) => format!("An error occurred while searching for characters: {}", e),
+    }
 }
 
-fn validate_role(role_name: &str) -> bool {
-    let valid_roles = ["all", "dps", "healer", "tank"];
-    valid_roles.contains(&role_name.to_lowercase().as_str())
+/// Maximum characters listed by /search
+const SEARCH_MAX_RESULTS: usize = 20;
+
+pub async fn handle_search_command(command: &ApplicationCommandInteraction, database: &Database) -> String {
+    let query = command
+        .data
+        .options
+        .iter()
+        .find(|opt| opt.name == "query")
+        .and_then(|opt| opt.value.as_ref().and_then(|v| v.as_str()))
+        .unwrap_or("")
+        .trim();
+
+    if query.chars().count() < 2 {
+        return "Error: Please enter at least 2 letters to search for.".to_string();
+    }
+
+    // One extra row tells us whether the list was cut off
+    match database.search_members(query, SEARCH_MAX_RESULTS + 1).await {
+        Ok(members) => format_search_results(query, &members),
+        Err(e) => format!("An error occurred while searching for characters: {}", e),
+    }
 }
 
-fn get_role_score(player: &PlayerData, role: &str) -> f64 {
-    match role {
-        "dps" => player.rio_dps.value(),
-        "healer" => player.rio_healer.value(),
-        "tank" => player.rio_tank.value(),
-        _ => player.rio_all.value(),
+fn format_search_results(query: &str, members: &[DbMember]) -> String {
+    if members.is_empty() {
+        return format!("No tracked character name contains '{}'.", query);
+    }
+
+    let mut message = format!("**Characters matching '{}':**\n", query);
+    for (i, member) in members.iter().take(SEARCH_MAX_RESULTS).enumerate() {
+        message.push_str(&format!(
+            "{}. {} - {} - {} - RIO {}\n",
+            i + 1,
+            member.name,
+            RealmName::from(member.realm.as_str()).display_name(),
+            member.guild_name.as_deref().unwrap_or("No Guild"),
+            MythicPlusScore::new(member.rio_all).display_rounded()
+        ));
+    }
+    if members.len() > SEARCH_MAX_RESULTS {
+        message.push_str(&format!("Showing the top {} by RIO; refine the search to see others.", SEARCH_MAX_RESULTS));
     }
+    message.trim_end().to_string()
 }
 
-fn get_spec_score(player: &PlayerData, spec: u8) -> f64 {
-    match spec {
-        0 => player.spec_0.value(),
-        1 => player.spec_1.value(),
-        2 => player.spec_2.value(),
-        3 => player.spec_3.value(),
-        _ => 0.0,
+pub async fn handle_classtop_command(database: &Database) -> String {
+    match database.top_player_per_class().await {
+        Ok(top) => format_class_top(&top),
+        Err(e) => format!("An error occurred while loading the top players: {}", e),
     }
 }
 
-/// Helper function to truncate and pad strings to consistent length for monospace alignment
-fn truncate_and_pad(s: &str, target_len: usize) -> String {
-    if s.len() >= target_len {
-        format!("{}...", &s[..target_len.saturating_sub(3)])
-    } else {
-        format!("{}{}", s, " ".repeat(target_len - s.len()))
-    }
-}
-
-/// Convert DbMember to PlayerData for compatibility with existing logic
-fn db_member_to_player_data(db_member: &DbMember) -> PlayerData {
-    PlayerData {
-        name: PlayerName::from(db_member.name.clone()),
-        realm: RealmName::from(db_member.realm.clone()),
-        guild: db_member.guild_name.as_ref().map(|g| GuildName::from(g.clone())),
-        class: db_member.class.clone(),
-        active_spec_name: db_member.spec.clone(),
-        rio_all: MythicPlusScore::from(db_member.rio_all),
-        rio_dps: MythicPlusScore::from(db_member.rio_dps),
-        rio_healer: MythicPlusScore::from(db_member.rio_healer),
-        rio_tank: MythicPlusScore::from(db_member.rio_tank),
-        spec_0: MythicPlusScore::from(db_member.spec_0),
-        spec_1: MythicPlusScore::from(db_member.spec_1),
-        spec_2: MythicPlusScore::from(db_member.spec_2),
-        spec_3: MythicPlusScore::from(db_member.spec_3),
-    }
-}
\ No newline at end of file
+/// One line per class in canonical order, "none" where no member of the class is tracked
+fn format_class_top(top: &[(String, DbMember)]) -> String {
+    let mut message = "**Top player per class:**\n".to_string();
+    for class in WowClass::all() {
+        let line = match top.iter().find(|(stored, _)| class.matches(stored)) {
+            Some((_, member)) => format!(
+                "{} - {} - RIO {}",
+                member.name,
+                RealmName::from(member.realm.as_str()).display_name(),
+                MythicPlusScore::new(member.rio_all).display_rounded()
+            ),
+            None => "none".to_string(),
+        };
+        message.push_str(&format!("**{}**: {}\n", class, line));
+    }
+    message.trim_end().to_string()
+}
+
+/// `/trackedguilds`: every tracked guild, grouped by realm. Long lists are split into
+/// several messages where they are sent.
+pub async fn handle_trackedguilds_command(database: &Database) -> String {
+    match database.get_all_guilds().await {
+        Ok(guilds) => format_tracked_guilds(&guilds),
+        Err(e) => format!("An error occurred while loading the tracked guilds: {}", e),
+    }
+}
+
+/// A total, then one section per realm (alphabetical) listing its guilds by name
+fn format_tracked_guilds(guilds: &[GuildUrl]) -> String {
+    if guilds.is_empty() {
+        return "No guilds are tracked yet.".to_string();
+    }
+
+    let mut by_realm: HashMap<String, Vec<&str>> = HashMap::new();
+    for guild in guilds {
+        by_realm.entry(guild.realm.display_name()).or_default().push(guild.name.as_str());
+    }
+    let mut realms: Vec<(String, Vec<&str>)> = by_realm.into_iter().collect();
+    realms.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let mut message = format!("**Tracked guilds ({}):**\n", guilds.len());
+    for (realm, mut names) in realms {
+        names.sort_unstable();
+        message.push_str(&format!("\n**{}** ({})\n", realm, names.len()));
+        for name in names {
+            message.push_str(&format!("{}\n", name));
+        }
+    }
+    message.trim_end().to_string()
+}
+
+/// Bucket for members whose class or spec isn't recognized
+const UNKNOWN_BUCKET: &str = "Unknown";
+
+/// Class and role counts for a roster
+#[derive(Debug, PartialEq)]
+struct ClassDistribution {
+    /// Most common class first
+    classes: Vec<(String, usize)>,
+    /// Tanks, healers, melee, ranged, then unknown when any member couldn't be classified
+    roles: Vec<(&'static str, usize)>,
+}
+
+fn class_distribution(members: &[DbMember]) -> ClassDistribution {
+    let mut classes: HashMap<String, usize> = HashMap::new();
+    let mut roles = [("Tanks", 0), ("Healers", 0), ("Melee", 0), ("Ranged", 0), (UNKNOWN_BUCKET, 0)];
+
+    for member in members {
+        let class = member.class.as_deref().and_then(|class| class.parse::<WowClass>().ok());
+        let class_label = class.as_ref().map_or_else(|| UNKNOWN_BUCKET.to_string(), WowClass::to_string);
+        *classes.entry(class_label).or_default() += 1;
+
+        let role = class
+            .as_ref()
+            .zip(member.spec.as_deref())
+            .and_then(|(class, spec)| CombatRole::for_spec(class, spec));
+        let bucket = match role {
+            Some(CombatRole::Tank) => 0,
+            Some(CombatRole::Healer) => 1,
+            Some(CombatRole::Melee) => 2,
+            Some(CombatRole::Ranged) => 3,
+            None => 4,
+        };
+        roles[bucket].1 += 1;
+    }
+
+    let mut classes: Vec<(String, usize)> = classes.into_iter().collect();
+    classes.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    let mut roles = roles.to_vec();
+    if roles[4].1 == 0 {
+        roles.pop();
+    }
+    ClassDistribution { classes, roles }
+}
+
+pub async fn handle_classdistribution_command(command: &ApplicationCommandInteraction, database: &Database) -> std::result::Result<CreateEmbed, String> {
+    let guild = command
+        .data
+        .options
+        .iter()
+        .find(|opt| opt.name == "guild")
+        .and_then(|opt| opt.value.as_ref().and_then(|v| v.as_str()))
+        .unwrap_or("")
+        .trim();
+
+    if guild.is_empty() {
+        return Err("Error: Please provide a guild name.".to_string());
+    }
+
+    let members = database
+        .get_guild_members(guild)
+        .await
+        .map_err(|e| format!("An error occurred while loading the roster: {}", e))?;
+    // Show the guild name as stored rather than as typed
+    let Some(guild_name) = members.first().and_then(|member| member.guild_name.clone()) else {
+        return Err(format!("No tracked members found for guild '{}'.", guild));
+    };
+
+    let distribution = class_distribution(&members);
+    let lines = |counts: &mut dyn Iterator<Item = (&str, usize)>| {
+        counts.map(|(label, count)| format!("{}: {}", label, count)).collect::<Vec<_>>().join("\n")
+    };
+
+    let mut embed = CreateEmbed::default();
+    embed.title(format!("Class distribution: {} ({} members)", guild_name, members.len()));
+    embed.field("Roles", lines(&mut distribution.roles.iter().copied()), true);
+    embed.field(
+        "Classes",
+        lines(&mut distribution.classes.iter().map(|(class, count)| (class.as_str(), *count))),
+        true,
+    );
+    Ok(embed)
+}
+
+/// Whether the invoker may run admin commands: a user listed in `discord.admin_user_ids`
+/// or a member with the Administrator permission. Discord hides admin commands from
+/// everyone else, but server overrides can re-grant them.
+fn invoked_by_admin(command: &ApplicationCommandInteraction, config: &DiscordConfig) -> bool {
+    config.is_admin(command.user.id.0)
+        || command
+            .member
+            .as_ref()
+            .and_then(|member| member.permissions)
+            .is_some_and(|permissions| permissions.administrator())
+}
+
+/// Reply for non-admins invoking an admin command
+const NOT_AUTHORIZED: &str = "You are not authorized to use this command.";
+
+pub async fn handle_refreshguild_command(command: &ApplicationCommandInteraction, config: &AppConfig, database: &Database, client: &RaiderIOClient) -> String {
+    if !invoked_by_admin(command, &config.discord) {
+        return NOT_AUTHORIZED.to_string();
+    }
+
+    let option = |name: &str| {
+        command
+            .data
+            .options
+            .iter()
+            .find(|opt| opt.name == name)
+            .and_then(|opt| opt.value.as_ref().and_then(|v| v.as_str()))
+            .unwrap_or("")
+            .trim()
+    };
+    let (name, realm) = (option("name"), option("realm"));
+    if name.is_empty() || realm.is_empty() {
+        return "Error: Please provide a guild name and realm.".to_string();
+    }
+
+    let guild = GuildUrl::new(realm, name);
+    match crate::parser::refresh_guild(config, database, client, &guild).await {
+        Ok(count) => format!("Refreshed {} members of {} on {}.", count, guild.name, guild.realm),
+        Err(e) => format!("An error occurred while refreshing {}: {}", guild, e),
+    }
+}
+
+pub async fn handle_import_command(command: &ApplicationCommandInteraction, config: &AppConfig, database: &Database) -> String {
+    if !invoked_by_admin(command, &config.discord) {
+        return NOT_AUTHORIZED.to_string();
+    }
+
+    let guilds = command
+        .data
+        .options
+        .iter()
+        .find(|opt| opt.name == "guilds")
+        .and_then(|opt| opt.value.as_ref().and_then(|v| v.as_str()))
+        .unwrap_or("");
+
+    // Discord's text input is single-line, so ';' also separates entries
+    let summary = database.import_guilds_from_text(&guilds.replace(';', "\n")).await;
+    format_import_summary(&summary)
+}
+
+fn format_import_summary(summary: &GuildImportSummary) -> String {
+    let mut message = format!(
+        "Imported {} guilds ({} already tracked, {} errors).",
+        summary.imported, summary.skipped, summary.errors
+    );
+    for rejected in &summary.rejected {
+        message.push_str(&format!("\n- {}", rejected));
+    }
+    message
+}
+
+/// Run VACUUM and ANALYZE, reporting how much the database file shrank
+pub async fn handle_maintenance_command(command: &ApplicationCommandInteraction, config: &AppConfig, database: &Database) -> String {
+    if !invoked_by_admin(command, &config.discord) {
+        return NOT_AUTHORIZED.to_string();
+    }
+
+    let result = async {
+        let before = database.file_size().await?;
+        database.vacuum().await?;
+        database.analyze().await?;
+        let after = database.file_size().await?;
+        Ok::<_, crate::error::BotError>((before, after))
+    }
+    .await;
+
+    match result {
+        Ok((before, after)) => format_maintenance_report(before, after),
+        Err(e) => format!("Database maintenance failed: {}", e),
+    }
+}
+
+fn format_maintenance_report(before: u64, after: u64) -> String {
+    let mib = |bytes: u64| bytes as f64 / (1024.0 * 1024.0);
+    format!(
+        "Database vacuumed and analyzed: {:.2} MiB → {:.2} MiB ({:.2} MiB freed).",
+        mib(before),
+        mib(after),
+        mib(before.saturating_sub(after))
+    )
+}
+
+/// Maximum characters listed by /whois before the list is cut off
+const WHOIS_MAX_RESULTS: usize = 20;
+
+/// Render /whois results: a hint when nothing matches, the profile for a single
+/// match, or a realm list to pick from when the name exists on several realms
+fn format_whois(name: &PlayerName, members: &[DbMember]) -> String {
+    match members {
+        [] => format!(
+            "No tracked character named {}. Only members of tracked guilds are stored; use `/player` with a realm for anyone else.",
+            name
+        ),
+        [member] => format_player_profile(&PlayerData::from(member)),
+        _ => {
+            let mut message = format!("**{} characters named {}:**\n", members.len(), name);
+            for (i, member) in members.iter().take(WHOIS_MAX_RESULTS).enumerate() {
+                message.push_str(&format!(
+                    "{}. {} - {} - RIO {}\n",
+                    i + 1,
+                    RealmName::from(member.realm.as_str()).display_name(),
+                    member.guild_name.as_deref().unwrap_or("No Guild"),
+                    MythicPlusScore::new(member.rio_all).display_rounded()
+                ));
+            }
+            if members.len() > WHOIS_MAX_RESULTS {
+                message.push_str(&format!("...and {} more\n", members.len() - WHOIS_MAX_RESULTS));
+            }
+            message.push_str(&format!("Use `/player name:{} realm:<realm>` for a full profile.", name));
+            message
+        }
+    }
+}
+
+fn format_player_profile(player: &PlayerData) -> String {
+    format!(
+        "**{}** ({}) - {}\n{} {}\nRIO: {} (DPS {} | Healer {} | Tank {})",
+        player.name,
+        player.realm.display_name(),
+        player.guild.as_deref().unwrap_or("No Guild"),
+        player.active_spec_name.as_deref().unwrap_or("Unknown"),
+        player.class.as_deref().unwrap_or("Unknown"),
+        player.rio_all.display_rounded(),
+        player.rio_dps.display_rounded(),
+        player.rio_healer.display_rounded(),
+        player.rio_tank.display_rounded()
+    )
+}
+
+pub async fn handle_about_us_command() -> String {
+    "https://www.wowprogress.com/guild/eu/tarren-mill/Thorned+Horde".to_string()
+}
+
+pub async fn handle_rules_command(config: &AppConfig) -> String {
+    if let (Some(server_id), Some(channel_id)) = (&config.discord.server_id, &config.discord.rules_channel_id) {
+        format!("Please check the rules in our dedicated channel: https://discord.com/channels/{}/{}", server_id, channel_id)
+    } else {
+        "Rules channel not configured. Please contact an administrator.".to_string()
+    }
+}
+
+pub async fn handle_help_command(locale: Locale) -> CreateEmbed {
+    let mut embed = CreateEmbed::default();
+    embed.title(t("help.title", locale));
+
+    let required_label = format!(" ({})", t("help.required", locale));
+    for meta in COMMANDS {
+        let description = locale::lookup(&format!("help.{}", meta.name), locale).unwrap_or(meta.description);
+        let mut value = description.to_string();
+        for option in meta.options {
+            let required = if option.required { required_label.as_str() } else { "" };
+            value.push_str(&format!("\n• `{}`{}: {}", option.name, required, option.description));
+        }
+        embed.field(format!("{} · /{}", meta.category, meta.name), value, false);
+    }
+
+    embed
+}
+
+/// Characters that separate the entries of the list options (`/rank guilds`, `classes`
+/// and `realm`); any of them may be used, since some guild names read badly next to a comma
+const LIST_SEPARATORS: [char; 3] = [',', ';', '|'];
+
+/// Parsed `/rank` classes option: a class list, or a single `class:spec`
+#[derive(Debug, PartialEq)]
+struct ClassSelection {
+    /// Selected classes; empty means all classes
+    classes: Vec<WowClass>,
+    /// One-based spec number, only set for a single `class:spec` entry
+    spec: Option<u8>,
+}
+
+impl ClassSelection {
+    /// Parse the option, returning the entries that aren't valid classes on failure
+    fn parse(input: &str) -> Result<Self, Vec<String>> {
+        let entries: Vec<&str> = input.split(LIST_SEPARATORS).map(str::trim).filter(|e| !e.is_empty()).collect();
+
+        if let [entry] = entries.as_slice() {
+            let (class, spec) = parse_class_spec(entry);
+            if class.trim().eq_ignore_ascii_case("all") {
+                return Ok(Self { classes: Vec::new(), spec: None });
+            }
+            return match class.parse::<WowClass>() {
+                Ok(class) => Ok(Self { classes: vec![class], spec }),
+                Err(_) => Err(vec![class]),
+            };
+        }
+
+        if entries.is_empty() || entries.iter().any(|entry| entry.eq_ignore_ascii_case("all")) {
+            return Ok(Self { classes: Vec::new(), spec: None });
+        }
+
+        let (classes, invalid): (Vec<_>, Vec<_>) = entries
+            .iter()
+            .map(|entry| entry.parse::<WowClass>().map_err(|_| entry.to_string()))
+            .partition(|parsed| parsed.is_ok());
+        if !invalid.is_empty() {
+            return Err(invalid.into_iter().filter_map(|parsed| parsed.err()).collect());
+        }
+        Ok(Self { classes: classes.into_iter().filter_map(|parsed| parsed.ok()).collect(), spec: None })
+    }
+
+    /// Header text for the selection
+    fn display(&self) -> String {
+        match (self.classes.as_slice(), self.spec) {
+            ([], _) => "all".to_string(),
+            ([class], Some(spec)) => match (spec as usize).checked_sub(1).and_then(|index| class.spec_names().get(index)) {
+                Some(spec_name) => format!("{}:{}", class.as_str().to_lowercase(), spec_name),
+                None => format!("{}:{}", class.as_str().to_lowercase(), spec),
+            },
+            (classes, _) => classes.iter().map(|class| class.as_str().to_lowercase()).collect::<Vec<_>>().join(", "),
+        }
+    }
+
+    fn retain_matching(&self, players: &mut Vec<PlayerData>) {
+        players.retain(|p| {
+            p.class
+                .as_ref()
+                .map(|c| self.classes.iter().any(|class| class.matches(c)))
+                .unwrap_or(false)
+        });
+    }
+}
+
+/// Values accepted by the `classes` option (before any `:spec` suffix)
+fn class_option_names() -> Vec<String> {
+    std::iter::once("all".to_string())
+        .chain(WowClass::all().map(|class| class.as_str().to_lowercase()))
+        .collect()
+}
+
+/// Maximum choices Discord accepts in an autocomplete response
+const AUTOCOMPLETE_MAX_CHOICES: usize = 25;
+
+/// Suggestions for the `/rank classes` option: completes the last entry of the list
+fn class_autocomplete_choices(input: &str) -> Vec<String> {
+    // Keep whichever separator the user typed
+    let (prefix, partial) = match input.rfind(LIST_SEPARATORS) {
+        Some(at) => {
+            let (before, rest) = input.split_at(at);
+            let mut rest = rest.chars();
+            let separator = rest.next().unwrap_or(',');
+            (format!("{}{} ", before.trim_end(), separator), rest.as_str().trim().to_lowercase())
+        }
+        None => (String::new(), input.trim().to_lowercase()),
+    };
+
+    class_option_names()
+        .into_iter()
+        // "all" can't be combined with other classes
+        .filter(|name| prefix.is_empty() || name != "all")
+        .filter(|name| name.starts_with(&partial))
+        .map(|name| format!("{}{}", prefix, name))
+        .take(AUTOCOMPLETE_MAX_CHOICES)
+        .collect()
+}
+
+/// Autocomplete suggestions for the focused option of a command
+pub fn autocomplete_choices(interaction: &AutocompleteInteraction) -> Vec<String> {
+    let Some(focused) = interaction.data.options.iter().find(|opt| opt.focused) else {
+        return Vec::new();
+    };
+    let input = focused.value.as_ref().and_then(|v| v.as_str()).unwrap_or("");
+
+    match (interaction.data.name.as_str(), focused.name.as_str()) {
+        ("rank", "classes") => class_autocomplete_choices(input),
+        _ => Vec::new(),
+    }
+}
+
+/// Map a class and spec name to its zero-based `spec_N` slot
+fn class_spec_index(class: &str, spec_name: &str) -> Option<u8> {
+    let spec_name = spec_name.trim();
+    class_spec_names(class)?
+        .iter()
+        .position(|spec| spec.eq_ignore_ascii_case(spec_name))
+        .map(|index| index as u8)
+}
+
+/// Split `class:spec` into the class and a one-based spec number. The spec may be
+/// given by name (`death knight:frost`) or by number (`death knight:2`); numbers outside
+/// 1-4 (including 0) aren't a spec, so the whole entry is returned as an unknown class.
+fn parse_class_spec(classes: &str) -> (String, Option<u8>) {
+    if classes.contains(':') {
+        let parts: Vec<&str> = classes.split(':').collect();
+        if parts.len() == 2 {
+            let class = parts[0].trim();
+            if let Ok(spec_num) = parts[1].trim().parse::<u8>() {
+                if (1..=4).contains(&spec_num) {
+                    return (class.to_string(), Some(spec_num));
+                }
+            } else if let Some(index) = class_spec_index(class, parts[1]) {
+                return (class.to_string(), Some(index + 1));
+            }
+        }
+    }
+    (classes.to_string(), None)
+}
+
+/// Resolve user-supplied realm input against the tracked realms, suggesting the
+/// closest known realm for typos
+fn resolve_realm(input: &str, known_realms: &[RealmName]) -> Result<RealmName, String> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Err("Error: Please provide a realm name.".to_string());
+    }
+
+    let realm = RealmName::from(input);
+    if known_realms.contains(&realm) {
+        return Ok(realm);
+    }
+
+    let closest = known_realms
+        .iter()
+        .map(|known| (levenshtein(realm.as_str(), known.as_str()), known))
+        .min_by_key(|(distance, _)| *distance);
+
+    match closest {
+        // Only suggest when the typo is small relative to the realm name
+        Some((distance, known)) if distance <= (known.chars().count() / 3).max(2) => Err(format!(
+            "Unknown realm '{}'. Did you mean '{}'?",
+            input,
+            known.display_name()
+        )),
+        _ => Err(format!(
+            "Unknown realm '{}'. Tracked realms: {}.",
+            input,
+            known_realms.iter().map(|r| r.display_name()).collect::<Vec<_>>().join(", ")
+        )),
+    }
+}
+
+/// Edit distance between two strings (by character)
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b_chars: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b_chars.len()).collect();
+
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b_chars.len() + 1];
+        for (j, b_char) in b_chars.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+
+    previous[b_chars.len()]
+}
+
+fn validate_role(role_name: &str) -> bool {
+    let valid_roles = ["all", "dps", "healer", "tank"];
+    valid_roles.contains(&role_name.to_lowercase().as_str())
+}
+
+fn get_role_score(player: &PlayerData, role: &str) -> f64 {
+    match role {
+        "dps" => player.rio_dps.value(),
+        "healer" => player.rio_healer.value(),
+        "tank" => player.rio_tank.value(),
+        _ => player.rio_all.value(),
+    }
+}
+
+/// A `/rank` guild filter entry: `Guild Name` or `Guild Name@Realm`
+struct GuildFilter {
+    name: String,
+    realm: Option<RealmName>,
+}
+
+impl GuildFilter {
+    fn parse(input: &str) -> Self {
+        match input.rsplit_once('@') {
+            Some((name, realm)) if !realm.trim().is_empty() => Self {
+                name: name.trim().to_lowercase(),
+                realm: Some(RealmName::from(realm.trim())),
+            },
+            _ => Self {
+                name: input.trim().to_lowercase(),
+                realm: None,
+            },
+        }
+    }
+
+    fn matches(&self, player: &PlayerData) -> bool {
+        let guild_matches = player
+            .guild
+            .as_ref()
+            .map(|g| g.to_lowercase() == self.name)
+            .unwrap_or(false);
+        guild_matches && self.realm.as_ref().is_none_or(|realm| player.guild_realm.as_ref() == Some(realm))
+    }
+}
+
+/// Realms from the /rank `realm` list; blanks and "all" are ignored
+fn parse_realm_filter(input: &str) -> Vec<RealmName> {
+    input
+        .split(LIST_SEPARATORS)
+        .map(str::trim)
+        .filter(|realm| !realm.is_empty() && !realm.eq_ignore_ascii_case("all"))
+        .map(RealmName::from)
+        .collect()
+}
+
+/// Keep only players without a guild
+fn retain_unguilded(players: &mut Vec<PlayerData>) {
+    players.retain(|p| p.guild.is_none());
+}
+
+/// Keep the first (best-ranked) character of each name, case-insensitively. raider.io
+/// doesn't link characters to accounts, so this only catches alts that share a name
+/// across realms; differently named alts are still listed separately.
+fn retain_best_per_name(players: &mut Vec<PlayerData>) {
+    let mut seen = HashSet::new();
+    players.retain(|p| seen.insert(p.name.as_str().to_lowercase()));
+}
+
+/// Keep players whose guild matches one of the listed filters. A bare "none"
+/// keeps unguilded players; it predates the `unguilded` option and is kept for old usage.
+fn filter_players_by_guild(players: &mut Vec<PlayerData>, guilds: &str) {
+    let filters: Vec<GuildFilter> = guilds.split(LIST_SEPARATORS).map(GuildFilter::parse).collect();
+    if filters.iter().any(|f| f.name == "none" && f.realm.is_none()) {
+        retain_unguilded(players);
+    } else {
+        players.retain(|p| filters.iter().any(|f| f.matches(p)));
+    }
+}
+
+/// Keep players scoring above `min` and, when given, at most `max`
+fn retain_rio_band(players: &mut Vec<PlayerData>, min: u32, max: Option<u32>, score: impl Fn(&PlayerData) -> f64) {
+    players.retain(|p| {
+        let score = score(p);
+        score > min as f64 && max.is_none_or(|max| score <= max as f64)
+    });
+}
+
+/// Score for the spec in slot `spec`. raider.io often leaves `spec_N` at zero even for
+/// ranked players, so a zero spec score falls back to the role score when the player's
+/// active spec plays the same role as the requested one.
+fn get_spec_score(player: &PlayerData, spec: u8) -> f64 {
+    let score = match spec {
+        0 => player.spec_0.value(),
+        1 => player.spec_1.value(),
+        2 => player.spec_2.value(),
+        3 => player.spec_3.value(),
+        _ => 0.0,
+    };
+    if score > 0.0 {
+        return score;
+    }
+
+    let requested = get_spec_name(player, spec).or_else(|| {
+        let specs = class_spec_names(player.class.as_deref()?)?;
+        specs.get(spec as usize).copied()
+    });
+    match (requested, player.active_spec_name.as_deref()) {
+        (Some(requested), Some(active)) if Role::for_spec(requested) == Role::for_spec(active) => {
+            get_role_score(player, &Role::for_spec(requested).to_string())
+        }
+        _ => score,
+    }
+}
+
+fn get_spec_name(player: &PlayerData, spec: u8) -> Option<&str> {
+    match spec {
+        0 => player.spec_0_name.as_deref(),
+        1 => player.spec_1_name.as_deref(),
+        2 => player.spec_2_name.as_deref(),
+        3 => player.spec_3_name.as_deref(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::GuildName;
+
+    #[test]
+    fn test_command_registry_is_valid() {
+        for (i, meta) in COMMANDS.iter().enumerate() {
+            assert!(COMMANDS[i + 1..].iter().all(|other| other.name != meta.name), "duplicate command {}", meta.name);
+            assert!(meta.description.len() <= 100, "description too long for /{}", meta.name);
+            // Discord rejects required options listed after optional ones
+            let first_optional = meta.options.iter().position(|o| !o.required).unwrap_or(meta.options.len());
+            assert!(meta.options[first_optional..].iter().all(|o| !o.required), "/{} option order", meta.name);
+        }
+    }
+
+    #[test]
+    fn test_every_command_dispatches_to_its_own_handler() {
+        for (i, meta) in COMMANDS.iter().enumerate() {
+            assert_eq!(find_command(meta.name).map(|found| found.handler), Some(meta.handler), "/{}", meta.name);
+            // A copy-pasted entry would answer with another command's handler
+            assert!(
+                COMMANDS[i + 1..].iter().all(|other| other.handler != meta.handler),
+                "/{} shares its handler",
+                meta.name
+            );
+        }
+        assert!(find_command("tournament").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_help_embed_lists_every_command() {
+        let embed = handle_help_command(Locale::En).await;
+        let fields = embed.0.get("fields").and_then(|f| f.as_array()).unwrap();
+        assert_eq!(fields.len(), COMMANDS.len());
+        for meta in COMMANDS {
+            let title = format!("{} · /{}", meta.category, meta.name);
+            assert!(fields.iter().any(|field| field["name"] == title.as_str()), "missing /{}", meta.name);
+        }
+    }
+
+    #[test]
+    fn test_class_spec_index() {
+        assert_eq!(class_spec_index("death knight", "blood"), Some(0));
+        assert_eq!(class_spec_index("death knight", "frost"), Some(1));
+        assert_eq!(class_spec_index("Mage", "Frost"), Some(2));
+        assert_eq!(class_spec_index("druid", "restoration"), Some(3));
+        assert_eq!(class_spec_index("hunter", "beast mastery"), Some(0));
+        assert_eq!(class_spec_index("mage", "holy"), None);
+        assert_eq!(class_spec_index("bard", "frost"), None);
+    }
+
+    #[test]
+    fn test_parse_class_spec_accepts_names_and_numbers() {
+        assert_eq!(parse_class_spec("death knight:frost"), ("death knight".to_string(), Some(2)));
+        assert_eq!(parse_class_spec("death knight:3"), ("death knight".to_string(), Some(3)));
+        assert_eq!(parse_class_spec("priest:Shadow"), ("priest".to_string(), Some(3)));
+        assert_eq!(parse_class_spec("mage"), ("mage".to_string(), None));
+        assert_eq!(parse_class_spec("mage:9"), ("mage:9".to_string(), None));
+        assert_eq!(parse_class_spec("mage:0"), ("mage:0".to_string(), None));
+    }
+
+    #[test]
+    fn test_spec_zero_is_rejected_not_underflowed() {
+        assert_eq!(ClassSelection::parse("mage:0").unwrap_err(), vec!["mage:0".to_string()]);
+
+        // A zero that slips past parsing scores nothing instead of panicking
+        let mage: WowClass = "mage".parse().unwrap();
+        let zero = RankFilters { classes: ClassSelection { classes: vec![mage], spec: Some(0) }, ..rank_filters() };
+        assert!(rank_players(rank_members(), &zero).is_empty());
+        assert_eq!(zero.classes.display(), "mage:0");
+    }
+
+    fn known_realms() -> Vec<RealmName> {
+        ["Tarren Mill", "Howling Fjord", "Silvermoon", "Kazzak"]
+            .into_iter()
+            .map(RealmName::from)
+            .collect()
+    }
+
+    #[test]
+    fn test_resolve_realm_accepts_any_spelling() {
+        let realms = known_realms();
+        for input in ["Tarren Mill", "tarren-mill", "TARREN MILL", "  tarren mill "] {
+            assert_eq!(resolve_realm(input, &realms).unwrap().as_str(), "tarren-mill");
+        }
+    }
+
+    #[test]
+    fn test_resolve_realm_suggests_closest_match() {
+        let realms = known_realms();
+        assert_eq!(
+            resolve_realm("Taren Mil", &realms).unwrap_err(),
+            "Unknown realm 'Taren Mil'. Did you mean 'Tarren Mill'?"
+        );
+        assert_eq!(
+            resolve_realm("silvermon", &realms).unwrap_err(),
+            "Unknown realm 'silvermon'. Did you mean 'Silvermoon'?"
+        );
+    }
+
+    #[test]
+    fn test_resolve_realm_without_close_match() {
+        let error = resolve_realm("Argent Dawn", &known_realms()).unwrap_err();
+        assert!(error.starts_with("Unknown realm 'Argent Dawn'. Tracked realms:"));
+    }
+
+    #[test]
+    fn test_top_guild_picks_best_progression() {
+        use crate::types::WorldRank;
+
+        let guild = |name: &str, progress: &str, rank: Option<u32>, best_percent: f64| GuildData {
+            name: GuildName::from(name),
+            realm: RealmName::from("Tarren Mill"),
+            progress: Some(progress.to_string()),
+            rank: rank.map(WorldRank::new),
+            best_percent,
+            pull_count: Some(100),
+            defeated_at: None,
+            current_boss: None,
+        };
+
+        let top = top_guild(vec![
+            guild("Heroic Clear", "8/8 H", None, 100.0),
+            guild("Mythic Push", "6/8 M", Some(300), 35.0),
+            guild("Mythic Leader", "7/8 M", Some(120), 12.5),
+            guild("Mythic Close", "7/8 M", Some(180), 3.0),
+        ])
+        .unwrap();
+        assert_eq!(top.name.as_str(), "Mythic Leader");
+
+        let details = format_top_guild(&top, 3, Locale::En);
+        assert!(details.contains("**Top guild in season 3: Mythic Leader**"));
+        assert!(details.contains("Progress: 7/8 M"));
+        assert!(details.contains("World rank: #120"));
+        assert!(details.contains("Best: 12.5%(100 pulls)"));
+        assert!(!details.contains("Currently on"));
+
+        let on_boss = GuildData { current_boss: Some("Dimensius".to_string()), ..top };
+        assert!(format_top_guild(&on_boss, 3, Locale::En).ends_with("\nCurrently on: Dimensius, 12.5% best"));
+
+        assert!(top_guild(Vec::new()).is_none());
+        let not_started = GuildData { progress: None, ..guild("Fresh Start", "", None, 0.0) };
+        assert!(top_guild(vec![not_started]).is_none());
+    }
+
+    #[test]
+    fn test_parse_guild_limit_defaults_and_clamps() {
+        assert_eq!(parse_guild_limit(None, 10, 50, Locale::En), Ok(Some(10)));
+        assert_eq!(parse_guild_limit(Some("25"), 10, 50, Locale::En), Ok(Some(25)));
+        assert_eq!(parse_guild_limit(Some("100000"), 10, 50, Locale::En), Ok(Some(50)));
+        assert_eq!(parse_guild_limit(Some("all"), 10, 50, Locale::En), Ok(None));
+        assert_eq!(parse_guild_limit(Some("ALL"), 10, 50, Locale::En), Ok(None));
+    }
+
+    #[test]
+    fn test_parse_guild_limit_rejects_invalid_input() {
+        assert_eq!(
+            parse_guild_limit(Some("ten"), 10, 50, Locale::En),
+            Err("Error: Invalid limit 'ten'. Use a number between 1 and 50 or 'all'.".to_string())
+        );
+        assert!(parse_guild_limit(Some("-5"), 10, 50, Locale::En).is_err());
+        assert!(parse_guild_limit(Some("0"), 10, 50, Locale::En).is_err());
+        assert_eq!(
+            parse_guild_limit(Some("0"), 10, 50, Locale::Uk),
+            Err("Помилка: значення limit має бути щонайменше 1.".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_difficulty_filter() {
+        assert_eq!(parse_difficulty_filter(None, Locale::En), Ok(None));
+        assert_eq!(parse_difficulty_filter(Some("All"), Locale::En), Ok(None));
+        assert_eq!(parse_difficulty_filter(Some("mythic"), Locale::En), Ok(Some(RaidDifficulty::Mythic)));
+        assert_eq!(
+            parse_difficulty_filter(Some("lfr"), Locale::En),
+            Err("Error: Unknown difficulty 'lfr'. Use all, mythic, heroic or normal.".to_string())
+        );
+    }
+
+    fn guild_player(name: &str, realm: &str, guild: Option<&str>) -> PlayerData {
+        PlayerData::from(&crate::database::tests::test_member(name, realm, guild, 2500.0))
+    }
+
+    #[test]
+    fn test_guild_filter_separates_same_named_guilds_by_realm() {
+        let players = vec![
+            guild_player("Tarrenone", "Tarren Mill", Some("Нехай Щастить")),
+            guild_player("Fjordone", "Howling Fjord", Some("Нехай Щастить")),
+            guild_player("Loner", "Tarren Mill", None),
+        ];
+
+        let mut unqualified = players.clone();
+        filter_players_by_guild(&mut unqualified, "нехай щастить");
+        assert_eq!(unqualified.len(), 2);
+
+        let mut qualified = players.clone();
+        filter_players_by_guild(&mut qualified, "Нехай Щастить@Howling Fjord");
+        assert_eq!(qualified.len(), 1);
+        assert_eq!(qualified[0].name.as_str(), "Fjordone");
+
+        let mut slug = players.clone();
+        filter_players_by_guild(&mut slug, "Нехай Щастить@tarren-mill, Other Guild");
+        assert_eq!(slug.len(), 1);
+        assert_eq!(slug[0].name.as_str(), "Tarrenone");
+
+        let mut unguilded = players;
+        filter_players_by_guild(&mut unguilded, "none");
+        assert_eq!(unguilded.len(), 1);
+        assert_eq!(unguilded[0].name.as_str(), "Loner");
+    }
+
+    #[test]
+    fn test_guild_filter_realm_is_the_guilds_realm() {
+        // An alt on a connected realm still belongs to the Tarren Mill guild
+        let connected_alt = PlayerData {
+            guild_realm: Some(RealmName::from("Tarren Mill")),
+            ..guild_player("Altone", "Howling Fjord", Some("Нехай Щастить"))
+        };
+        let players = vec![connected_alt, guild_player("Fjordone", "Howling Fjord", Some("Нехай Щастить"))];
+
+        let mut selected = players.clone();
+        filter_players_by_guild(&mut selected, "Нехай Щастить@Tarren Mill");
+        assert_eq!(selected.len(), 1);
+        assert_eq!(selected[0].name.as_str(), "Altone");
+
+        let mut selected = players;
+        filter_players_by_guild(&mut selected, "Нехай Щастить@Howling Fjord");
+        assert_eq!(selected.len(), 1);
+        assert_eq!(selected[0].name.as_str(), "Fjordone");
+    }
+
+    #[test]
+    fn test_guild_list_accepts_every_separator() {
+        let players = vec![
+            guild_player("Horde", "Tarren Mill", Some("Thorned Horde")),
+            guild_player("Щастя", "Tarren Mill", Some("Нехай Щастить")),
+            guild_player("Nexer", "Silvermoon", Some("Nexus")),
+        ];
+
+        for list in ["Нехай Щастить,Nexus", "Нехай Щастить; Nexus", "Нехай Щастить | Nexus"] {
+            let mut selected = players.clone();
+            filter_players_by_guild(&mut selected, list);
+            let names: Vec<&str> = selected.iter().map(|p| p.name.as_str()).collect();
+            assert_eq!(names, vec!["Щастя", "Nexer"], "{}", list);
+        }
+
+        assert_eq!(ClassSelection::parse("mage;druid|priest").unwrap().classes.len(), 3);
+        assert_eq!(parse_realm_filter("Tarren Mill|Silvermoon").len(), 2);
+    }
+
+    #[test]
+    fn test_unguilded_filter_keeps_only_players_without_guild() {
+        let mut players = vec![
+            guild_player("Guilded", "Tarren Mill", Some("Thorned Horde")),
+            guild_player("Loner", "Tarren Mill", None),
+            guild_player("Drifter", "Silvermoon", None),
+            guild_player("Literal", "Tarren Mill", Some("None")),
+        ];
+
+        retain_unguilded(&mut players);
+        let names: Vec<&str> = players.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["Loner", "Drifter"]);
+    }
+
+    fn rank_members() -> Vec<DbMember> {
+        use crate::database::tests::test_member;
+        vec![
+            test_member("Frostmage", "Tarren Mill", Some("Thorned Horde"), 2800.0),
+            DbMember {
+                class: Some("Druid".to_string()),
+                rio_healer: 2650.0,
+                ..test_member("Treeheal", "Silvermoon", Some("Nexus"), 2600.0)
+            },
+            test_member("Loner", "Tarren Mill", None, 2400.0),
+            test_member("Altmage", "Tarren Mill", Some("Thorned Horde"), 1900.0),
+        ]
+    }
+
+    fn rank_filters() -> RankFilters {
+        RankFilters {
+            top: 10,
+            guilds: "all".to_string(),
+            unguilded: false,
+            classes: ClassSelection::parse("all").unwrap(),
+            role: "all".to_string(),
+            rio: 2000,
+            rio_max: None,
+            unique_players: false,
+            realms: Vec::new(),
+        }
+    }
+
+    fn ranked_names(filters: &RankFilters) -> Vec<String> {
+        ranked_names_of(rank_members(), filters)
+    }
+
+    fn ranked_names_of(members: Vec<DbMember>, filters: &RankFilters) -> Vec<String> {
+        rank_players(members, filters).iter().map(|r| r.player.name.to_string()).collect()
+    }
+
+    #[test]
+    fn test_format_class_top_lists_every_class() {
+        use crate::database::tests::test_member;
+
+        let top = vec![
+            ("Mage".to_string(), test_member("Blink", "Tarren Mill", None, 3100.4)),
+            ("Death Knight".to_string(), DbMember {
+                class: Some("Death Knight".to_string()),
+                ..test_member("Deathgrip", "Silvermoon", None, 2950.0)
+            }),
+        ];
+        let message = format_class_top(&top);
+        let lines: Vec<&str> = message.lines().collect();
+
+        assert_eq!(lines.len(), 1 + WowClass::all().count());
+        assert_eq!(lines[1], "**Death Knight**: Deathgrip - Silvermoon - RIO 2950");
+        assert_eq!(lines[2], "**Demon Hunter**: none");
+        assert!(lines.contains(&"**Mage**: Blink - Tarren Mill - RIO 3100.4"));
+    }
+
+    #[test]
+    fn test_format_tracked_guilds_groups_by_realm() {
+        let guild = |name: &str, realm: &str| GuildUrl::new(RealmName::from(realm), GuildName::from(name));
+        let guilds = vec![
+            guild("Нехай Щастить", "Tarren Mill"),
+            guild("Thorned Horde", "Silvermoon"),
+            guild("Alpha", "Tarren Mill"),
+        ];
+
+        assert_eq!(
+            format_tracked_guilds(&guilds),
+            "**Tracked guilds (3):**\n\n**Silvermoon** (1)\nThorned Horde\n\n**Tarren Mill** (2)\nAlpha\nНехай Щастить"
+        );
+        assert_eq!(format_tracked_guilds(&[]), "No guilds are tracked yet.");
+    }
+
+    #[test]
+    fn test_class_distribution() {
+        use crate::database::tests::test_member;
+
+        let member = |name: &str, class: Option<&str>, spec: Option<&str>| DbMember {
+            class: class.map(str::to_string),
+            spec: spec.map(str::to_string),
+            ..test_member(name, "Tarren Mill", Some("Thorned Horde"), 2500.0)
+        };
+        let members = vec![
+            member("Frostmage", Some("Mage"), Some("Frost")),
+            member("Firemage", Some("Mage"), Some("Fire")),
+            member("Deathgrip", Some("Death Knight"), Some("Frost")),
+            member("Bear", Some("Druid"), Some("Guardian")),
+            member("Tree", Some("Druid"), Some("Restoration")),
+            member("Mystery", None, None),
+            member("Oddball", Some("Priest"), Some("Arms")),
+        ];
+
+        let distribution = class_distribution(&members);
+        assert_eq!(
+            distribution.classes,
+            vec![
+                ("Druid".to_string(), 2),
+                ("Mage".to_string(), 2),
+                ("Death Knight".to_string(), 1),
+                ("Priest".to_string(), 1),
+                ("Unknown".to_string(), 1),
+            ]
+        );
+        assert_eq!(
+            distribution.roles,
+            vec![("Tanks", 1), ("Healers", 1), ("Melee", 1), ("Ranged", 2), ("Unknown", 2)]
+        );
+
+        // The unknown role bucket is only listed when needed
+        let roles = class_distribution(&members[..5]).roles;
+        assert_eq!(roles.last(), Some(&("Ranged", 2)));
+    }
+
+    #[test]
+    fn test_spec_score_falls_back_to_role_score() {
+        use crate::database::tests::test_member;
+
+        // Frost mage with no per-spec scores but a strong dps score
+        let members = vec![DbMember { spec_2: 0.0, rio_dps: 2700.0, ..test_member("Frostmage", "Tarren Mill", None, 2700.0) }];
+        let frost = RankFilters { classes: ClassSelection::parse("mage:3").unwrap(), ..rank_filters() };
+        let ranked = rank_players(members.clone(), &frost);
+        assert_eq!(ranked.len(), 1);
+        assert_eq!(ranked[0].score, 2700.0);
+
+        // A healer's role score never stands in for a missing dps spec
+        let healer = vec![DbMember {
+            class: Some("Priest".to_string()),
+            spec: Some("Holy".to_string()),
+            spec_2: 0.0,
+            rio_dps: 0.0,
+            rio_healer: 2700.0,
+            spec_0_name: Some("Discipline".to_string()),
+            spec_1_name: Some("Holy".to_string()),
+            spec_2_name: Some("Shadow".to_string()),
+            ..test_member("Priestess", "Tarren Mill", None, 2700.0)
+        }];
+        let shadow = RankFilters { classes: ClassSelection::parse("priest:3").unwrap(), ..rank_filters() };
+        assert!(rank_players(healer, &shadow).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_rank_distinguishes_empty_database_from_no_matches() {
+        let database = Database::open_temp().await;
+        let filters = RankFilters { rio: 3000, ..rank_filters() };
+
+        assert_eq!(
+            rank_from_database(&database, &filters, Locale::En).await,
+            vec![t("rank.no_members", Locale::En)]
+        );
+
+        database.clear_temp_members().await.unwrap();
+        database.insert_temp_members_batch(&rank_members()).await.unwrap();
+        database.swap_members_tables().await.unwrap();
+        assert_eq!(
+            rank_from_database(&database, &filters, Locale::En).await,
+            vec![t("rank.no_players", Locale::En)]
+        );
+    }
+
+    #[test]
+    fn test_parse_season_previous() {
+        use crate::types::Season;
+
+        let (season, tier) = parse_season(Some("previous"), 3).unwrap();
+        assert_eq!((season, tier), (2, RaidTier::liberation_of_undermine()));
+        assert_eq!(Season::for_tier(tier).as_str(), "season-tww-2");
+        assert_eq!(parse_season(Some("Previous"), 2).unwrap().1, RaidTier::nerubar_palace());
+
+        // There is no tier before the first one
+        assert_eq!(parse_season(Some("previous"), 1), None);
+    }
+
+    #[test]
+    fn test_parse_season_numbers_and_default() {
+        assert_eq!(parse_season(None, 3), Some((3, RaidTier::manaforge_omega())));
+        // An explicit season overrides raider_io.default_season
+        assert_eq!(parse_season(Some("2"), 3), Some((2, RaidTier::liberation_of_undermine())));
+        assert_eq!(parse_season(Some(" 1 "), 3), Some((1, RaidTier::nerubar_palace())));
+        assert_eq!(parse_season(Some("4"), 3), None);
+        assert_eq!(parse_season(Some("last"), 3), None);
+
+        // Out-of-range integers are rejected rather than wrapped into a u8
+        assert_eq!(parse_season(Some("300"), 3), None);
+        assert_eq!(parse_season(Some("-1"), 3), None);
+        assert_eq!(parse_season(Some("0"), 3), None);
+    }
+
+    #[test]
+    fn test_rank_bounds_rejects_out_of_range_integers() {
+        assert_eq!(rank_bounds(10, 2000, None, Locale::En), Ok((10, 2000, None)));
+        assert_eq!(rank_bounds(50, 0, Some(3500), Locale::En), Ok((50, 0, Some(3500))));
+
+        let top_error = Err(t("rank.top_range", Locale::En));
+        assert_eq!(rank_bounds(0, 2000, None, Locale::En), top_error);
+        assert_eq!(rank_bounds(-1, 2000, None, Locale::En), top_error);
+
+        // 2^32 + 2000 would wrap to 2000 if cast to u32 first
+        let rio_error = Err(t("rank.rio_range", Locale::En));
+        assert_eq!(rank_bounds(10, (1 << 32) + 2000, None, Locale::En), rio_error);
+        assert_eq!(rank_bounds(10, -1, None, Locale::En), rio_error);
+
+        let rio_max_error = Err(t("rank.rio_max_range", Locale::En));
+        assert_eq!(rank_bounds(10, 2500, Some(2000), Locale::En), rio_max_error);
+        assert_eq!(rank_bounds(10, 2500, Some((1 << 32) + 3000), Locale::En), rio_max_error);
+    }
+
+    #[test]
+    fn test_rank_players_default_filters() {
+        let ranked = rank_players(rank_members(), &rank_filters());
+
+        let rows: Vec<(usize, &str, f64, &str)> = ranked
+            .iter()
+            .map(|r| (r.rank, r.player.name.as_str(), r.score, r.score_label.as_str()))
+            .collect();
+        assert_eq!(
+            rows,
+            vec![(1, "Frostmage", 2800.0, "all"), (2, "Treeheal", 2600.0, "all"), (3, "Loner", 2400.0, "all")]
+        );
+    }
+
+    #[test]
+    fn test_rank_players_guild_filters() {
+        let guild = RankFilters { guilds: "Thorned Horde".to_string(), ..rank_filters() };
+        assert_eq!(ranked_names(&guild), vec!["Frostmage"]);
+
+        let unguilded = RankFilters { unguilded: true, guilds: "Thorned Horde".to_string(), ..rank_filters() };
+        assert_eq!(ranked_names(&unguilded), vec!["Loner"]);
+    }
+
+    #[test]
+    fn test_rank_players_class_and_spec_filters() {
+        let druids = RankFilters { classes: ClassSelection::parse("druid").unwrap(), ..rank_filters() };
+        assert_eq!(ranked_names(&druids), vec!["Treeheal"]);
+
+        let frost = RankFilters { classes: ClassSelection::parse("mage:frost").unwrap(), ..rank_filters() };
+        let ranked = rank_players(rank_members(), &frost);
+        let rows: Vec<(&str, f64, &str)> =
+            ranked.iter().map(|r| (r.player.name.as_str(), r.score, r.score_label.as_str())).collect();
+        assert_eq!(rows, vec![("Frostmage", 2800.0, "Frost"), ("Loner", 2400.0, "Frost")]);
+    }
+
+    #[test]
+    fn test_rank_players_role_rio_band_and_top() {
+        let healers = RankFilters { role: "healer".to_string(), ..rank_filters() };
+        let ranked = rank_players(rank_members(), &healers);
+        assert_eq!(ranked.len(), 1);
+        assert_eq!((ranked[0].player.name.as_str(), ranked[0].score), ("Treeheal", 2650.0));
+        assert_eq!(ranked[0].score_label, "healer");
+
+        let band = RankFilters { rio: 2500, rio_max: Some(2700), ..rank_filters() };
+        assert_eq!(ranked_names(&band), vec!["Treeheal"]);
+
+        let everyone = RankFilters { rio: 0, ..rank_filters() };
+        assert_eq!(ranked_names(&everyone).len(), 4);
+
+        let top_two = RankFilters { top: 2, ..rank_filters() };
+        assert_eq!(ranked_names(&top_two), vec!["Frostmage", "Treeheal"]);
+    }
+
+    #[test]
+    fn test_format_maintenance_report() {
+        assert_eq!(
+            format_maintenance_report(3 * 1024 * 1024, 1024 * 1024),
+            "Database vacuumed and analyzed: 3.00 MiB → 1.00 MiB (2.00 MiB freed)."
+        );
+    }
+
+    #[tokio::test]
+    async fn test_with_command_timeout() {
+        assert_eq!(with_command_timeout(async { 7 }, 5).await, Some(7));
+        assert_eq!(with_command_timeout(std::future::pending::<u32>(), 0).await, None);
+    }
+
+    #[test]
+    fn test_rank_players_realm_filter() {
+        use crate::database::tests::test_member;
+
+        let mut members = rank_members();
+        members.push(test_member("Drakthul", "Draenor", None, 2700.0));
+
+        let tarren_mill = RankFilters { realms: parse_realm_filter("tarren mill"), ..rank_filters() };
+        assert_eq!(ranked_names_of(members.clone(), &tarren_mill), vec!["Frostmage", "Loner"]);
+
+        let two = RankFilters { realms: parse_realm_filter(" Silvermoon, draenor ,"), ..rank_filters() };
+        assert_eq!(ranked_names_of(members.clone(), &two), vec!["Drakthul", "Treeheal"]);
+        assert_eq!(two.realms_label(), "Silvermoon, Draenor");
+
+        assert!(parse_realm_filter("all").is_empty());
+        assert_eq!(ranked_names_of(members, &rank_filters()).len(), 4);
+    }
+
+    #[test]
+    fn test_rank_players_unique_players_keeps_best_alt() {
+        use crate::database::tests::test_member;
+
+        let mut members = rank_members();
+        members.push(test_member("FROSTMAGE", "Silvermoon", Some("Nexus"), 2500.0));
+        members.push(test_member("Frostmage", "Draenor", None, 2900.0));
+
+        let ranked = rank_players(members.clone(), &rank_filters());
+        assert_eq!(ranked.iter().filter(|r| r.player.name.as_str().eq_ignore_ascii_case("frostmage")).count(), 3);
+
+        let unique = RankFilters { unique_players: true, ..rank_filters() };
+        let ranked = rank_players(members, &unique);
+        let rows: Vec<(&str, &str, f64)> =
+            ranked.iter().map(|r| (r.player.name.as_str(), r.player.realm.as_str(), r.score)).collect();
+        assert_eq!(
+            rows,
+            vec![("Frostmage", "draenor", 2900.0), ("Treeheal", "silvermoon", 2600.0), ("Loner", "tarren-mill", 2400.0)]
+        );
+    }
+
+    #[test]
+    fn test_format_rank_messages_labels_scores() {
+        let filters = RankFilters { classes: ClassSelection::parse("mage:frost").unwrap(), ..rank_filters() };
+        let messages = format_rank_messages(&rank_players(rank_members(), &filters), &filters, Locale::En);
+        assert_eq!(messages.len(), 1);
+        assert!(messages[0].contains("Frost: 2800"));
+
+        let filters = rank_filters();
+        let messages = format_rank_messages(&rank_players(rank_members(), &filters), &filters, Locale::En);
+        assert!(messages[0].contains("2800 (Overall)"));
+        assert!(messages[0].contains("Guilds: all"));
+    }
+
+    fn class_player(name: &str, class: &str) -> PlayerData {
+        PlayerData {
+            class: Some(class.to_string()),
+            ..guild_player(name, "Tarren Mill", None)
+        }
+    }
+
+    #[test]
+    fn test_two_class_filter() {
+        let selection = ClassSelection::parse("mage, Druid").unwrap();
+        assert_eq!(selection.classes, vec!["Mage".parse::<WowClass>().unwrap(), "Druid".parse().unwrap()]);
+        assert_eq!(selection.display(), "mage, druid");
+
+        let mut players = vec![
+            class_player("Frosty", "Mage"),
+            class_player("Treeform", "Druid"),
+            class_player("Plated", "Warrior"),
+        ];
+        selection.retain_matching(&mut players);
+        let names: Vec<&str> = players.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["Frosty", "Treeform"]);
+    }
+
+    #[test]
+    fn test_class_filter_reports_invalid_classes() {
+        assert_eq!(ClassSelection::parse("mage,bard"), Err(vec!["bard".to_string()]));
+        assert_eq!(
+            ClassSelection::parse("pirate, druid, ninja"),
+            Err(vec!["pirate".to_string(), "ninja".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_single_class_spec_selection() {
+        let selection = ClassSelection::parse("death knight:frost").unwrap();
+        assert_eq!(selection.classes, vec!["Death Knight".parse::<WowClass>().unwrap()]);
+        assert_eq!(selection.spec, Some(2));
+        assert_eq!(selection.display(), "death knight:Frost");
+
+        let all = ClassSelection::parse("all").unwrap();
+        assert!(all.classes.is_empty());
+        assert_eq!(all.display(), "all");
+    }
+
+    #[test]
+    fn test_class_autocomplete_choices() {
+        assert_eq!(class_autocomplete_choices("d"), vec!["death knight", "demon hunter", "druid"]);
+        assert_eq!(class_autocomplete_choices("mage, dr"), vec!["mage, druid"]);
+        assert_eq!(class_autocomplete_choices("mage;dr"), vec!["mage; druid"]);
+        assert_eq!(class_autocomplete_choices("").len(), 14);
+        assert!(!class_autocomplete_choices("mage,").contains(&"mage, all".to_string()));
+        assert!(class_autocomplete_choices("bard").is_empty());
+    }
+
+    #[test]
+    fn test_rio_band_filter() {
+        let mut players: Vec<PlayerData> = [2400.0, 2500.0, 2750.0, 3000.0, 3100.0]
+            .iter()
+            .enumerate()
+            .map(|(i, rio)| PlayerData::from(&crate::database::tests::test_member(
+                &format!("Player{}", i), "Tarren Mill", None, *rio,
+            )))
+            .collect();
+
+        retain_rio_band(&mut players, 2500, Some(3000), |p| p.rio_all.value());
+        let scores: Vec<f64> = players.iter().map(|p| p.rio_all.value()).collect();
+        assert_eq!(scores, vec![2750.0, 3000.0]);
+
+        retain_rio_band(&mut players, 2000, None, |p| p.rio_all.value());
+        assert_eq!(players.len(), 2);
+    }
+
+    #[test]
+    fn test_format_whois_cases() {
+        use crate::database::tests::test_member;
+        let name = PlayerName::from("frostbolt");
+
+        assert!(format_whois(&name, &[]).starts_with("No tracked character named Frostbolt."));
+
+        let single = format_whois(&name, &[test_member("Frostbolt", "Tarren Mill", Some("Thorned Horde"), 2847.5)]);
+        assert!(single.starts_with("**Frostbolt** (Tarren Mill) - Thorned Horde"));
+
+        let many = format_whois(&name, &[
+            test_member("Frostbolt", "Tarren Mill", Some("Thorned Horde"), 2847.5),
+            test_member("Frostbolt", "Silvermoon", None, 2100.0),
+        ]);
+        assert!(many.starts_with("**2 characters named Frostbolt:**"));
+        assert!(many.contains("1. Tarren Mill - Thorned Horde - RIO 2847.5"));
+        assert!(many.contains("2. Silvermoon - No Guild - RIO 2100"));
+    }
+
+    #[test]
+    fn test_levenshtein() {
+        assert_eq!(levenshtein("kazzak", "kazzak"), 0);
+        assert_eq!(levenshtein("kazak", "kazzak"), 1);
+        assert_eq!(levenshtein("", "abc"), 3);
+        assert_eq!(levenshtein("терокар", "терокkар"), 1);
+    }
+}
@@ -1,8 +1,15 @@
 /// Configuration management for the WoW Guild Bot
 use crate::error::{BotError, Result};
+use crate::types::{DiscordId, GuildUrl, RaidTier, RealmName, Season};
 use config::{Config, ConfigError, Environment, File};
+use serde::de::value::StrDeserializer;
+use serde::de::IntoDeserializer;
 use serde::{Deserialize, Serialize};
+use serenity::gateway::GatewayError;
+use serenity::model::gateway::GatewayIntents;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
+use tracing::warn;
 
 /// Main application configuration
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -19,11 +26,25 @@ pub struct AppConfig {
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct DiscordConfig {
     pub token: String,
-    pub guild_id: Option<u64>,
-    pub server_id: Option<String>,
-    pub rules_channel_id: Option<String>,
-    pub auto_role_id: Option<String>,
+    pub guild_id: Option<DiscordId>,
+    pub server_id: Option<DiscordId>,
+    pub rules_channel_id: Option<DiscordId>,
+    pub auto_role_id: Option<DiscordId>,
     pub auto_role_enabled: bool,
+    /// Assign a guild-specific role when the new member's nickname matches a tracked character
+    pub auto_role_guild_matching: bool,
+    /// Guild name -> Discord role ID used by guild matching
+    #[serde(default)]
+    pub guild_role_map: HashMap<String, DiscordId>,
+    /// Gateway intent names, e.g. GUILD_MESSAGES (GUILD_MEMBERS is privileged)
+    pub intents: Vec<String>,
+    /// Response language (`en`/`uk`); falls back to the user's Discord locale when unset
+    pub locale: Option<String>,
+    /// Command name -> per-user cooldown in seconds; commands not listed have none
+    pub command_cooldowns: HashMap<String, u64>,
+    /// Discord user IDs allowed to run admin commands, alongside server administrators
+    #[serde(default)]
+    pub admin_user_ids: Vec<DiscordId>,
 }
 
 /// Raider.io API configuration
@@ -32,6 +53,20 @@ pub struct RaiderIoConfig {
     pub api_key: Option<String>,
     pub base_url: String,
     pub timeout_secs: u64,
+    /// Per-request timeout for guild roster fetches, which can be much larger than profiles
+    pub roster_timeout_secs: u64,
+    /// Consecutive failed requests before raider.io calls are paused (0 = never pause)
+    pub circuit_breaker_threshold: u32,
+    /// How long raider.io calls stay paused before a probe request is allowed
+    pub circuit_breaker_cooldown_secs: u64,
+    /// Most characters a parser run may fetch without an API key (0 = no limit)
+    pub unauthenticated_character_limit: usize,
+    /// User-Agent header sent with every raider.io request
+    pub user_agent: String,
+    /// Character profile fields requested on top of the scores, class and spec the bot
+    /// always needs (e.g. `gear`); duplicates of the built-in fields are ignored
+    #[serde(default)]
+    pub player_fields: Vec<String>,
     pub season: String,
     pub region: Region,
     pub default_season: u8,
@@ -44,6 +79,8 @@ pub struct RateLimitConfig {
     pub concurrent_requests: usize,
     pub retry_attempts: u32,
     pub retry_delay_secs: u64,
+    /// Maximum concurrent parser requests against a single realm (unset = no per-realm cap)
+    pub per_realm_concurrency: Option<usize>,
 }
 
 /// Data handling configuration
@@ -51,6 +88,22 @@ pub struct RateLimitConfig {
 pub struct DataConfig {
     pub backup_enabled: bool,
     pub batch_size: usize,
+    /// Number of guilds shown by /guilds when no limit is given
+    pub default_guild_limit: usize,
+    /// Upper bound for a numeric /guilds limit
+    pub max_guild_limit: usize,
+    /// Re-run the parser inside the bot every N hours (0 = disabled)
+    pub refresh_interval_hours: u64,
+    /// Deadline for fetching guilds in /guilds before partial results are shown (0 = none)
+    pub guilds_command_timeout_secs: u64,
+    /// After a guild refresh, delete that guild's members who are no longer on its roster
+    /// and haven't been updated for this many days (0 = keep)
+    pub member_retention_days: u64,
+    /// Only parse and fetch guilds on these realms, e.g. ["Tarren Mill"] (empty = all)
+    #[serde(default)]
+    pub realm_allowlist: Vec<String>,
+    /// Parser skips storing characters whose overall RIO is below this (0 = store all)
+    pub min_stored_rio: f64,
 }
 
 /// Database configuration
@@ -58,6 +111,12 @@ pub struct DataConfig {
 pub struct DatabaseConfig {
     pub url: String,
     pub auto_migrate: bool,
+    /// Maximum pooled SQLite connections
+    pub max_connections: u32,
+    /// How long a connection waits on a locked database before failing
+    pub busy_timeout_ms: u64,
+    /// Refuse to swap in `members_tmp` when it has fewer rows than this fraction of `members`
+    pub min_swap_ratio: f64,
 }
 
 /// Logging configuration
@@ -67,10 +126,12 @@ pub struct LoggingConfig {
     pub format: LogFormat,
     pub file_enabled: bool,
     pub file_path: Option<String>,
+    /// Most error-detail files kept in `logs/errors`; older ones are deleted (0 = no limit)
+    pub max_error_files: usize,
 }
 
 /// Supported WoW regions
-#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, Hash)]
 #[serde(rename_all = "lowercase")]
 pub enum Region {
     Us,
@@ -89,6 +150,16 @@ pub enum LogFormat {
     Compact,
 }
 
+impl Region {
+    /// Parse a lowercase or uppercase region code such as "eu" or "US", using the
+    /// same names as the config file
+    pub fn from_code(code: &str) -> Option<Self> {
+        let code = code.trim().to_lowercase();
+        let deserializer: StrDeserializer<'_, serde::de::value::Error> = code.as_str().into_deserializer();
+        Self::deserialize(deserializer).ok()
+    }
+}
+
 impl fmt::Display for Region {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -123,6 +194,19 @@ impl Default for DiscordConfig {
             rules_channel_id: None,
             auto_role_id: None,
             auto_role_enabled: true,
+            auto_role_guild_matching: false,
+            guild_role_map: HashMap::new(),
+            intents: vec![
+                "GUILD_MESSAGES".to_string(),
+                "DIRECT_MESSAGES".to_string(),
+                "GUILD_MEMBERS".to_string(),
+            ],
+            locale: None,
+            command_cooldowns: HashMap::from([
+                ("guilds".to_string(), 30),
+                ("rank".to_string(), 10),
+            ]),
+            admin_user_ids: Vec::new(),
         }
     }
 }
@@ -133,6 +217,12 @@ impl Default for RaiderIoConfig {
             api_key: None,
             base_url: "https://raider.io/api/v1".to_string(),
             timeout_secs: 15,
+            roster_timeout_secs: 60,
+            circuit_breaker_threshold: 5,
+            circuit_breaker_cooldown_secs: 60,
+            unauthenticated_character_limit: 1000,
+            user_agent: "wow-guild-bot/1.0".to_string(),
+            player_fields: Vec::new(),
             season: "season-tww-3".to_string(),
             region: Region::Eu,
             default_season: 3,
@@ -147,15 +237,55 @@ impl Default for RateLimitConfig {
             concurrent_requests: 25,    // Increased from 5 to match Python concurrency
             retry_attempts: 3,
             retry_delay_secs: 30,
+            per_realm_concurrency: None,
         }
     }
 }
 
+impl DataConfig {
+    /// Normalized `realm_allowlist` without blank entries, so `[""]` from an empty env
+    /// var means no restriction
+    fn allowed_realms(&self) -> HashSet<RealmName> {
+        self.realm_allowlist
+            .iter()
+            .filter(|realm| !realm.trim().is_empty())
+            .map(|realm| RealmName::new(realm.as_str()))
+            .collect()
+    }
+
+    /// Whether `realm_allowlist` leaves out any realm
+    pub fn restricts_realms(&self) -> bool {
+        !self.allowed_realms().is_empty()
+    }
+
+    /// Drop guilds whose realm isn't in `realm_allowlist`, comparing normalized realm
+    /// names; an empty allowlist keeps every guild
+    pub fn retain_allowed_realms(&self, guilds: &mut Vec<GuildUrl>) {
+        let allowed = self.allowed_realms();
+        if allowed.is_empty() {
+            return;
+        }
+        guilds.retain(|guild| allowed.contains(&guild.realm));
+    }
+
+    /// Whether a character with this overall RIO meets `min_stored_rio`
+    pub fn stores_rio(&self, rio_all: f64) -> bool {
+        rio_all >= self.min_stored_rio
+    }
+}
+
 impl Default for DataConfig {
     fn default() -> Self {
         Self {
             backup_enabled: true,
             batch_size: 100,
+            default_guild_limit: 10,
+            max_guild_limit: 50,
+            refresh_interval_hours: 0,
+            guilds_command_timeout_secs: 600,
+            member_retention_days: 30,
+            realm_allowlist: Vec::new(),
+            min_stored_rio: 0.0,
         }
     }
 }
@@ -165,6 +295,9 @@ impl Default for DatabaseConfig {
         Self {
             url: "sqlite://wow_guild_bot.db".to_string(),
             auto_migrate: true,
+            max_connections: 5,
+            busy_timeout_ms: 5000,
+            min_swap_ratio: 0.5,
         }
     }
 }
@@ -176,24 +309,109 @@ impl Default for LoggingConfig {
             format: LogFormat::Pretty,
             file_enabled: true, // Enable file logging by default for error tracking
             file_path: Some("logs/bot_errors.log".to_string()),
+            max_error_files: 1000,
         }
     }
 }
 
+impl DiscordConfig {
+    /// Look up the role mapped to a guild name (case-insensitive)
+    pub fn guild_role_for(&self, guild_name: &str) -> Option<DiscordId> {
+        let guild_name = guild_name.to_lowercase();
+        self.guild_role_map
+            .iter()
+            .find(|(name, _)| name.to_lowercase() == guild_name)
+            .map(|(_, role_id)| *role_id)
+    }
+
+    /// Whether the user is listed in `admin_user_ids`; an empty list admits nobody
+    pub fn is_admin(&self, user_id: u64) -> bool {
+        self.admin_user_ids.contains(&DiscordId::from(user_id))
+    }
+
+    /// Gateway intents requested from Discord
+    pub fn gateway_intents(&self) -> GatewayIntents {
+        parse_intents(&self.intents)
+    }
+}
+
+/// Whether a raider.io season identifier looks valid ("current", "previous" or "season-tww-3")
+fn is_valid_season(season: &str) -> bool {
+    match season {
+        "current" | "previous" => true,
+        _ => season.strip_prefix("season-").is_some_and(|rest| {
+            !rest.is_empty()
+                && !rest.starts_with('-')
+                && !rest.ends_with('-')
+                && rest.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+        }),
+    }
+}
+
+/// The intents to retry with when Discord refused the connection because GUILD_MEMBERS
+/// isn't approved for the application; `None` for any other failure
+pub fn intents_without_unapproved(intents: GatewayIntents, error: &BotError) -> Option<GatewayIntents> {
+    let disallowed = matches!(
+        error,
+        BotError::Discord(serenity::Error::Gateway(GatewayError::DisallowedGatewayIntents))
+    );
+    (disallowed && intents.contains(GatewayIntents::GUILD_MEMBERS))
+        .then(|| intents - GatewayIntents::GUILD_MEMBERS)
+}
+
+/// Parse intent names (case-insensitive) into gateway intents, skipping unknown names
+pub fn parse_intents(names: &[String]) -> GatewayIntents {
+    names.iter().fold(GatewayIntents::empty(), |intents, name| {
+        let intent = match name.trim().to_uppercase().as_str() {
+            "GUILDS" => GatewayIntents::GUILDS,
+            "GUILD_MEMBERS" => GatewayIntents::GUILD_MEMBERS,
+            "GUILD_BANS" => GatewayIntents::GUILD_BANS,
+            "GUILD_EMOJIS_AND_STICKERS" => GatewayIntents::GUILD_EMOJIS_AND_STICKERS,
+            "GUILD_INTEGRATIONS" => GatewayIntents::GUILD_INTEGRATIONS,
+            "GUILD_WEBHOOKS" => GatewayIntents::GUILD_WEBHOOKS,
+            "GUILD_INVITES" => GatewayIntents::GUILD_INVITES,
+            "GUILD_VOICE_STATES" => GatewayIntents::GUILD_VOICE_STATES,
+            "GUILD_PRESENCES" => GatewayIntents::GUILD_PRESENCES,
+            "GUILD_MESSAGES" => GatewayIntents::GUILD_MESSAGES,
+            "GUILD_MESSAGE_REACTIONS" => GatewayIntents::GUILD_MESSAGE_REACTIONS,
+            "GUILD_MESSAGE_TYPING" => GatewayIntents::GUILD_MESSAGE_TYPING,
+            "DIRECT_MESSAGES" => GatewayIntents::DIRECT_MESSAGES,
+            "DIRECT_MESSAGE_REACTIONS" => GatewayIntents::DIRECT_MESSAGE_REACTIONS,
+            "DIRECT_MESSAGE_TYPING" => GatewayIntents::DIRECT_MESSAGE_TYPING,
+            "MESSAGE_CONTENT" => GatewayIntents::MESSAGE_CONTENT,
+            "GUILD_SCHEDULED_EVENTS" => GatewayIntents::GUILD_SCHEDULED_EVENTS,
+            "AUTO_MODERATION_CONFIGURATION" => GatewayIntents::AUTO_MODERATION_CONFIGURATION,
+            "AUTO_MODERATION_EXECUTION" => GatewayIntents::AUTO_MODERATION_EXECUTION,
+            _ => {
+                warn!(intent = %name, "Ignoring unknown Discord gateway intent");
+                GatewayIntents::empty()
+            }
+        };
+        intents | intent
+    })
+}
+
 impl AppConfig {
     /// Load configuration from multiple sources
     pub fn load() -> Result<Self> {
+        Self::load_with_env(
+            Environment::with_prefix("WGB")
+                .prefix_separator("_")
+                .separator("__"),
+        )
+    }
+
+    /// Load configuration with `env` as the WGB_ environment source. The defaults pass
+    /// through the `config` crate, which drops empty maps and lists, so collection
+    /// fields that default to empty need `#[serde(default)]`.
+    fn load_with_env(env: Environment) -> Result<Self> {
         let config = Config::builder()
             // Start with default values
             .add_source(config::Config::try_from(&AppConfig::default())?)
             // Add configuration file if it exists
             .add_source(File::with_name("config").required(false))
             // Add environment variables with prefix WGB_
-            .add_source(
-                Environment::with_prefix("WGB")
-                    .prefix_separator("_")
-                    .separator("__"),
-            )
+            .add_source(env)
             // Legacy environment variables support (without prefix)
             .add_source(Self::legacy_env_source())
             .build()?;
@@ -223,6 +441,22 @@ impl AppConfig {
         if let Ok(enabled) = std::env::var("DISCORD_AUTO_ROLE_ENABLED") {
             builder = builder.set_override("discord.auto_role_enabled", enabled.parse::<bool>().unwrap_or(true)).unwrap();
         }
+        if let Ok(enabled) = std::env::var("DISCORD_AUTO_ROLE_GUILD_MATCHING") {
+            if let Ok(enabled) = enabled.parse::<bool>() {
+                builder = builder.set_override("discord.auto_role_guild_matching", enabled).unwrap();
+            }
+        }
+        if let Ok(intents) = std::env::var("DISCORD_INTENTS") {
+            let intents: Vec<String> = intents
+                .split(',')
+                .map(|name| name.trim().to_string())
+                .filter(|name| !name.is_empty())
+                .collect();
+            builder = builder.set_override("discord.intents", intents).unwrap();
+        }
+        if let Ok(locale) = std::env::var("DISCORD_LOCALE") {
+            builder = builder.set_override("discord.locale", locale).unwrap();
+        }
         if let Ok(api_key) = std::env::var("RAIDERIO_API_KEY") {
             builder = builder.set_override("raider_io.api_key", api_key).unwrap();
         }
@@ -239,6 +473,16 @@ impl AppConfig {
         if let Ok(log_level) = std::env::var("LOG_LEVEL") {
             builder = builder.set_override("logging.level", log_level).unwrap();
         }
+        if let Ok(hours) = std::env::var("REFRESH_INTERVAL_HOURS") {
+            if let Ok(hours) = hours.parse::<u64>() {
+                builder = builder.set_override("data.refresh_interval_hours", hours).unwrap();
+            }
+        }
+        if let Ok(secs) = std::env::var("GUILDS_COMMAND_TIMEOUT_SECS") {
+            if let Ok(secs) = secs.parse::<u64>() {
+                builder = builder.set_override("data.guilds_command_timeout_secs", secs).unwrap();
+            }
+        }
         if let Ok(log_file_enabled) = std::env::var("LOG_FILE_ENABLED") {
             if let Ok(enabled) = log_file_enabled.parse::<bool>() {
                 builder = builder.set_override("logging.file_enabled", enabled).unwrap();
@@ -271,6 +515,61 @@ impl AppConfig {
             )));
         }
 
+        if self.data.default_guild_limit == 0 || self.data.default_guild_limit > self.data.max_guild_limit {
+            return Err(BotError::Config(ConfigError::Message(
+                "Default guild limit must be between 1 and the max guild limit".to_string(),
+            )));
+        }
+
+        let base_url_valid = reqwest::Url::parse(&self.raider_io.base_url)
+            .map(|url| matches!(url.scheme(), "http" | "https") && url.has_host())
+            .unwrap_or(false);
+        if !base_url_valid {
+            return Err(BotError::Config(ConfigError::Message(format!(
+                "Raider.io base URL must be an absolute http(s) URL, got '{}'",
+                self.raider_io.base_url
+            ))));
+        }
+
+        if !is_valid_season(&self.raider_io.season) {
+            return Err(BotError::Config(ConfigError::Message(format!(
+                "Season must be 'current', 'previous' or 'season-...', got '{}'",
+                self.raider_io.season
+            ))));
+        }
+
+        // A pinned season should match the default raid tier, or /rank scores and /guilds
+        // progress describe different seasons
+        if self.raider_io.season.starts_with("season-") {
+            let expected = Season::for_tier(RaidTier::new(self.raider_io.default_season));
+            if expected.as_str() != self.raider_io.season && expected != Season::current() {
+                warn!(
+                    season = %self.raider_io.season,
+                    default_season = self.raider_io.default_season,
+                    expected = %expected,
+                    "Configured raider.io season does not match the default raid tier"
+                );
+            }
+        }
+
+        if self.database.max_connections == 0 {
+            return Err(BotError::Config(ConfigError::Message(
+                "Database max connections must be greater than 0".to_string(),
+            )));
+        }
+
+        if !(0.0..=1.0).contains(&self.database.min_swap_ratio) {
+            return Err(BotError::Config(ConfigError::Message(
+                "Database min swap ratio must be between 0 and 1".to_string(),
+            )));
+        }
+
+        if self.rate_limiting.per_realm_concurrency == Some(0) {
+            return Err(BotError::Config(ConfigError::Message(
+                "Per-realm concurrency must be greater than 0 when set".to_string(),
+            )));
+        }
+
         Ok(())
     }
 
@@ -308,6 +607,156 @@ mod tests {
         assert_eq!(config.data.batch_size, 100);
     }
 
+    #[test]
+    fn test_default_season_is_the_live_tier() {
+        let config = AppConfig::default();
+        assert_eq!(RaidTier::new(config.raider_io.default_season), RaidTier::manaforge_omega());
+        assert_eq!(Season::for_tier(RaidTier::new(config.raider_io.default_season)).as_str(), "season-tww-3");
+    }
+
+    #[test]
+    fn test_discord_config_ids_accept_strings_and_integers() {
+        // Environment overrides arrive as strings, config files may use integers
+        let load = |role_id: serde_json::Value| {
+            let mut discord = serde_json::to_value(DiscordConfig::default()).unwrap();
+            discord["auto_role_id"] = role_id;
+            serde_json::from_value::<DiscordConfig>(discord)
+        };
+
+        assert_eq!(load("12345".into()).unwrap().auto_role_id, Some(DiscordId::from(12345)));
+        assert_eq!(load(12345.into()).unwrap().auto_role_id, Some(DiscordId::from(12345)));
+        assert!(load("not-a-role".into()).is_err());
+    }
+
+    #[test]
+    fn test_load_from_env_only() {
+        // No config file: every field comes from the defaults or the environment
+        let env = Environment::with_prefix("WGB")
+            .prefix_separator("_")
+            .separator("__")
+            .source(Some(HashMap::from([("WGB_DISCORD__TOKEN".to_string(), "token".to_string())])));
+
+        let config = AppConfig::load_with_env(env).unwrap();
+        assert!(config.discord.guild_role_map.is_empty());
+        assert!(config.discord.admin_user_ids.is_empty());
+        assert!(config.data.realm_allowlist.is_empty());
+        assert!(config.raider_io.player_fields.is_empty());
+    }
+
+    #[test]
+    fn test_guild_role_lookup_is_case_insensitive() {
+        let mut config = DiscordConfig::default();
+        config.guild_role_map.insert("Thorned Horde".to_string(), DiscordId::from(42));
+        assert_eq!(config.guild_role_for("thorned horde"), Some(DiscordId::from(42)));
+        assert_eq!(config.guild_role_for("THORNED HORDE"), Some(DiscordId::from(42)));
+        assert_eq!(config.guild_role_for("Nomads TM"), None);
+    }
+
+    #[test]
+    fn test_is_admin_checks_configured_ids() {
+        let mut config = DiscordConfig::default();
+        assert!(!config.is_admin(42), "no admins are configured by default");
+
+        config.admin_user_ids = vec![DiscordId::from(42), DiscordId::from(7)];
+        assert!(config.is_admin(42));
+        assert!(config.is_admin(7));
+        assert!(!config.is_admin(8));
+    }
+
+    fn valid_config() -> AppConfig {
+        let mut config = AppConfig::default();
+        config.discord.token = "token".to_string();
+        config
+    }
+
+    #[test]
+    fn test_validate_accepts_defaults() {
+        assert!(valid_config().validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_rejects_bad_base_url() {
+        for base_url in ["", "raider.io/api/v1", "ftp://raider.io/api/v1", "https://"] {
+            let mut config = valid_config();
+            config.raider_io.base_url = base_url.to_string();
+            assert!(config.validate().is_err(), "base_url {:?}", base_url);
+        }
+    }
+
+    #[test]
+    fn test_validate_rejects_bad_season() {
+        for season in ["", "tww-3", "season-", "Season-TWW-3", "season tww 3"] {
+            let mut config = valid_config();
+            config.raider_io.season = season.to_string();
+            assert!(config.validate().is_err(), "season {:?}", season);
+        }
+
+        for season in ["current", "previous", "season-tww-3"] {
+            let mut config = valid_config();
+            config.raider_io.season = season.to_string();
+            assert!(config.validate().is_ok(), "season {:?}", season);
+        }
+    }
+
+    #[test]
+    fn test_parse_intents() {
+        let names = vec!["GUILD_MESSAGES".to_string(), "direct_messages".to_string()];
+        assert_eq!(
+            parse_intents(&names),
+            GatewayIntents::GUILD_MESSAGES | GatewayIntents::DIRECT_MESSAGES
+        );
+        assert!(DiscordConfig::default().gateway_intents().contains(GatewayIntents::GUILD_MEMBERS));
+    }
+
+    #[test]
+    fn test_parse_intents_skips_unknown_names() {
+        let names = vec!["GUILD_MESSAGES".to_string(), "NOT_AN_INTENT".to_string()];
+        assert_eq!(parse_intents(&names), GatewayIntents::GUILD_MESSAGES);
+        assert!(parse_intents(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_intents_without_unapproved_strips_guild_members() {
+        let intents = GatewayIntents::GUILDS | GatewayIntents::GUILD_MEMBERS;
+        let disallowed = BotError::Discord(serenity::Error::Gateway(GatewayError::DisallowedGatewayIntents));
+        assert_eq!(intents_without_unapproved(intents, &disallowed), Some(GatewayIntents::GUILDS));
+
+        // Nothing left to strip, or a different failure: don't retry
+        assert_eq!(intents_without_unapproved(GatewayIntents::GUILDS, &disallowed), None);
+        let invalid = BotError::Discord(serenity::Error::Gateway(GatewayError::InvalidGatewayIntents));
+        assert_eq!(intents_without_unapproved(intents, &invalid), None);
+    }
+
+    #[test]
+    fn test_realm_allowlist_restricts_guilds() {
+        let guilds = vec![
+            GuildUrl::new("Tarren Mill", "Thorned Horde"),
+            GuildUrl::new("Silvermoon", "Nexus"),
+            GuildUrl::new("Draenor", "Echo"),
+        ];
+
+        let mut all = guilds.clone();
+        DataConfig::default().retain_allowed_realms(&mut all);
+        assert_eq!(all, guilds);
+
+        // Blank entries, e.g. from an empty env var, don't restrict anything
+        let blank = DataConfig { realm_allowlist: vec!["".to_string(), "  ".to_string()], ..DataConfig::default() };
+        assert!(!blank.restricts_realms());
+        let mut all = guilds.clone();
+        blank.retain_allowed_realms(&mut all);
+        assert_eq!(all, guilds);
+
+        let data = DataConfig {
+            realm_allowlist: vec!["tarren mill".to_string(), "DRAENOR".to_string()],
+            ..DataConfig::default()
+        };
+        assert!(data.restricts_realms());
+        let mut restricted = guilds;
+        data.retain_allowed_realms(&mut restricted);
+        let names: Vec<&str> = restricted.iter().map(|g| g.name.as_str()).collect();
+        assert_eq!(names, vec!["Thorned Horde", "Echo"]);
+    }
+
     #[test]
     fn test_request_delay_calculation() {
         let mut config = AppConfig::default();
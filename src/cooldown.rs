@@ -0,0 +1,117 @@
+/// Per-user cooldowns for expensive slash commands
+use serenity::model::id::UserId;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Tracks when each user last ran each rate-limited command
+pub struct CommandCooldowns {
+    durations: HashMap<String, Duration>,
+    last_used: Mutex<HashMap<(UserId, String), Instant>>,
+}
+
+impl CommandCooldowns {
+    /// Build from the configured command name -> seconds map; zero disables a cooldown
+    pub fn new(seconds: &HashMap<String, u64>) -> Self {
+        let durations = seconds
+            .iter()
+            .filter(|(_, secs)| **secs > 0)
+            .map(|(command, secs)| (command.to_lowercase(), Duration::from_secs(*secs)))
+            .collect();
+        Self { durations, last_used: Mutex::new(HashMap::new()) }
+    }
+
+    /// Record a use of `command` by `user`, or return the time left if they are still on cooldown
+    pub fn check(&self, user: UserId, command: &str) -> Option<Duration> {
+        self.check_at(user, command, Instant::now())
+    }
+
+    fn check_at(&self, user: UserId, command: &str, now: Instant) -> Option<Duration> {
+        let command = command.to_lowercase();
+        let cooldown = *self.durations.get(&command)?;
+        let mut last_used = self.last_used.lock().unwrap();
+
+        if let Some(used_at) = last_used.get(&(user, command.clone())) {
+            let elapsed = now.saturating_duration_since(*used_at);
+            if elapsed < cooldown {
+                return Some(cooldown - elapsed);
+            }
+        }
+
+        // Drop expired entries so the map only holds users currently on cooldown
+        let durations = &self.durations;
+        last_used.retain(|(_, name), used_at| {
+            durations.get(name).is_some_and(|d| now.saturating_duration_since(*used_at) < *d)
+        });
+        last_used.insert((user, command), now);
+        None
+    }
+}
+
+/// Whole seconds left on a cooldown, rounded up so we never tell the user "0s"
+pub fn remaining_secs(remaining: Duration) -> u64 {
+    remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cooldowns() -> CommandCooldowns {
+        CommandCooldowns::new(&HashMap::from([
+            ("guilds".to_string(), 30),
+            ("rank".to_string(), 10),
+            ("player".to_string(), 0),
+        ]))
+    }
+
+    #[test]
+    fn test_cooldown_allows_then_denies_then_allows() {
+        let cooldowns = cooldowns();
+        let user = UserId(1);
+        let start = Instant::now();
+
+        assert_eq!(cooldowns.check_at(user, "guilds", start), None);
+        assert_eq!(
+            cooldowns.check_at(user, "guilds", start + Duration::from_secs(10)),
+            Some(Duration::from_secs(20))
+        );
+        // A denied attempt doesn't restart the cooldown
+        assert_eq!(
+            cooldowns.check_at(user, "guilds", start + Duration::from_secs(25)),
+            Some(Duration::from_secs(5))
+        );
+        assert_eq!(cooldowns.check_at(user, "guilds", start + Duration::from_secs(30)), None);
+        assert!(cooldowns.check_at(user, "guilds", start + Duration::from_secs(31)).is_some());
+    }
+
+    #[test]
+    fn test_cooldown_is_per_user_and_per_command() {
+        let cooldowns = cooldowns();
+        let start = Instant::now();
+
+        assert_eq!(cooldowns.check_at(UserId(1), "guilds", start), None);
+        assert_eq!(cooldowns.check_at(UserId(2), "guilds", start), None);
+        assert_eq!(cooldowns.check_at(UserId(1), "rank", start), None);
+        assert!(cooldowns.check_at(UserId(1), "rank", start + Duration::from_secs(5)).is_some());
+        assert_eq!(cooldowns.check_at(UserId(1), "rank", start + Duration::from_secs(10)), None);
+    }
+
+    #[test]
+    fn test_unconfigured_or_zero_cooldown_never_blocks() {
+        let cooldowns = cooldowns();
+        let start = Instant::now();
+
+        for _ in 0..3 {
+            assert_eq!(cooldowns.check_at(UserId(1), "player", start), None);
+            assert_eq!(cooldowns.check_at(UserId(1), "help", start), None);
+        }
+    }
+
+    #[test]
+    fn test_remaining_secs_rounds_up() {
+        assert_eq!(remaining_secs(Duration::from_secs(5)), 5);
+        assert_eq!(remaining_secs(Duration::from_millis(4100)), 5);
+        assert_eq!(remaining_secs(Duration::from_millis(300)), 1);
+    }
+}
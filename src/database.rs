@@ -1,14 +1,21 @@
 /// Database module with SQLite and migrations support
-use sqlx::{SqlitePool, Row, sqlite::SqliteQueryResult};
+use sqlx::{SqlitePool, Row, sqlite::{SqliteJournalMode, SqlitePoolOptions, SqliteQueryResult, SqliteRow}};
+use crate::config::{DatabaseConfig, Region};
 use crate::error::{BotError, Result};
-use crate::types::{GuildUrl, GuildName, RealmName, PlayerName};
+use crate::raider_io::PlayerData;
+use crate::types::{GuildUrl, GuildName, RealmName, PlayerName, MythicPlusScore};
+use futures::future::BoxFuture;
+use std::collections::HashSet;
 use std::path::Path;
+use std::time::Duration;
 use tracing::{info, warn, error};
 
 /// Database connection wrapper
 #[derive(Clone)]
 pub struct Database {
     pool: SqlitePool,
+    /// Smallest `members_tmp` size, as a fraction of `members`, that may be swapped in
+    min_swap_ratio: f64,
 }
 
 /// Guild data structure for database
@@ -42,22 +49,174 @@ pub struct DbMember {
     pub spec_1: f64,
     pub spec_2: f64,
     pub spec_3: f64,
+    pub spec_0_name: Option<String>,
+    pub spec_1_name: Option<String>,
+    pub spec_2_name: Option<String>,
+    pub spec_3_name: Option<String>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
 }
 
+/// Stored row for fetched player data. `updated_at` is set to now; `guild_realm` falls
+/// back to the player's realm when the guild's realm isn't known.
+impl From<&PlayerData> for DbMember {
+    fn from(player: &PlayerData) -> Self {
+        Self {
+            id: 0, // Will be auto-generated
+            name: player.name.to_string(),
+            realm: player.realm.to_string(),
+            guild_name: player.guild.as_ref().map(|g| g.to_string()),
+            guild_realm: Some(player.guild_realm.as_ref().unwrap_or(&player.realm).to_string()),
+            class: player.class.clone(),
+            spec: player.active_spec_name.clone(),
+            rio_score: Some(player.rio_all.value()), // Legacy field - kept for compatibility
+            ilvl: player.ilvl,
+            rio_all: player.rio_all.value(),
+            rio_dps: player.rio_dps.value(),
+            rio_healer: player.rio_healer.value(),
+            rio_tank: player.rio_tank.value(),
+            spec_0: player.spec_0.value(),
+            spec_1: player.spec_1.value(),
+            spec_2: player.spec_2.value(),
+            spec_3: player.spec_3.value(),
+            spec_0_name: player.spec_0_name.clone(),
+            spec_1_name: player.spec_1_name.clone(),
+            spec_2_name: player.spec_2_name.clone(),
+            spec_3_name: player.spec_3_name.clone(),
+            updated_at: chrono::Utc::now(),
+        }
+    }
+}
+
+impl From<&DbMember> for PlayerData {
+    fn from(member: &DbMember) -> Self {
+        Self {
+            name: PlayerName::from(member.name.clone()),
+            realm: RealmName::from(member.realm.clone()),
+            guild: member.guild_name.as_ref().map(|g| GuildName::from(g.clone())),
+            guild_realm: member.guild_realm.as_deref().map(RealmName::from),
+            class: member.class.clone(),
+            active_spec_name: member.spec.clone(),
+            ilvl: member.ilvl,
+            rio_all: MythicPlusScore::from(member.rio_all),
+            rio_dps: MythicPlusScore::from(member.rio_dps),
+            rio_healer: MythicPlusScore::from(member.rio_healer),
+            rio_tank: MythicPlusScore::from(member.rio_tank),
+            spec_0: MythicPlusScore::from(member.spec_0),
+            spec_1: MythicPlusScore::from(member.spec_1),
+            spec_2: MythicPlusScore::from(member.spec_2),
+            spec_3: MythicPlusScore::from(member.spec_3),
+            spec_0_name: member.spec_0_name.clone(),
+            spec_1_name: member.spec_1_name.clone(),
+            spec_2_name: member.spec_2_name.clone(),
+            spec_3_name: member.spec_3_name.clone(),
+        }
+    }
+}
+
+/// Snapshot of the database printed by `db-status`
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct DatabaseStatus {
+    pub guild_count: usize,
+    pub member_count: usize,
+    pub migrations: Vec<MigrationRecord>,
+    /// Registered migrations that haven't been applied
+    pub pending_migrations: Vec<String>,
+    /// When member data was last refreshed; `None` before the first parser run
+    pub last_updated: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// An executed migration
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct MigrationRecord {
+    pub name: String,
+    pub executed_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Outcome of a bulk guild import
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GuildImportSummary {
+    pub imported: usize,
+    /// Lines naming a guild that is already tracked
+    pub skipped: usize,
+    /// Lines that couldn't be parsed or inserted
+    pub errors: usize,
+    /// Why each rejected line was rejected, e.g. "line 3: unknown region 'xx' ..."
+    pub rejected: Vec<String>,
+}
+
+/// Whether `temp_count` rows may replace `active_count` rows; an empty members table
+/// can always be replaced
+fn swap_allowed(temp_count: usize, active_count: usize, min_ratio: f64) -> bool {
+    active_count == 0 || temp_count as f64 >= active_count as f64 * min_ratio
+}
+
+const INSERT_TEMP_MEMBER_SQL: &str = r#"
+    INSERT OR REPLACE INTO members_tmp 
+    (name, realm, guild_name, guild_realm, class, spec, rio_score, ilvl, 
+     rio_all, rio_dps, rio_healer, rio_tank, spec_0, spec_1, spec_2, spec_3,
+     spec_0_name, spec_1_name, spec_2_name, spec_3_name, updated_at)
+    VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+"#;
+
+const UPSERT_MEMBER_SQL: &str = r#"
+    INSERT INTO members 
+    (name, realm, guild_name, guild_realm, class, spec, rio_score, ilvl, 
+     rio_all, rio_dps, rio_healer, rio_tank, spec_0, spec_1, spec_2, spec_3,
+     spec_0_name, spec_1_name, spec_2_name, spec_3_name, updated_at)
+    VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+    ON CONFLICT(name, realm) DO UPDATE SET
+        guild_name = excluded.guild_name,
+        guild_realm = excluded.guild_realm,
+        class = excluded.class,
+        spec = excluded.spec,
+        rio_score = excluded.rio_score,
+        ilvl = excluded.ilvl,
+        rio_all = excluded.rio_all,
+        rio_dps = excluded.rio_dps,
+        rio_healer = excluded.rio_healer,
+        rio_tank = excluded.rio_tank,
+        spec_0 = excluded.spec_0,
+        spec_1 = excluded.spec_1,
+        spec_2 = excluded.spec_2,
+        spec_3 = excluded.spec_3,
+        spec_0_name = excluded.spec_0_name,
+        spec_1_name = excluded.spec_1_name,
+        spec_2_name = excluded.spec_2_name,
+        spec_3_name = excluded.spec_3_name,
+        updated_at = excluded.updated_at
+"#;
+
+type MigrationFn = for<'a> fn(&'a Database) -> BoxFuture<'a, Result<()>>;
+
+/// Every migration, in the order they run. `run_migrations` records each one after it
+/// succeeds; a migration that isn't listed here never runs.
+const MIGRATIONS: &[(&str, MigrationFn)] = &[
+    ("001_create_guilds_table", |db| Box::pin(db.migrate_001_create_guilds_table())),
+    ("002_create_members_tables", |db| Box::pin(db.migrate_002_create_members_tables())),
+    ("003_populate_guild_data", |db| Box::pin(db.migrate_003_populate_guild_data())),
+    ("004_add_rio_fields_to_members", |db| Box::pin(db.migrate_004_add_rio_fields_to_members())),
+    ("005_add_spec_names_to_members", |db| Box::pin(db.migrate_005_add_spec_names_to_members())),
+    ("006_add_region_to_guilds", |db| Box::pin(db.migrate_006_add_region_to_guilds())),
+];
+
 impl Database {
-    /// Create a new database connection
-    pub async fn new(database_url: &str) -> Result<Self> {
+    /// Create a new database connection pool
+    pub async fn new(config: &DatabaseConfig) -> Result<Self> {
         // SQLx requires specific format for SQLite - create database file if needed
-        let database_path = database_url.replace("sqlite://", "");
-        let pool = SqlitePool::connect_with(
-            sqlx::sqlite::SqliteConnectOptions::new()
-                .filename(&database_path)
-                .create_if_missing(true)
-        ).await
-        .map_err(|e| BotError::Database(format!("Failed to connect to database: {}", e)))?;
-
-        let db = Self { pool };
+        let database_path = config.url.replace("sqlite://", "");
+        // WAL lets /rank readers proceed while the parser writes batches
+        let pool = SqlitePoolOptions::new()
+            .max_connections(config.max_connections)
+            .connect_with(
+                sqlx::sqlite::SqliteConnectOptions::new()
+                    .filename(&database_path)
+                    .create_if_missing(true)
+                    .journal_mode(SqliteJournalMode::Wal)
+                    .busy_timeout(Duration::from_millis(config.busy_timeout_ms))
+            ).await
+            .map_err(|e| BotError::Database(format!("Failed to connect to database: {}", e)))?;
+
+        let db = Self { pool, min_swap_ratio: config.min_swap_ratio };
         db.run_migrations().await?;
         Ok(db)
     }
@@ -78,11 +237,14 @@ impl Database {
         .await
         .map_err(|e| BotError::Database(format!("Failed to create migrations table: {}", e)))?;
 
-        // Run each migration
-        self.migrate_001_create_guilds_table().await?;
-        self.migrate_002_create_members_tables().await?;
-        self.migrate_003_populate_guild_data().await?;
-        self.migrate_004_add_rio_fields_to_members().await?;
+        for (name, migrate) in MIGRATIONS {
+            if self.migration_exists(name).await? {
+                continue;
+            }
+            info!("Running migration: {}", name);
+            migrate(self).await?;
+            self.record_migration(name).await?;
+        }
         
         info!("Database migrations completed successfully");
         Ok(())
@@ -91,12 +253,6 @@ impl Database {
     /// Migration 001: Create guilds table
     async fn migrate_001_create_guilds_table(&self) -> Result<()> {
         let migration_name = "001_create_guilds_table";
-        
-        if self.migration_exists(migration_name).await? {
-            return Ok(());
-        }
-
-        info!("Running migration: {}", migration_name);
 
         sqlx::query(r#"
             CREATE TABLE guilds (
@@ -112,19 +268,12 @@ impl Database {
         .await
         .map_err(|e| BotError::Database(format!("Migration {} failed: {}", migration_name, e)))?;
 
-        self.record_migration(migration_name).await?;
         Ok(())
     }
 
     /// Migration 002: Create members tables (active and temporary)
     async fn migrate_002_create_members_tables(&self) -> Result<()> {
         let migration_name = "002_create_members_tables";
-        
-        if self.migration_exists(migration_name).await? {
-            return Ok(());
-        }
-
-        info!("Running migration: {}", migration_name);
 
         // Active members table
         sqlx::query(r#"
@@ -166,20 +315,11 @@ impl Database {
         .await
         .map_err(|e| BotError::Database(format!("Migration {} failed: {}", migration_name, e)))?;
 
-        self.record_migration(migration_name).await?;
         Ok(())
     }
 
     /// Migration 003: Populate guild data
     async fn migrate_003_populate_guild_data(&self) -> Result<()> {
-        let migration_name = "003_populate_guild_data";
-        
-        if self.migration_exists(migration_name).await? {
-            return Ok(());
-        }
-
-        info!("Running migration: {}", migration_name);
-
         // Guild data embedded in migration (originally from uaguildlist.txt)
         let guild_data = vec![
             ("Tarren Mill", "Нехай Щастить"),
@@ -265,19 +405,12 @@ impl Database {
         }
 
         info!("Populated {} guilds from migration", guild_count);
-        self.record_migration(migration_name).await?;
         Ok(())
     }
 
     /// Migration 004: Add RIO fields to members tables to match JSON structure
     async fn migrate_004_add_rio_fields_to_members(&self) -> Result<()> {
         let migration_name = "004_add_rio_fields_to_members";
-        
-        if self.migration_exists(migration_name).await? {
-            return Ok(());
-        }
-
-        info!("Running migration: {}", migration_name);
 
         // Add missing RIO fields to members table
         let alter_statements = vec![
@@ -318,7 +451,36 @@ impl Database {
         }
 
         info!("Added RIO fields to members and members_tmp tables");
-        self.record_migration(migration_name).await?;
+        Ok(())
+    }
+
+    /// Migration 005: Add spec names so spec_0..spec_3 scores can be labelled
+    async fn migrate_005_add_spec_names_to_members(&self) -> Result<()> {
+        let migration_name = "005_add_spec_names_to_members";
+
+        for table in ["members", "members_tmp"] {
+            for column in ["spec_0_name", "spec_1_name", "spec_2_name", "spec_3_name"] {
+                sqlx::query(&format!("ALTER TABLE {} ADD COLUMN {} TEXT", table, column))
+                    .execute(&self.pool)
+                    .await
+                    .map_err(|e| BotError::Database(format!("Migration {} failed: {}", migration_name, e)))?;
+            }
+        }
+
+        info!("Added spec name fields to members and members_tmp tables");
+        Ok(())
+    }
+
+    /// Migration 006: Store each guild's region; existing rows are backfilled to 'eu'
+    async fn migrate_006_add_region_to_guilds(&self) -> Result<()> {
+        let migration_name = "006_add_region_to_guilds";
+
+        sqlx::query("ALTER TABLE guilds ADD COLUMN region TEXT NOT NULL DEFAULT 'eu'")
+            .execute(&self.pool)
+            .await
+            .map_err(|e| BotError::Database(format!("Migration {} failed: {}", migration_name, e)))?;
+
+        info!("Added region column to guilds table");
         Ok(())
     }
 
@@ -354,52 +516,40 @@ impl Database {
         let content = std::fs::read_to_string(file_path)
             .map_err(|e| BotError::Io(e))?;
 
-        let mut imported = 0;
-        let mut errors = 0;
+        let summary = self.import_guilds_from_text(&content).await;
+        info!("Imported {} guilds from {} (errors: {})", summary.imported, file_path, summary.errors);
+        Ok(summary.imported)
+    }
+
+    /// Import guilds from one entry per line (`realm=...&name=...` or `Realm/Guild`).
+    /// Blank lines and `#` comments are ignored; guilds already tracked count as skipped.
+    pub async fn import_guilds_from_text(&self, text: &str) -> GuildImportSummary {
+        let mut summary = GuildImportSummary::default();
 
-        for line in content.lines() {
+        for (index, line) in text.lines().enumerate() {
             let trimmed = line.trim();
             if trimmed.is_empty() || trimmed.starts_with('#') {
                 continue;
             }
 
-            if let Some(guild_url) = self.parse_guild_url(trimmed) {
-                match self.insert_guild(&guild_url).await {
-                    Ok(_) => imported += 1,
+            match GuildUrl::parse(trimmed) {
+                Ok(guild_url) => match self.insert_guild(&guild_url).await {
+                    Ok(result) if result.rows_affected() > 0 => summary.imported += 1,
+                    Ok(_) => summary.skipped += 1,
                     Err(e) => {
                         error!("Failed to insert guild {}: {}", trimmed, e);
-                        errors += 1;
+                        summary.errors += 1;
                     }
+                },
+                Err(reason) => {
+                    warn!("Failed to parse guild URL on line {}: {} ({})", index + 1, trimmed, reason);
+                    summary.errors += 1;
+                    summary.rejected.push(format!("line {}: {}", index + 1, reason));
                 }
-            } else {
-                warn!("Failed to parse guild URL: {}", trimmed);
-                errors += 1;
             }
         }
 
-        info!("Imported {} guilds from {} (errors: {})", imported, file_path, errors);
-        Ok(imported)
-    }
-
-    /// Parse guild URL from string format
-    fn parse_guild_url(&self, url_str: &str) -> Option<GuildUrl> {
-        let mut realm = None;
-        let mut guild = None;
-
-        for part in url_str.split('&') {
-            if let Some((key, value)) = part.split_once('=') {
-                match key {
-                    "realm" => realm = Some(RealmName::from(value)),
-                    "name" => guild = Some(GuildName::from(value)),
-                    _ => {}
-                }
-            }
-        }
-
-        match (realm, guild) {
-            (Some(realm), Some(name)) => Some(GuildUrl { realm, name }),
-            _ => None,
-        }
+        summary
     }
 
     /// Insert guild into database
@@ -407,12 +557,13 @@ impl Database {
         let url_str = format!("realm={}&name={}", guild_url.realm, guild_url.name);
         
         sqlx::query(r#"
-            INSERT OR IGNORE INTO guilds (name, realm, url)
-            VALUES (?, ?, ?)
+            INSERT OR IGNORE INTO guilds (name, realm, url, region)
+            VALUES (?, ?, ?, ?)
         "#)
         .bind(guild_url.name.to_string())
         .bind(guild_url.realm.to_string())
         .bind(url_str)
+        .bind(guild_url.region.unwrap_or(Region::Eu).to_string())
         .execute(&self.pool)
         .await
         .map_err(|e| BotError::Database(format!("Failed to insert guild: {}", e)))
@@ -420,7 +571,7 @@ impl Database {
 
     /// Get all guilds from database
     pub async fn get_all_guilds(&self) -> Result<Vec<GuildUrl>> {
-        let rows = sqlx::query("SELECT name, realm FROM guilds ORDER BY name")
+        let rows = sqlx::query("SELECT name, realm, region FROM guilds ORDER BY name")
             .fetch_all(&self.pool)
             .await
             .map_err(|e| BotError::Database(format!("Failed to fetch guilds: {}", e)))?;
@@ -429,12 +580,31 @@ impl Database {
             GuildUrl {
                 name: GuildName::from(row.get::<String, _>("name")),
                 realm: RealmName::from(row.get::<String, _>("realm")),
+                region: Region::from_code(&row.get::<String, _>("region")),
             }
         }).collect();
 
         Ok(guilds)
     }
 
+    /// Get the distinct realms of all tracked guilds
+    pub async fn distinct_realms(&self) -> Result<Vec<RealmName>> {
+        let rows = sqlx::query("SELECT DISTINCT realm FROM guilds")
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| BotError::Database(format!("Failed to fetch realms: {}", e)))?;
+
+        let mut realms: Vec<RealmName> = rows
+            .into_iter()
+            .map(|row| RealmName::from(row.get::<String, _>("realm")))
+            .collect();
+        // Rows may differ only in spelling ("Tarren Mill" vs "tarren-mill")
+        realms.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+        realms.dedup();
+
+        Ok(realms)
+    }
+
     /// Clear temporary members table
     pub async fn clear_temp_members(&self) -> Result<()> {
         sqlx::query("DELETE FROM members_tmp")
@@ -445,14 +615,83 @@ impl Database {
         Ok(())
     }
 
-    /// Insert member into temporary table
-    pub async fn insert_temp_member(&self, member: &DbMember) -> Result<()> {
-        sqlx::query(r#"
-            INSERT OR REPLACE INTO members_tmp 
-            (name, realm, guild_name, guild_realm, class, spec, rio_score, ilvl, 
-             rio_all, rio_dps, rio_healer, rio_tank, spec_0, spec_1, spec_2, spec_3, updated_at)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
-        "#)
+    /// Insert a batch of members into the temporary table in a single transaction
+    pub async fn insert_temp_members_batch(&self, members: &[DbMember]) -> Result<()> {
+        let mut tx = self.pool.begin().await
+            .map_err(|e| BotError::Database(format!("Failed to start transaction: {}", e)))?;
+
+        for member in members {
+            Self::insert_temp_member_query(member)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| BotError::Database(format!(
+                    "Failed to insert temp member {}-{}: {}", member.name, member.realm, e
+                )))?;
+        }
+
+        tx.commit().await
+            .map_err(|e| BotError::Database(format!("Failed to commit member batch: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Insert or update a single member directly in the active members table,
+    /// leaving every other row (including other guilds' members) untouched
+    pub async fn upsert_member(&self, member: &DbMember) -> Result<()> {
+        Self::member_query(UPSERT_MEMBER_SQL, member)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| BotError::Database(format!(
+                "Failed to upsert member {}-{}: {}", member.name, member.realm, e
+            )))?;
+
+        Ok(())
+    }
+
+    /// Delete one guild's members that are missing from its current `roster` (stored
+    /// realm and name keys) and haven't been refreshed since `cutoff`. Other guilds' rows
+    /// are never touched. Returns the number removed.
+    pub async fn prune_guild_members(
+        &self,
+        guild_name: &str,
+        guild_realm: &str,
+        roster: &HashSet<(String, String)>,
+        cutoff: chrono::DateTime<chrono::Utc>,
+    ) -> Result<u64> {
+        let rows = sqlx::query("SELECT id, name, realm FROM members WHERE guild_name = ? AND guild_realm = ? AND updated_at < ?")
+            .bind(guild_name)
+            .bind(guild_realm)
+            .bind(cutoff)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| BotError::Database(format!("Failed to find stale guild members: {}", e)))?;
+        let stale: Vec<i64> = rows
+            .into_iter()
+            .filter(|row| !roster.contains(&(row.get::<String, _>("realm"), row.get::<String, _>("name"))))
+            .map(|row| row.get("id"))
+            .collect();
+
+        let mut tx = self.pool.begin().await
+            .map_err(|e| BotError::Database(format!("Failed to start transaction: {}", e)))?;
+        for id in &stale {
+            sqlx::query("DELETE FROM members WHERE id = ?")
+                .bind(id)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| BotError::Database(format!("Failed to prune member {}: {}", id, e)))?;
+        }
+        tx.commit().await
+            .map_err(|e| BotError::Database(format!("Failed to commit member prune: {}", e)))?;
+
+        Ok(stale.len() as u64)
+    }
+
+    fn insert_temp_member_query(member: &DbMember) -> sqlx::query::Query<'_, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'_>> {
+        Self::member_query(INSERT_TEMP_MEMBER_SQL, member)
+    }
+
+    fn member_query<'q>(sql: &'q str, member: &'q DbMember) -> sqlx::query::Query<'q, sqlx::Sqlite, sqlx::sqlite::SqliteArguments<'q>> {
+        sqlx::query(sql)
         .bind(&member.name)
         .bind(&member.realm)
         .bind(&member.guild_name)
@@ -469,16 +708,45 @@ impl Database {
         .bind(member.spec_1)
         .bind(member.spec_2)
         .bind(member.spec_3)
+        .bind(&member.spec_0_name)
+        .bind(&member.spec_1_name)
+        .bind(&member.spec_2_name)
+        .bind(&member.spec_3_name)
         .bind(member.updated_at)
-        .execute(&self.pool)
-        .await
-        .map_err(|e| BotError::Database(format!("Failed to insert temp member: {}", e)))?;
+    }
 
-        Ok(())
+    /// Get the `(realm, name)` keys of members already stored in the temporary table
+    pub async fn temp_member_keys(&self) -> Result<HashSet<(String, String)>> {
+        let rows = sqlx::query("SELECT realm, name FROM members_tmp")
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| BotError::Database(format!("Failed to fetch temp member keys: {}", e)))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| (row.get::<String, _>("realm"), row.get::<String, _>("name")))
+            .collect())
     }
 
     /// Swap temporary table with active members table
     pub async fn swap_members_tables(&self) -> Result<()> {
+        // Refuse to replace a populated roster with a mostly empty one (e.g. after an API outage)
+        let (temp_count, active_count) = (self.temp_member_count().await?, self.member_count().await?);
+        if !swap_allowed(temp_count, active_count, self.min_swap_ratio) {
+            error!(
+                temp_count = temp_count,
+                active_count = active_count,
+                min_swap_ratio = self.min_swap_ratio,
+                "members_tmp is too small, keeping the current members table"
+            );
+            return Err(BotError::Database(format!(
+                "Refusing to swap: members_tmp has {} rows, fewer than {:.0}% of the {} current members",
+                temp_count,
+                self.min_swap_ratio * 100.0,
+                active_count
+            )));
+        }
+
         info!("Swapping members tables (tmp -> active)");
 
         // Use transaction for atomic swap
@@ -523,6 +791,10 @@ impl Database {
                 spec_1 REAL DEFAULT 0,
                 spec_2 REAL DEFAULT 0,
                 spec_3 REAL DEFAULT 0,
+                spec_0_name TEXT,
+                spec_1_name TEXT,
+                spec_2_name TEXT,
+                spec_3_name TEXT,
                 updated_at DATETIME DEFAULT CURRENT_TIMESTAMP,
                 UNIQUE(name, realm)
             )
@@ -561,28 +833,7 @@ impl Database {
             .await
             .map_err(|e| BotError::Database(format!("Failed to fetch members: {}", e)))?;
 
-        let members = rows.into_iter().map(|row| {
-            DbMember {
-                id: row.get("id"),
-                name: row.get("name"),
-                realm: row.get("realm"),
-                guild_name: row.get("guild_name"),
-                guild_realm: row.get("guild_realm"),
-                class: row.get("class"),
-                spec: row.get("spec"),
-                rio_score: row.get("rio_score"),
-                ilvl: row.get("ilvl"),
-                rio_all: row.get("rio_all"),
-                rio_dps: row.get("rio_dps"),
-                rio_healer: row.get("rio_healer"),
-                rio_tank: row.get("rio_tank"),
-                spec_0: row.get("spec_0"),
-                spec_1: row.get("spec_1"),
-                spec_2: row.get("spec_2"),
-                spec_3: row.get("spec_3"),
-                updated_at: row.get("updated_at"),
-            }
-        }).collect();
+        let members = rows.iter().map(Self::member_from_row).collect();
 
         Ok(members)
     }
@@ -592,39 +843,226 @@ impl Database {
         let rows = sqlx::query(r#"
             SELECT id, name, realm, guild_name, guild_realm, class, spec, 
                    rio_score, ilvl, rio_all, rio_dps, rio_healer, rio_tank,
-                   spec_0, spec_1, spec_2, spec_3, updated_at
+                   spec_0, spec_1, spec_2, spec_3,
+                   spec_0_name, spec_1_name, spec_2_name, spec_3_name, updated_at
             FROM members
         "#)
         .fetch_all(&self.pool)
         .await
         .map_err(|e| BotError::Database(format!("Failed to fetch members: {}", e)))?;
 
-        let members = rows.into_iter().map(|row| {
-            DbMember {
-                id: row.get("id"),
-                name: row.get("name"),
-                realm: row.get("realm"),
-                guild_name: row.get("guild_name"),
-                guild_realm: row.get("guild_realm"),
-                class: row.get("class"),
-                spec: row.get("spec"),
-                rio_score: row.get("rio_score"),
-                ilvl: row.get("ilvl"),
-                rio_all: row.get("rio_all"),
-                rio_dps: row.get("rio_dps"),
-                rio_healer: row.get("rio_healer"),
-                rio_tank: row.get("rio_tank"),
-                spec_0: row.get("spec_0"),
-                spec_1: row.get("spec_1"),
-                spec_2: row.get("spec_2"),
-                spec_3: row.get("spec_3"),
-                updated_at: row.get("updated_at"),
-            }
-        }).collect();
+        let members = rows.iter().map(Self::member_from_row).collect();
 
         Ok(members)
     }
 
+    /// Get a single member by character name and realm
+    pub async fn get_member(&self, name: &str, realm: &str) -> Result<Option<DbMember>> {
+        let name = PlayerName::from(name);
+        let realm = RealmName::from(realm);
+
+        let row = sqlx::query(r#"
+            SELECT id, name, realm, guild_name, guild_realm, class, spec, 
+                   rio_score, ilvl, rio_all, rio_dps, rio_healer, rio_tank,
+                   spec_0, spec_1, spec_2, spec_3,
+                   spec_0_name, spec_1_name, spec_2_name, spec_3_name, updated_at
+            FROM members
+            WHERE name = ? AND realm = ?
+        "#)
+        .bind(name.as_str())
+        .bind(realm.as_str())
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| BotError::Database(format!("Failed to fetch member: {}", e)))?;
+
+        Ok(row.as_ref().map(Self::member_from_row))
+    }
+
+    /// Find members with the given character name on any realm
+    pub async fn find_members_by_name(&self, name: &str) -> Result<Vec<DbMember>> {
+        let name = PlayerName::from(name);
+
+        let rows = sqlx::query(r#"
+            SELECT id, name, realm, guild_name, guild_realm, class, spec, 
+                   rio_score, ilvl, rio_all, rio_dps, rio_healer, rio_tank,
+                   spec_0, spec_1, spec_2, spec_3,
+                   spec_0_name, spec_1_name, spec_2_name, spec_3_name, updated_at
+            FROM members
+            WHERE name = ?
+            ORDER BY rio_all DESC
+        "#)
+        .bind(name.as_str())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| BotError::Database(format!("Failed to find members by name: {}", e)))?;
+
+        Ok(rows.iter().map(Self::member_from_row).collect())
+    }
+
+    /// Members of a guild (name matched case-insensitively, in Rust like
+    /// [`Self::search_members`]), highest RIO first
+    pub async fn get_guild_members(&self, guild_name: &str) -> Result<Vec<DbMember>> {
+        let guild_name = guild_name.trim().to_lowercase();
+
+        let rows = sqlx::query(r#"
+            SELECT id, name, realm, guild_name, guild_realm, class, spec, 
+                   rio_score, ilvl, rio_all, rio_dps, rio_healer, rio_tank,
+                   spec_0, spec_1, spec_2, spec_3,
+                   spec_0_name, spec_1_name, spec_2_name, spec_3_name, updated_at
+            FROM members
+            WHERE guild_name IS NOT NULL
+            ORDER BY rio_all DESC
+        "#)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| BotError::Database(format!("Failed to get guild members: {}", e)))?;
+
+        Ok(rows
+            .iter()
+            .filter(|row| row.get::<String, _>("guild_name").to_lowercase() == guild_name)
+            .map(Self::member_from_row)
+            .collect())
+    }
+
+    /// Members whose name contains `query`, case-insensitively, highest RIO first.
+    /// SQLite's LIKE only folds ASCII case, so the match is done in Rust to handle
+    /// Cyrillic names; the members table is small enough to scan.
+    pub async fn search_members(&self, query: &str, limit: usize) -> Result<Vec<DbMember>> {
+        let query = query.trim().to_lowercase();
+        if query.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let rows = sqlx::query(r#"
+            SELECT id, name, realm, guild_name, guild_realm, class, spec, 
+                   rio_score, ilvl, rio_all, rio_dps, rio_healer, rio_tank,
+                   spec_0, spec_1, spec_2, spec_3,
+                   spec_0_name, spec_1_name, spec_2_name, spec_3_name, updated_at
+            FROM members
+            ORDER BY rio_all DESC
+        "#)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| BotError::Database(format!("Failed to search members: {}", e)))?;
+
+        Ok(rows
+            .iter()
+            .filter(|row| row.get::<String, _>("name").to_lowercase().contains(&query))
+            .take(limit)
+            .map(Self::member_from_row)
+            .collect())
+    }
+
+    /// The highest-RIO member of each class, as (class, member) pairs ordered by class.
+    /// Classes without any tracked member are simply absent.
+    pub async fn top_player_per_class(&self) -> Result<Vec<(String, DbMember)>> {
+        let rows = sqlx::query(r#"
+            SELECT id, name, realm, guild_name, guild_realm, class, spec,
+                   rio_score, ilvl, rio_all, rio_dps, rio_healer, rio_tank,
+                   spec_0, spec_1, spec_2, spec_3,
+                   spec_0_name, spec_1_name, spec_2_name, spec_3_name, updated_at
+            FROM (
+                SELECT *, ROW_NUMBER() OVER (PARTITION BY class ORDER BY rio_all DESC, name) AS class_rank
+                FROM members
+                WHERE class IS NOT NULL
+            )
+            WHERE class_rank = 1
+            ORDER BY class
+        "#)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| BotError::Database(format!("Failed to get top player per class: {}", e)))?;
+
+        Ok(rows
+            .iter()
+            .map(Self::member_from_row)
+            .filter_map(|member| Some((member.class.clone()?, member)))
+            .collect())
+    }
+
+    /// Map a `members`/`members_tmp` row to a DbMember
+    fn member_from_row(row: &SqliteRow) -> DbMember {
+        DbMember {
+            id: row.get("id"),
+            name: row.get("name"),
+            realm: row.get("realm"),
+            guild_name: row.get("guild_name"),
+            guild_realm: row.get("guild_realm"),
+            class: row.get("class"),
+            spec: row.get("spec"),
+            rio_score: row.get("rio_score"),
+            ilvl: row.get("ilvl"),
+            rio_all: row.get("rio_all"),
+            rio_dps: row.get("rio_dps"),
+            rio_healer: row.get("rio_healer"),
+            rio_tank: row.get("rio_tank"),
+            spec_0: row.get("spec_0"),
+            spec_1: row.get("spec_1"),
+            spec_2: row.get("spec_2"),
+            spec_3: row.get("spec_3"),
+            spec_0_name: row.get("spec_0_name"),
+            spec_1_name: row.get("spec_1_name"),
+            spec_2_name: row.get("spec_2_name"),
+            spec_3_name: row.get("spec_3_name"),
+            updated_at: row.get("updated_at"),
+        }
+    }
+
+    /// When the stored member data was last refreshed
+    pub async fn members_last_updated(&self) -> Result<Option<chrono::DateTime<chrono::Utc>>> {
+        let row = sqlx::query("SELECT MAX(updated_at) as last_updated FROM members")
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| BotError::Database(format!("Failed to get last update time: {}", e)))?;
+
+        Ok(row.get("last_updated"))
+    }
+
+    /// Number of rows in the temporary members table
+    pub async fn temp_member_count(&self) -> Result<usize> {
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM members_tmp")
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| BotError::Database(format!("Failed to get temp member count: {}", e)))?;
+        Ok(count as usize)
+    }
+
+    /// Number of rows in the active members table
+    pub async fn member_count(&self) -> Result<usize> {
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM members")
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| BotError::Database(format!("Failed to get member count: {}", e)))?;
+        Ok(count as usize)
+    }
+
+    /// Size of the database file in bytes, including free pages
+    pub async fn file_size(&self) -> Result<u64> {
+        let size: i64 = sqlx::query_scalar("SELECT page_count * page_size FROM pragma_page_count(), pragma_page_size()")
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| BotError::Database(format!("Failed to get database size: {}", e)))?;
+        Ok(size as u64)
+    }
+
+    /// Rebuild the database file, releasing the free pages left behind by table swaps
+    pub async fn vacuum(&self) -> Result<()> {
+        sqlx::query("VACUUM")
+            .execute(&self.pool)
+            .await
+            .map_err(|e| BotError::Database(format!("Failed to vacuum database: {}", e)))?;
+        Ok(())
+    }
+
+    /// Refresh the statistics SQLite's query planner uses to pick indexes
+    pub async fn analyze(&self) -> Result<()> {
+        sqlx::query("ANALYZE")
+            .execute(&self.pool)
+            .await
+            .map_err(|e| BotError::Database(format!("Failed to analyze database: {}", e)))?;
+        Ok(())
+    }
+
     /// Get database statistics
     pub async fn get_stats(&self) -> Result<(usize, usize)> {
         let guild_count = sqlx::query("SELECT COUNT(*) as count FROM guilds")
@@ -658,4 +1096,499 @@ impl Database {
 
         Ok(migrations)
     }
-}
\ No newline at end of file
+
+    /// Every registered migration in run order, with whether it has been applied
+    pub async fn migration_status(&self) -> Result<Vec<(String, bool)>> {
+        let applied: HashSet<String> = self.get_migrations().await?.into_iter().map(|(name, _)| name).collect();
+        Ok(MIGRATIONS
+            .iter()
+            .map(|(name, _)| (name.to_string(), applied.contains(*name)))
+            .collect())
+    }
+
+    /// Collect counts, migrations and the last refresh time for `db-status`
+    pub async fn status(&self) -> Result<DatabaseStatus> {
+        let (guild_count, member_count) = self.get_stats().await?;
+        let migrations = self
+            .get_migrations()
+            .await?
+            .into_iter()
+            .map(|(name, executed_at)| MigrationRecord { name, executed_at })
+            .collect();
+        let pending_migrations = self
+            .migration_status()
+            .await?
+            .into_iter()
+            .filter(|(_, applied)| !applied)
+            .map(|(name, _)| name)
+            .collect();
+        let last_updated = self.members_last_updated().await?;
+
+        Ok(DatabaseStatus { guild_count, member_count, migrations, pending_migrations, last_updated })
+    }
+}
+
+#[cfg(test)]
+impl Database {
+    /// Open a fresh, fully migrated database in a temporary file
+    pub async fn open_temp() -> Self {
+        Self::open_temp_with(DatabaseConfig::default()).await
+    }
+
+    /// Remove every tracked guild
+    pub async fn clear_guilds(&self) {
+        sqlx::query("DELETE FROM guilds")
+            .execute(&self.pool)
+            .await
+            .expect("Failed to clear guilds");
+    }
+
+    /// Open a fresh temporary database with custom pool settings
+    pub async fn open_temp_with(config: DatabaseConfig) -> Self {
+        let path = std::env::temp_dir().join(format!("wow_guild_bot_test_{}.db", uuid::Uuid::new_v4()));
+        let config = DatabaseConfig {
+            url: format!("sqlite://{}", path.display()),
+            ..config
+        };
+        Self::new(&config)
+            .await
+            .expect("Failed to create test database")
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    pub(crate) fn test_member(name: &str, realm: &str, guild: Option<&str>, rio_all: f64) -> DbMember {
+        DbMember {
+            id: 0,
+            name: PlayerName::from(name).to_string(),
+            realm: RealmName::from(realm).to_string(),
+            guild_name: guild.map(str::to_string),
+            guild_realm: Some(RealmName::from(realm).to_string()),
+            class: Some("Mage".to_string()),
+            spec: Some("Frost".to_string()),
+            rio_score: Some(rio_all),
+            ilvl: None,
+            rio_all,
+            rio_dps: rio_all,
+            rio_healer: 0.0,
+            rio_tank: 0.0,
+            spec_0: 0.0,
+            spec_1: 0.0,
+            spec_2: rio_all,
+            spec_3: 0.0,
+            spec_0_name: Some("Arcane".to_string()),
+            spec_1_name: Some("Fire".to_string()),
+            spec_2_name: Some("Frost".to_string()),
+            spec_3_name: None,
+            updated_at: chrono::Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pool_uses_configured_settings() {
+        let config = DatabaseConfig {
+            max_connections: 3,
+            ..DatabaseConfig::default()
+        };
+        let database = Database::open_temp_with(config).await;
+        assert_eq!(database.pool.options().get_max_connections(), 3);
+
+        let journal_mode: String = sqlx::query_scalar("PRAGMA journal_mode")
+            .fetch_one(&database.pool)
+            .await
+            .unwrap();
+        assert_eq!(journal_mode, "wal");
+    }
+
+    #[tokio::test]
+    async fn test_insert_temp_members_batch() {
+        let database = Database::open_temp().await;
+        database.clear_temp_members().await.unwrap();
+
+        let members: Vec<DbMember> = (0..250)
+            .map(|i| test_member(&format!("Player{}", i), "Tarren Mill", None, 2000.0 + i as f64))
+            .collect();
+        database.insert_temp_members_batch(&members).await.unwrap();
+
+        assert_eq!(database.temp_member_keys().await.unwrap().len(), 250);
+    }
+
+    #[tokio::test]
+    async fn test_vacuum_populated_database() {
+        let database = Database::open_temp().await;
+        database.clear_temp_members().await.unwrap();
+        let members: Vec<DbMember> = (0..500)
+            .map(|i| test_member(&format!("Player{}", i), "Tarren Mill", None, 2000.0 + i as f64))
+            .collect();
+        database.insert_temp_members_batch(&members).await.unwrap();
+        database.swap_members_tables().await.unwrap();
+        let before = database.file_size().await.unwrap();
+
+        database.vacuum().await.unwrap();
+        // The swap dropped the old members table, so vacuuming frees its pages
+        assert!(database.file_size().await.unwrap() <= before);
+
+        database.analyze().await.unwrap();
+        assert_eq!(database.member_count().await.unwrap(), 500);
+    }
+
+    #[test]
+    fn test_player_data_round_trips_through_db_member() {
+        let base = PlayerData::from(&test_member("Frostbolt", "Tarren Mill", Some("Thorned Horde"), 2847.5));
+        let variants = [
+            base.clone(),
+            PlayerData { ilvl: Some(678), ..base.clone() },
+            PlayerData { guild: None, class: None, active_spec_name: None, ..base.clone() },
+            PlayerData {
+                realm: RealmName::from("Howling Fjord"),
+                rio_dps: MythicPlusScore::from(2811.25),
+                rio_healer: MythicPlusScore::from(1200.0),
+                rio_tank: MythicPlusScore::from(3012.75),
+                spec_0: MythicPlusScore::from(1.5),
+                spec_1: MythicPlusScore::from(2.5),
+                spec_3: MythicPlusScore::from(3.5),
+                spec_0_name: Some("Arcane".to_string()),
+                spec_1_name: Some("Fire".to_string()),
+                spec_2_name: Some("Frost".to_string()),
+                spec_3_name: None,
+                ..base
+            },
+        ];
+
+        for player in variants {
+            assert_eq!(PlayerData::from(&DbMember::from(&player)), player);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_members_last_updated() {
+        let database = Database::open_temp().await;
+        assert_eq!(database.members_last_updated().await.unwrap(), None);
+
+        let newest = chrono::Utc::now();
+        let members: Vec<DbMember> = (0..3)
+            .map(|i| DbMember {
+                updated_at: newest - chrono::Duration::hours(i),
+                ..test_member(&format!("Player{}", i), "Tarren Mill", None, 2000.0)
+            })
+            .collect();
+        database.clear_temp_members().await.unwrap();
+        database.insert_temp_members_batch(&members).await.unwrap();
+        database.swap_members_tables().await.unwrap();
+
+        assert_eq!(database.members_last_updated().await.unwrap(), Some(newest));
+    }
+
+    #[tokio::test]
+    async fn test_find_members_by_name_across_realms() {
+        let database = Database::open_temp().await;
+        database.clear_temp_members().await.unwrap();
+        database.insert_temp_members_batch(&[
+            test_member("Frostbolt", "Tarren Mill", Some("Thorned Horde"), 2847.5),
+            test_member("Frostbolt", "Silvermoon", None, 2100.0),
+            test_member("Fireball", "Tarren Mill", None, 2500.0),
+        ]).await.unwrap();
+        database.swap_members_tables().await.unwrap();
+
+        let members = database.find_members_by_name("FROSTBOLT").await.unwrap();
+        let realms: Vec<&str> = members.iter().map(|m| m.realm.as_str()).collect();
+        assert_eq!(realms, vec!["tarren-mill", "silvermoon"]);
+
+        assert!(database.find_members_by_name("Pyroblast").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_get_member_by_name_and_realm() {
+        let database = Database::open_temp().await;
+        database.clear_temp_members().await.unwrap();
+        database.insert_temp_members_batch(&[test_member("Frostbolt", "Tarren Mill", Some("Thorned Horde"), 2847.5)]).await.unwrap();
+        database.swap_members_tables().await.unwrap();
+
+        let member = database.get_member("frostbolt", "tarren mill").await.unwrap().unwrap();
+        assert_eq!(member.name, "Frostbolt");
+        assert_eq!(member.realm, "tarren-mill");
+        assert_eq!(member.guild_name.as_deref(), Some("Thorned Horde"));
+        assert_eq!(member.rio_all, 2847.5);
+
+        assert!(database.get_member("Frostbolt", "Silvermoon").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_spec_names_round_trip() {
+        let database = Database::open_temp().await;
+        database.clear_temp_members().await.unwrap();
+        database.insert_temp_members_batch(&[test_member("Frostbolt", "Tarren Mill", None, 2847.5)]).await.unwrap();
+        database.swap_members_tables().await.unwrap();
+
+        let member = database.get_member("Frostbolt", "Tarren Mill").await.unwrap().unwrap();
+        assert_eq!(member.spec_0_name.as_deref(), Some("Arcane"));
+        assert_eq!(member.spec_1_name.as_deref(), Some("Fire"));
+        assert_eq!(member.spec_2_name.as_deref(), Some("Frost"));
+        assert_eq!(member.spec_3_name, None);
+
+        // Spec names survive the next swap's freshly created tmp table as well
+        database.insert_temp_members_batch(&[test_member("Frostbolt", "Tarren Mill", None, 2900.0)]).await.unwrap();
+        database.swap_members_tables().await.unwrap();
+        let member = database.get_member("Frostbolt", "Tarren Mill").await.unwrap().unwrap();
+        assert_eq!(member.spec_2_name.as_deref(), Some("Frost"));
+    }
+
+    #[tokio::test]
+    async fn test_distinct_realms_are_normalized() {
+        let database = Database::open_temp().await;
+        database.insert_guild(&GuildUrl::new("tarren-mill", "Another Guild")).await.unwrap();
+
+        let realms = database.distinct_realms().await.unwrap();
+        let tarren_mill = realms.iter().filter(|r| r.as_str() == "tarren-mill").count();
+        assert_eq!(tarren_mill, 1);
+        assert!(realms.contains(&RealmName::from("Silvermoon")));
+    }
+
+    #[tokio::test]
+    async fn test_upsert_member_keeps_other_guilds() {
+        let database = Database::open_temp().await;
+        database.clear_temp_members().await.unwrap();
+        database.insert_temp_members_batch(&[
+            test_member("Frostbolt", "Tarren Mill", Some("Thorned Horde"), 2500.0),
+            test_member("Fireball", "Silvermoon", Some("Other Guild"), 2100.0),
+        ]).await.unwrap();
+        database.swap_members_tables().await.unwrap();
+
+        database.upsert_member(&test_member("Frostbolt", "Tarren Mill", Some("Thorned Horde"), 2900.0)).await.unwrap();
+        database.upsert_member(&test_member("Arcane", "Tarren Mill", Some("Thorned Horde"), 2300.0)).await.unwrap();
+
+        let members = database.get_all_members().await.unwrap();
+        assert_eq!(members.len(), 3);
+
+        let updated = database.get_member("Frostbolt", "Tarren Mill").await.unwrap().unwrap();
+        assert_eq!(updated.rio_all, 2900.0);
+        let untouched = database.get_member("Fireball", "Silvermoon").await.unwrap().unwrap();
+        assert_eq!(untouched.guild_name.as_deref(), Some("Other Guild"));
+        assert_eq!(untouched.rio_all, 2100.0);
+    }
+
+    #[tokio::test]
+    async fn test_guild_region_round_trip() {
+        let database = Database::open_temp().await;
+        database.clear_guilds().await;
+
+        let us_guild = GuildUrl { region: Some(Region::Us), ..GuildUrl::new("Stormrage", "Liquid") };
+        database.insert_guild(&us_guild).await.unwrap();
+        database.insert_guild(&GuildUrl::new("Tarren Mill", "Thorned Horde")).await.unwrap();
+
+        let guilds = database.get_all_guilds().await.unwrap();
+        let region_of = |name: &str| guilds.iter().find(|g| g.name.as_str() == name).unwrap().region;
+        assert_eq!(region_of("Liquid"), Some(Region::Us));
+        assert_eq!(region_of("Thorned Horde"), Some(Region::Eu));
+    }
+
+    #[tokio::test]
+    async fn test_import_guilds_parses_region() {
+        let database = Database::open_temp().await;
+        database.clear_guilds().await;
+
+        let blob = "region=us&realm=Illidan&name=Liquid\n\
+                    realm=Tarren Mill&name=Thorned Horde\n\
+                    region=mars&realm=Silvermoon&name=Nexus\n";
+        let summary = database.import_guilds_from_text(blob).await;
+
+        assert_eq!((summary.imported, summary.errors), (2, 1));
+        assert_eq!(
+            summary.rejected,
+            vec!["line 3: unknown region 'mars' (expected us, eu, kr, tw or cn)".to_string()]
+        );
+
+        let guilds = database.get_all_guilds().await.unwrap();
+        let region_of = |name: &str| guilds.iter().find(|g| g.name.as_str() == name).map(|g| g.region);
+        assert_eq!(region_of("Liquid"), Some(Some(Region::Us)));
+        // No region given: stored as EU
+        assert_eq!(region_of("Thorned Horde"), Some(Some(Region::Eu)));
+        assert_eq!(region_of("Nexus"), None);
+    }
+
+    #[tokio::test]
+    async fn test_import_guilds_from_text_counts() {
+        let database = Database::open_temp().await;
+        database.clear_guilds().await;
+
+        let blob = "realm=Tarren Mill&name=Thorned Horde\n\
+                    # onboarding batch\n\
+                    Silvermoon/Nexus\n\
+                    not a guild line\n\
+                    \n\
+                    realm=Tarren Mill&name=Thorned Horde\n";
+        let summary = database.import_guilds_from_text(blob).await;
+
+        assert_eq!((summary.imported, summary.skipped, summary.errors), (2, 1, 1));
+        assert_eq!(summary.rejected, vec!["line 4: expected realm=...&name=... or Realm/Guild".to_string()]);
+        let names: Vec<String> = database.get_all_guilds().await.unwrap().iter().map(|g| g.name.to_string()).collect();
+        assert_eq!(names, vec!["Nexus".to_string(), "Thorned Horde".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_status_serializes_to_json() {
+        let database = Database::open_temp().await;
+        let status = database.status().await.unwrap();
+
+        let json: serde_json::Value = serde_json::to_value(&status).unwrap();
+        assert_eq!(json["guild_count"], status.guild_count);
+        assert_eq!(json["member_count"], 0);
+        assert!(json["last_updated"].is_null());
+        let migrations = json["migrations"].as_array().unwrap();
+        assert_eq!(migrations.len(), status.migrations.len());
+        assert_eq!(migrations[0]["name"], "001_create_guilds_table");
+        assert!(migrations[0]["executed_at"].is_string());
+        assert_eq!(json["pending_migrations"], serde_json::json!([]));
+    }
+
+    #[tokio::test]
+    async fn test_unrun_migration_shows_as_pending() {
+        let database = Database::open_temp().await;
+        let status = database.migration_status().await.unwrap();
+        assert_eq!(status.len(), MIGRATIONS.len());
+        assert!(status.iter().all(|(_, applied)| *applied));
+        assert!(database.status().await.unwrap().pending_migrations.is_empty());
+
+        sqlx::query("DELETE FROM _migrations WHERE name = '006_add_region_to_guilds'")
+            .execute(&database.pool)
+            .await
+            .unwrap();
+
+        let status = database.migration_status().await.unwrap();
+        assert_eq!(status.last(), Some(&("006_add_region_to_guilds".to_string(), false)));
+        assert_eq!(database.status().await.unwrap().pending_migrations, vec!["006_add_region_to_guilds"]);
+    }
+
+    #[tokio::test]
+    async fn test_prune_guild_members_only_touches_departed_members() {
+        let database = Database::open_temp().await;
+        let now = chrono::Utc::now();
+        let aged = |name: &str, guild: &str, days: i64| DbMember {
+            updated_at: now - chrono::Duration::days(days),
+            ..test_member(name, "Tarren Mill", Some(guild), 2500.0)
+        };
+        for member in [
+            aged("Fresh", "Thorned Horde", 0),
+            aged("Departed", "Thorned Horde", 40),
+            aged("Failedfetch", "Thorned Horde", 40),
+            aged("Otherguild", "Nexus", 400),
+        ] {
+            database.upsert_member(&member).await.unwrap();
+        }
+
+        // Failedfetch is still on the roster, its request just didn't succeed
+        let roster: HashSet<(String, String)> = ["Fresh", "Failedfetch"]
+            .iter()
+            .map(|name| ("tarren-mill".to_string(), name.to_string()))
+            .collect();
+        let pruned = database
+            .prune_guild_members("Thorned Horde", "tarren-mill", &roster, now - chrono::Duration::days(30))
+            .await
+            .unwrap();
+        assert_eq!(pruned, 1);
+
+        let mut names: Vec<String> = database.get_all_members().await.unwrap().into_iter().map(|m| m.name).collect();
+        names.sort();
+        assert_eq!(names, vec!["Failedfetch".to_string(), "Fresh".to_string(), "Otherguild".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_get_guild_members_ignores_case() {
+        let database = Database::open_temp().await;
+        database.clear_temp_members().await.unwrap();
+        database.insert_temp_members_batch(&[
+            test_member("Ярослав", "Tarren Mill", Some("Нехай Щастить"), 2600.0),
+            test_member("Ярема", "Silvermoon", Some("Thorned Horde"), 2900.0),
+            test_member("Frostbolt", "Tarren Mill", None, 2500.0),
+        ]).await.unwrap();
+        database.swap_members_tables().await.unwrap();
+
+        let members = database.get_guild_members("нехай щастить").await.unwrap();
+        assert_eq!(members.iter().map(|m| m.name.as_str()).collect::<Vec<_>>(), vec!["Ярослав"]);
+        assert!(database.get_guild_members("Nomads").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_search_members_folds_cyrillic_case() {
+        let database = Database::open_temp().await;
+        database.clear_temp_members().await.unwrap();
+        database.insert_temp_members_batch(&[
+            test_member("Ярослав", "Tarren Mill", Some("Нехай Щастить"), 2600.0),
+            test_member("Ярема", "Silvermoon", None, 2900.0),
+            test_member("Frostbolt", "Tarren Mill", None, 2500.0),
+        ]).await.unwrap();
+        database.swap_members_tables().await.unwrap();
+
+        let names = |members: Vec<DbMember>| members.into_iter().map(|m| m.name).collect::<Vec<_>>();
+        assert_eq!(names(database.search_members("РОСЛ", 10).await.unwrap()), vec!["Ярослав"]);
+        assert_eq!(names(database.search_members("яр", 10).await.unwrap()), vec!["Ярема", "Ярослав"]);
+        assert_eq!(names(database.search_members("яр", 1).await.unwrap()), vec!["Ярема"]);
+        assert_eq!(names(database.search_members("BOLT", 10).await.unwrap()), vec!["Frostbolt"]);
+        assert!(database.search_members("  ", 10).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_top_player_per_class() {
+        let database = Database::open_temp().await;
+        let with_class = |name: &str, class: Option<&str>, rio: f64| DbMember {
+            class: class.map(str::to_string),
+            ..test_member(name, "Tarren Mill", None, rio)
+        };
+        database.clear_temp_members().await.unwrap();
+        database.insert_temp_members_batch(&[
+            with_class("Frostbolt", Some("Mage"), 2800.0),
+            with_class("Blink", Some("Mage"), 3100.0),
+            with_class("Holyfist", Some("Paladin"), 2500.0),
+            with_class("Bubble", Some("Paladin"), 2400.0),
+            with_class("Arrow", Some("Hunter"), 1900.0),
+            with_class("Nobody", None, 3400.0),
+        ]).await.unwrap();
+        database.swap_members_tables().await.unwrap();
+
+        let top: Vec<(String, String)> = database
+            .top_player_per_class()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|(class, member)| (class, member.name))
+            .collect();
+        // One row per class that has members; members without a class are skipped
+        assert_eq!(top, vec![
+            ("Hunter".to_string(), "Arrow".to_string()),
+            ("Mage".to_string(), "Blink".to_string()),
+            ("Paladin".to_string(), "Holyfist".to_string()),
+        ]);
+    }
+
+    #[tokio::test]
+    async fn test_swap_rejects_empty_temp_table() {
+        let database = Database::open_temp().await;
+        database.clear_temp_members().await.unwrap();
+        let members: Vec<DbMember> = (0..10)
+            .map(|i| test_member(&format!("Player{}", i), "Tarren Mill", None, 2000.0))
+            .collect();
+        database.insert_temp_members_batch(&members).await.unwrap();
+        database.swap_members_tables().await.unwrap();
+
+        // The swap leaves a fresh, empty members_tmp behind
+        assert_eq!(database.temp_member_count().await.unwrap(), 0);
+        assert!(database.swap_members_tables().await.is_err());
+        assert_eq!(database.member_count().await.unwrap(), 10);
+
+        // A nearly complete run is still promoted
+        database.insert_temp_members_batch(&members[..8]).await.unwrap();
+        database.swap_members_tables().await.unwrap();
+        assert_eq!(database.member_count().await.unwrap(), 8);
+    }
+
+    #[test]
+    fn test_swap_allowed_ratio() {
+        assert!(swap_allowed(0, 0, 0.5));
+        assert!(swap_allowed(50, 100, 0.5));
+        assert!(!swap_allowed(49, 100, 0.5));
+        assert!(swap_allowed(0, 100, 0.0));
+    }
+}
@@ -1,13 +1,18 @@
 /// Guild data management and fetching operations
+use std::fmt;
 use std::fs;
 use std::path::Path;
+use std::str::FromStr;
 use crate::config::AppConfig;
 use crate::database::Database;
 use crate::error::Result;
-use crate::raider_io::{RaiderIOClient, GuildData};
-use crate::types::{GuildUrl, GuildName, PlayerName, RaidTier, RealmName};
-use futures::stream::{self, StreamExt};
+use crate::raider_io::{RaiderIOClient, GuildData, GuildFetchResult};
+use crate::table::{Column, TableLayout};
+use crate::responder::DISCORD_MESSAGE_LIMIT;
+use crate::types::{GuildUrl, PlayerName, RaidDifficulty, RealmDisplayNames, RaidTier, RealmName};
+use futures::stream::{self, Stream, StreamExt};
 use std::sync::Arc;
+use std::time::Duration;
 use tracing::{debug, error, info, warn};
 
 /// Read guild URLs from configuration file
@@ -26,11 +31,9 @@ pub fn read_guild_data(file_path: &str) -> Result<Vec<GuildUrl>> {
             continue;
         }
         
-        // Parse guild URL format: "realm=name&guild=guildname" or similar
-        if let Some(guild_url) = parse_guild_url(trimmed) {
-            guild_urls.push(guild_url);
-        } else {
-            warn!("Failed to parse guild URL: {}", trimmed);
+        match GuildUrl::parse(trimmed) {
+            Ok(guild_url) => guild_urls.push(guild_url),
+            Err(reason) => warn!("Failed to parse guild URL: {} ({})", trimmed, reason),
         }
     }
     
@@ -38,29 +41,6 @@ pub fn read_guild_data(file_path: &str) -> Result<Vec<GuildUrl>> {
     Ok(guild_urls)
 }
 
-/// Parse a guild URL string into a GuildUrl struct
-fn parse_guild_url(url_str: &str) -> Option<GuildUrl> {
-    // Handle different formats - this is a simplified parser
-    // Example: "realm=tarren-mill&name=guild-name"
-    let mut realm = None;
-    let mut guild = None;
-    
-    for part in url_str.split('&') {
-        if let Some((key, value)) = part.split_once('=') {
-            match key {
-                "realm" => realm = Some(RealmName::from(value)),
-                "name" => guild = Some(GuildName::from(value)),
-                _ => {}
-            }
-        }
-    }
-    
-    match (realm, guild) {
-        (Some(realm), Some(guild)) => Some(GuildUrl::new(realm, guild)),
-        _ => None,
-    }
-}
-
 /// Read additional characters from file
 pub fn read_additional_characters(file_path: &str) -> Result<Vec<(PlayerName, RealmName)>> {
     if !Path::new(file_path).exists() {
@@ -86,17 +66,25 @@ pub fn read_additional_characters(file_path: &str) -> Result<Vec<(PlayerName, Re
     Ok(characters)
 }
 
-/// Fetch all guild data for a given raid tier (using database)
-pub async fn fetch_all_guild_data(tier: RaidTier, config: &AppConfig) -> Result<Vec<GuildData>> {
-    let client = RaiderIOClient::from_config(config)?;
-    
-    // Initialize database and get guild URLs from it
-    let database = Database::new(&config.database.url).await?;
-    let guild_urls = database.get_all_guilds().await?;
+/// Guilds fetched by [`fetch_all_guild_data`]
+#[derive(Debug, Default)]
+pub struct GuildFetch {
+    pub guilds: Vec<GuildData>,
+    /// The deadline passed before every guild was fetched; `guilds` is partial
+    pub timed_out: bool,
+}
+
+/// Fetch every tracked guild's progress for `tier`. When `data.guilds_command_timeout_secs`
+/// is set, fetching stops at that deadline and the guilds finished so far are returned.
+/// `client` is long-lived, so a circuit breaker opened by one command holds for the next.
+pub async fn fetch_all_guild_data(tier: RaidTier, config: &AppConfig, database: &Database, client: &RaiderIOClient) -> Result<GuildFetch> {
+
+    let mut guild_urls = database.get_all_guilds().await?;
+    config.data.retain_allowed_realms(&mut guild_urls);
     
     if guild_urls.is_empty() {
         warn!("No guild URLs found");
-        return Ok(Vec::new());
+        return Ok(GuildFetch::default());
     }
     
     let total_guilds = guild_urls.len();
@@ -108,13 +96,12 @@ pub async fn fetch_all_guild_data(tier: RaidTier, config: &AppConfig) -> Result<
     
     // Concurrent guild data fetching (like Python bot - no artificial delays)
     let results = stream::iter(guild_urls.into_iter().map(|url| {
-        let client = &client;
         let progress_counter = Arc::clone(&progress_counter);
         async move {
             debug!("Fetching guild data for: {}", url);
             
             let result = match client.fetch_guild_data(&url, tier).await {
-                Ok(Some(guild)) => {
+                Ok(GuildFetchResult::Found(guild)) => {
                     let current = progress_counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1;
                     info!(
                         guild = %guild.name,
@@ -128,13 +115,13 @@ pub async fn fetch_all_guild_data(tier: RaidTier, config: &AppConfig) -> Result<
                     }
                     Some(guild)
                 }
-                Ok(None) => {
+                Ok(GuildFetchResult::NotFound) => {
                     let current = progress_counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1;
-                    debug!(
+                    warn!(
                         guild_url = %url,
                         progress = current,
                         total = total_guilds,
-                        "No data found for guild"
+                        "Guild not found on raider.io"
                     );
                     None
                 }
@@ -154,15 +141,26 @@ pub async fn fetch_all_guild_data(tier: RaidTier, config: &AppConfig) -> Result<
             result
         }
     }))
-    .buffer_unordered(config.rate_limiting.concurrent_requests)
-    .collect::<Vec<_>>()
-    .await;
-    
-    let guilds: Vec<GuildData> = results.into_iter().flatten().collect();
+    .buffer_unordered(config.rate_limiting.concurrent_requests);
+
+    let deadline = match config.data.guilds_command_timeout_secs {
+        0 => None,
+        secs => Some(Duration::from_secs(secs)),
+    };
+    let (guilds, timed_out) = collect_within(results, deadline).await;
     let successful_count = guilds.len();
     let failed_count = total_guilds - successful_count;
-    
-    crate::log_data_processing!("guild data fetch complete", total_guilds, total_guilds);
+
+    if timed_out {
+        warn!(
+            successful = successful_count,
+            total = total_guilds,
+            timeout_secs = config.data.guilds_command_timeout_secs,
+            "Guild data fetch timed out, returning partial results"
+        );
+    } else {
+        crate::log_data_processing!("guild data fetch complete", total_guilds, total_guilds);
+    }
     info!(
         successful = successful_count,
         failed = failed_count,
@@ -171,12 +169,62 @@ pub async fn fetch_all_guild_data(tier: RaidTier, config: &AppConfig) -> Result<
     );
     info!("Successfully fetched data for {} guilds", guilds.len());
 
-    Ok(guilds)
+    Ok(GuildFetch { guilds, timed_out })
+}
+
+/// Drain a stream of optional results until it ends or `deadline` passes, keeping the
+/// `Some` items received so far. Returns the items and whether the deadline was hit.
+async fn collect_within<S, T>(results: S, deadline: Option<Duration>) -> (Vec<T>, bool)
+where
+    S: Stream<Item = Option<T>>,
+{
+    let mut collected = Vec::new();
+    let drain = async {
+        let mut results = std::pin::pin!(results);
+        while let Some(item) = results.next().await {
+            collected.extend(item);
+        }
+    };
+
+    let timed_out = match deadline {
+        Some(deadline) => tokio::time::timeout(deadline, drain).await.is_err(),
+        None => {
+            drain.await;
+            false
+        }
+    };
+
+    (collected, timed_out)
+}
+
+/// Estimate `(best_percent, pull_count)` from a progress summary when boss-kill
+/// details are unavailable, so guilds with progression don't show as zero:
+/// - a full clear of any raid size ("8/8", "9/9") is 100%
+/// - mythic progress scales with kills ("6/9 M" -> 66.7%), or 75% if the count is unreadable
+/// - heroic progress is 25%
+/// - other non-empty progress is 10%; "0/..." and "No progress" are 0%
+///
+/// The pull count is never known from the summary alone.
+pub fn estimate_best_percent(progress: &str) -> (f64, Option<u32>) {
+    let percent = match progress.parse::<Progression>() {
+        Ok(progression) if progression.is_full_clear() => 100.0,
+        Ok(progression) => match progression.difficulty {
+            Difficulty::Mythic => (progression.bosses as f64 / progression.total as f64) * 100.0,
+            Difficulty::Heroic => 25.0,
+            _ if progression.bosses == 0 => 0.0,
+            _ => 10.0,
+        },
+        Err(_) if progress.contains('M') => 75.0,
+        Err(_) if progress.contains('H') => 25.0,
+        Err(_) if progress.starts_with("0/") || progress == "No progress" => 0.0,
+        Err(_) => 10.0,
+    };
+    (percent, None)
 }
 
 /// Difficulty levels in order of importance (higher = better)
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
-enum Difficulty {
+pub enum Difficulty {
     Lfr = 1,
     Normal = 2,
     Heroic = 3,
@@ -184,102 +232,194 @@ enum Difficulty {
 }
 
 impl Difficulty {
-    fn from_progress(progress: &str) -> Self {
-        let difficulty_char = progress.chars().last().unwrap_or('N');
-        match difficulty_char {
-            'M' => Difficulty::Mythic,
-            'H' => Difficulty::Heroic,
-            'N' => Difficulty::Normal,
-            _ => {
-                // Check for LFR
-                if progress.contains("LFR") {
-                    Difficulty::Lfr
-                } else {
-                    Difficulty::Normal
-                }
-            }
+    fn from_suffix(suffix: &str) -> Option<Self> {
+        match suffix {
+            "LFR" => Some(Difficulty::Lfr),
+            "N" => Some(Difficulty::Normal),
+            "H" => Some(Difficulty::Heroic),
+            "M" => Some(Difficulty::Mythic),
+            _ => None,
+        }
+    }
+
+    /// Difficulty of a progress summary such as "3/8 M"; `None` for "No progress"
+    pub fn from_progress(progress: &str) -> Option<Self> {
+        progress.parse::<Progression>().ok().map(|progression| progression.difficulty)
+    }
+
+    /// Whether this is `difficulty`; LFR has no `RaidDifficulty` and never matches
+    fn is(self, difficulty: RaidDifficulty) -> bool {
+        matches!(
+            (self, difficulty),
+            (Difficulty::Normal, RaidDifficulty::Normal) | (Difficulty::Heroic, RaidDifficulty::Heroic) | (Difficulty::Mythic, RaidDifficulty::Mythic)
+        )
+    }
+
+    fn suffix(self) -> &'static str {
+        match self {
+            Difficulty::Lfr => "LFR",
+            Difficulty::Normal => "N",
+            Difficulty::Heroic => "H",
+            Difficulty::Mythic => "M",
+        }
+    }
+
+    /// Difficulty name used by raider.io's boss-kill API, which has no LFR rankings
+    pub fn api_name(self) -> &'static str {
+        match self {
+            Difficulty::Mythic => "mythic",
+            Difficulty::Heroic => "heroic",
+            Difficulty::Normal | Difficulty::Lfr => "normal",
         }
     }
 }
 
-/// Parse progression string to extract boss count and difficulty
-fn parse_progression(progress: &str) -> (u8, Difficulty) {
-    // Parse "X/8 M" format
-    let boss_count = progress.split('/')
-        .next()
-        .and_then(|s| s.trim().parse::<u8>().ok())
-        .unwrap_or(0);
-    
-    let difficulty = Difficulty::from_progress(progress);
-    (boss_count, difficulty)
+/// A raid progress summary such as "3/8 M". Fields are declared in comparison order,
+/// so the derived ordering ranks difficulty first, then bosses killed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Progression {
+    pub difficulty: Difficulty,
+    pub bosses: u8,
+    pub total: u8,
 }
 
-/// Compare two progressions considering difficulty hierarchy
-fn compare_progression(progress_a: &str, progress_b: &str) -> std::cmp::Ordering {
-    let (bosses_a, diff_a) = parse_progression(progress_a);
-    let (bosses_b, diff_b) = parse_progression(progress_b);
-    
-    // First compare difficulty (Mythic > Heroic > Normal > LFR)
-    match diff_a.cmp(&diff_b) {
-        std::cmp::Ordering::Equal => {
-            // Same difficulty, compare boss count
-            bosses_a.cmp(&bosses_b)
+impl Progression {
+    pub fn is_full_clear(&self) -> bool {
+        self.total > 0 && self.bosses >= self.total
+    }
+}
+
+impl FromStr for Progression {
+    type Err = &'static str;
+
+    /// Parse "bosses/total difficulty"; anything else, including raider.io's
+    /// "No progress", is an error
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let (counts, suffix) = s.trim().split_once(' ').ok_or("Missing difficulty")?;
+        let (bosses, total) = counts.split_once('/').ok_or("Missing boss count")?;
+        let bosses = bosses.parse().map_err(|_| "Invalid boss count")?;
+        let total = total.parse().map_err(|_| "Invalid boss total")?;
+        let difficulty = Difficulty::from_suffix(suffix.trim()).ok_or("Unknown difficulty")?;
+        if bosses > total {
+            return Err("More bosses killed than the raid has");
         }
-        other => other
+        Ok(Self { difficulty, bosses, total })
     }
 }
 
-/// Sort guilds by progression and rank
+impl fmt::Display for Progression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{} {}", self.bosses, self.total, self.difficulty.suffix())
+    }
+}
+
+/// Difficulty and bosses killed for sorting; missing or unreadable progress sorts below everything
+fn standing_key(progress: Option<&str>) -> (Option<Difficulty>, u8) {
+    match progress.unwrap_or_default().parse::<Progression>() {
+        Ok(progression) => (Some(progression.difficulty), progression.bosses),
+        Err(_) => (None, 0),
+    }
+}
+
+/// Sort guilds by progression and rank, falling back to guild name then realm so
+/// fully tied guilds keep a stable order between calls
 pub fn sort_guilds(mut guilds: Vec<GuildData>) -> Vec<GuildData> {
     guilds.sort_by(|a, b| {
-        // Parse progression to get difficulty for both guilds
-        let (bosses_a, diff_a) = parse_progression(&a.progress);
-        let (bosses_b, diff_b) = parse_progression(&b.progress);
-        
-        // STEP 1: Compare by difficulty first (Mythic > Heroic > Normal > LFR)
-        // Higher difficulty should rank higher
-        if diff_a != diff_b {
-            // Different difficulties - higher difficulty wins
-            return diff_b.cmp(&diff_a);
-        }
-        
-        // STEP 2: Same difficulty - compare within difficulty
-        
-        // Compare by boss count first
-        match bosses_b.cmp(&bosses_a) {
-            std::cmp::Ordering::Equal => {
-                // Same boss count - now check difficulty for ranking logic
-                if diff_a == Difficulty::Mythic {
-                    // Mythic difficulty: same boss count -> sort by world rank first
-                    let rank_a = a.rank.as_ref().filter(|r| r.value() > 0);
-                    let rank_b = b.rank.as_ref().filter(|r| r.value() > 0);
-                    
-                    match (rank_a, rank_b) {
-                        (Some(rank_a), Some(rank_b)) => rank_a.value().cmp(&rank_b.value()),
-                        (Some(_), None) => std::cmp::Ordering::Less,  // Ranked comes first
-                        (None, Some(_)) => std::cmp::Ordering::Greater, // Unranked comes last
-                        (None, None) => {
-                            // Both unranked - sort by best percent (lower is better, closer to kill)
-                            a.best_percent.partial_cmp(&b.best_percent).unwrap_or(std::cmp::Ordering::Equal)
-                        }
-                    }
-                } else {
-                    // Non-Mythic difficulty: same boss count -> sort by percent only (ignore world rank)
-                    a.best_percent.partial_cmp(&b.best_percent).unwrap_or(std::cmp::Ordering::Equal)
-                }
-            }
-            other => other // Different boss counts - higher boss count wins
-        }
+        compare_guild_standing(a, b)
+            .then_with(|| a.name.as_str().cmp(b.name.as_str()))
+            .then_with(|| a.realm.as_str().cmp(b.realm.as_str()))
     });
     
     debug!("Sorted {} guilds by progression", guilds.len());
     guilds
 }
 
-/// Format guild list for display
-pub fn format_guild_list(guilds: &[GuildData], limit: Option<usize>, show_all: bool) -> String {
+/// Progression order used by [`sort_guilds`]; `Equal` for guilds that are fully tied
+fn compare_guild_standing(a: &GuildData, b: &GuildData) -> std::cmp::Ordering {
+    // Parse progression to get difficulty for both guilds
+    let (diff_a, bosses_a) = standing_key(a.progress.as_deref());
+    let (diff_b, bosses_b) = standing_key(b.progress.as_deref());
+    
+    // STEP 1: Compare by difficulty first (Mythic > Heroic > Normal > LFR)
+    // Higher difficulty should rank higher
+    if diff_a != diff_b {
+        // Different difficulties - higher difficulty wins
+        return diff_b.cmp(&diff_a);
+    }
+    
+    // STEP 2: Same difficulty - compare within difficulty
+    
+    // Compare by boss count first
+    match bosses_b.cmp(&bosses_a) {
+        std::cmp::Ordering::Equal => {
+            // Same boss count - now check difficulty for ranking logic
+            if diff_a == Some(Difficulty::Mythic) {
+                // Mythic difficulty: same boss count -> sort by world rank first
+                match (&a.rank, &b.rank) {
+                    (Some(rank_a), Some(rank_b)) => rank_a.value().cmp(&rank_b.value()),
+                    (Some(_), None) => std::cmp::Ordering::Less,  // Ranked comes first
+                    (None, Some(_)) => std::cmp::Ordering::Greater, // Unranked comes last
+                    // Both unranked - sort by how close they are to the next kill
+                    (None, None) => compare_best_percent(a, b),
+                }
+            } else {
+                // Non-Mythic difficulty: same boss count -> sort by percent only (ignore world rank)
+                compare_best_percent(a, b)
+            }
+        }
+        other => other // Different boss counts - higher boss count wins
+    }
+}
+
+/// Same-boss-count tiebreak. `best_percent` is the boss health left on the best pull, so
+/// lower is better, but 100.0 without pulls means "complete" (see [`is_complete`]);
+/// complete guilds therefore come first, then the lowest percent.
+fn compare_best_percent(a: &GuildData, b: &GuildData) -> std::cmp::Ordering {
+    is_complete(b)
+        .cmp(&is_complete(a))
+        .then_with(|| a.best_percent.partial_cmp(&b.best_percent).unwrap_or(std::cmp::Ordering::Equal))
+}
+
+/// Whether a guild is shown as "Complete": a Mythic full clear, or the 100.0-without-pulls
+/// marker that boss-kill lookups return when the current boss is down
+fn is_complete(guild: &GuildData) -> bool {
+    let Some(progress) = &guild.progress else {
+        return false;
+    };
+    let full_clear = progress
+        .parse::<Progression>()
+        .is_ok_and(|progression| progression.difficulty == Difficulty::Mythic && progression.is_full_clear());
+    full_clear || (guild.best_percent == 100.0 && guild.pull_count.is_none())
+}
+
+/// Keep only guilds whose current progress is on `difficulty`, in their existing order
+pub fn filter_by_difficulty(guilds: Vec<GuildData>, difficulty: RaidDifficulty) -> Vec<GuildData> {
+    guilds
+        .into_iter()
+        .filter(|guild| {
+            guild
+                .progress
+                .as_deref()
+                .and_then(Difficulty::from_progress)
+                .is_some_and(|current| current.is(difficulty))
+        })
+        .collect()
+}
+
+/// /guilds table columns and the widest each may grow
+const GUILD_TABLE: &[Column] = &[
+    Column::new("Rank", 4),
+    Column::new("Guild Name", 40),
+    Column::new("Server", 20),
+    Column::new("Progress", 11),
+    Column::new("World Rank", 11),
+    Column::new("Best", 20),
+];
+
+/// Format guild list for display as one or more Discord messages
+pub fn format_guild_list(guilds: &[GuildData], limit: Option<usize>, show_all: bool) -> Vec<String> {
     if guilds.is_empty() {
-        return "No guild data available.".to_string();
+        return vec!["No guild data available.".to_string()];
     }
     
     let display_count = if show_all {
@@ -288,101 +428,212 @@ pub fn format_guild_list(guilds: &[GuildData], limit: Option<usize>, show_all: b
         limit.unwrap_or(10).min(guilds.len())
     };
     
-    let mut result = String::new();
-    result.push_str(&format!("**Guild Rankings (Showing {} of {}):**\n", display_count, guilds.len()));
-    
-    // Use code block for monospace alignment
-    result.push_str("```");
-    result.push_str("Rank Guild Name                              Server               Progress  World Rank  Best\n");
-    result.push_str("──── ──────────────────────────────────── ──────────────────── ───────── ─────────── ────────────\n");
+    let header = format!("**Guild Rankings (Showing {} of {}):**\n", display_count, guilds.len());
     
-    for (i, guild) in guilds.iter().take(display_count).enumerate() {
-        let rank_num = format!("#{}", i + 1);
-        let guild_name = truncate_and_pad(&guild.name, 40);
-        let server = truncate_and_pad(&guild.realm.display_name(), 20);
-        let progress = truncate_and_pad(&guild.progress, 9);
-        
+    let mut realm_names = RealmDisplayNames::default();
+    let cells: Vec<Vec<String>> = guilds.iter().take(display_count).enumerate().map(|(i, guild)| {
         let world_rank = match &guild.rank {
             Some(rank) => format!("#{}", rank.value()),
             None => "Unranked".to_string(),
         };
-        let world_rank_str = truncate_and_pad(&world_rank, 11);
-        
-        // Check if progress shows completion or no progress data
-        let is_completed = guild.progress.contains("/8 M") && guild.progress.starts_with("8/");
-        let has_no_progress = guild.best_percent == 100.0 && guild.pull_count.is_none();
-        
-        let best_progress = if is_completed || has_no_progress {
-            "Complete".to_string()
+        vec![
+            format!("#{}", i + 1),
+            guild.name.to_string(),
+            realm_names.get(&guild.realm).to_string(),
+            guild.progress_label().to_string(),
+            world_rank,
+            format_best_progress(guild),
+        ]
+    }).collect();
+
+    // Use code block for monospace alignment
+    let layout = TableLayout::fit(GUILD_TABLE, &cells);
+    let table_header = format!("```{}", layout.header());
+    let table_footer = "```";
+    let rows: Vec<String> = cells.iter().map(|row| layout.row(row)).collect();
+    
+    // Split rows across messages to stay under Discord's 2000 character limit,
+    // sized by the widest row; the margin leaves room for the continuation header
+    // and any note appended to the last message
+    let discord_limit = DISCORD_MESSAGE_LIMIT;
+    let widest_row = rows.iter().map(|row| row.chars().count()).max().unwrap_or(1);
+    let base_message_size = header.chars().count() + table_header.chars().count() + table_footer.len() + 200;
+    let max_rows_per_message = (discord_limit.saturating_sub(base_message_size) / widest_row).max(1);
+    
+    let mut messages = Vec::new();
+    for (chunk_index, chunk) in rows.chunks(max_rows_per_message).enumerate() {
+        let chunk_start = chunk_index * max_rows_per_message;
+        let mut message = if chunk_start == 0 {
+            header.clone()
         } else {
-            match guild.pull_count {
-                Some(pulls) => format!("{:.1}%({} pulls)", guild.best_percent, pulls),
-                None => format!("{:.1}%", guild.best_percent),
-            }
+            format!("**Guild Rankings (continued - {} to {}):**\n", chunk_start + 1, chunk_start + chunk.len())
         };
         
-        result.push_str(&format!(
-            "{:<4} {:<40} {:<20} {:<9} {:<11} {}\n",
-            rank_num,
-            guild_name,
-            server,
-            progress,
-            world_rank_str,
-            best_progress
-        ));
+        message.push_str(&table_header);
+        for row in chunk {
+            message.push_str(row);
+        }
+        message.push_str(table_footer);
+        messages.push(message);
     }
     
-    result.push_str("```");
-    result
+    messages
 }
 
-/// Helper function to truncate and pad strings to consistent length for monospace alignment
-fn truncate_and_pad(s: &str, target_len: usize) -> String {
-    if s.len() >= target_len {
-        format!("{}...", &s[..target_len.saturating_sub(3)])
+/// Best-pull column text: "-" for a guild that hasn't started the raid, "Complete" for a
+/// full clear (or when no pull data exists), otherwise the best percent with the pull
+/// count when known
+pub fn format_best_progress(guild: &GuildData) -> String {
+    if guild.progress.is_none() {
+        return "-".to_string();
+    }
+
+    if is_complete(guild) {
+        "Complete".to_string()
     } else {
-        format!("{}{}", s, " ".repeat(target_len - s.len()))
+        match guild.pull_count {
+            Some(pulls) => format!("{:.1}%({} pulls)", guild.best_percent, pulls),
+            None => format!("{:.1}%", guild.best_percent),
+        }
     }
 }
 
+/// One difficulty section of `/standings`
+#[derive(Debug, Clone)]
+pub struct StandingsSection {
+    pub title: &'static str,
+    pub guilds: Vec<GuildData>,
+}
+
+/// Group guilds into Mythic, Heroic and Normal sections (LFR counts as Normal), each in
+/// [`sort_guilds`] order. Guilds that haven't started the raid and empty sections are left out.
+pub fn group_standings(guilds: Vec<GuildData>) -> Vec<StandingsSection> {
+    let mut sections = vec![
+        StandingsSection { title: "Mythic", guilds: Vec::new() },
+        StandingsSection { title: "Heroic", guilds: Vec::new() },
+        StandingsSection { title: "Normal", guilds: Vec::new() },
+    ];
+    for guild in sort_guilds(guilds) {
+        let Some(progress) = &guild.progress else {
+            continue;
+        };
+        let section = match progress.parse::<Progression>().map(|progression| progression.difficulty) {
+            Ok(Difficulty::Mythic) => 0,
+            Ok(Difficulty::Heroic) => 1,
+            _ => 2,
+        };
+        sections[section].guilds.push(guild);
+    }
+    sections.retain(|section| !section.guilds.is_empty());
+    sections
+}
+
+/// Render a standings section as numbered lines that fit in `max_len` characters,
+/// ending with "...and N more" when guilds had to be left out
+pub fn format_standings_section(guilds: &[GuildData], max_len: usize) -> String {
+    let mut text = String::new();
+    for (i, guild) in guilds.iter().enumerate() {
+        let rank = guild.rank.map(|rank| format!(" · #{}", rank.value())).unwrap_or_default();
+        let line = format!("{}. {} ({}) — {}{}\n", i + 1, guild.name, guild.realm.display_name(), guild.progress_label(), rank);
+
+        let more = format!("...and {} more", guilds.len() - i);
+        let remaining_after = guilds.len() - i - 1;
+        let reserve = if remaining_after > 0 { more.chars().count() } else { 0 };
+        if text.chars().count() + line.chars().count() + reserve > max_len {
+            text.push_str(&more);
+            return text;
+        }
+        text.push_str(&line);
+    }
+    text.trim_end().to_string()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::types::{GuildName, RealmName, WorldRank};
 
+    /// A Tarren Mill guild at `progress`; override other fields with struct update syntax
+    fn progressed(name: &str, progress: &str, rank: Option<u32>) -> GuildData {
+        GuildData {
+            name: GuildName::from(name),
+            realm: RealmName::from("Tarren Mill"),
+            progress: Some(progress.to_string()),
+            rank: rank.map(crate::types::WorldRank::from),
+            best_percent: 50.0,
+            pull_count: None,
+            defeated_at: None,
+            current_boss: None,
+        }
+    }
+
+    #[test]
+    fn test_format_guild_list_splits_long_lists() {
+        let guilds: Vec<GuildData> = (0..62)
+            .map(|i| GuildData {
+                name: GuildName::from(format!("Guild Number {} With A Long Name", i)),
+                realm: RealmName::from("Tarren Mill"),
+                progress: Some("5/8 M".to_string()),
+                rank: Some(WorldRank::new(1000 + i)),
+                best_percent: 42.5,
+                pull_count: Some(150),
+                defeated_at: None,
+                current_boss: None,
+            })
+            .collect();
+
+        let messages = format_guild_list(&guilds, None, true);
+        assert!(messages.len() > 1);
+        for message in &messages {
+            assert!(message.chars().count() < 2000, "message has {} chars", message.chars().count());
+            assert!(message.contains("```Rank"));
+            assert!(message.ends_with("```"));
+        }
+        assert!(messages[0].starts_with("**Guild Rankings (Showing 62 of 62):**"));
+        assert!(messages[1].starts_with("**Guild Rankings (continued"));
+
+        let rows: usize = messages.iter().map(|m| m.matches("Guild Number").count()).sum();
+        assert_eq!(rows, 62);
+    }
+
     #[test]
     fn test_table_formatting() {
         let test_guilds = vec![
             GuildData {
                 name: GuildName::from("Нехай Щастить"),
                 realm: RealmName::from("Tarren Mill"),
-                progress: "8/8 M".to_string(),
+                progress: Some("8/8 M".to_string()),
                 rank: Some(WorldRank::new(50)),
                 best_percent: 100.0,
                 pull_count: None,
                 defeated_at: None,
+                current_boss: None,
             },
             GuildData {
                 name: GuildName::from("Very Long Guild Name That Should Be Truncated"),
                 realm: RealmName::from("Howling Fjord"),
-                progress: "7/8 M".to_string(),
+                progress: Some("7/8 M".to_string()),
                 rank: Some(WorldRank::new(1250)),
                 best_percent: 85.5,
                 pull_count: Some(120),
                 defeated_at: None,
+                current_boss: None,
             },
             GuildData {
                 name: GuildName::from("Short"),
                 realm: RealmName::from("Kazzak"),
-                progress: "6/8 M".to_string(),
+                progress: Some("6/8 M".to_string()),
                 rank: None,
                 best_percent: 75.0,
                 pull_count: None,
                 defeated_at: None,
+                current_boss: None,
             },
         ];
 
-        let output = format_guild_list(&test_guilds, Some(10), false);
+        let messages = format_guild_list(&test_guilds, Some(10), false);
+        assert_eq!(messages.len(), 1);
+        let output = &messages[0];
         println!("Dynamic padding output:\n{}", output);
         
         // Should start with guild rankings header
@@ -401,8 +652,8 @@ mod tests {
     #[test]
     fn test_parse_guild_url() {
         let url = "realm=tarren-mill&name=test-guild";
-        let parsed = parse_guild_url(url);
-        assert!(parsed.is_some());
+        let parsed = GuildUrl::parse(url);
+        assert!(parsed.is_ok());
         
         let guild_url = parsed.unwrap();
         assert_eq!(guild_url.realm.to_string(), "tarren-mill");
@@ -412,8 +663,8 @@ mod tests {
     #[test]
     fn test_parse_invalid_guild_url() {
         let url = "invalid-format";
-        let parsed = parse_guild_url(url);
-        assert!(parsed.is_none());
+        let parsed = GuildUrl::parse(url);
+        assert!(parsed.is_err());
     }
 
     #[test]
@@ -422,20 +673,22 @@ mod tests {
             GuildData {
                 name: GuildName::from("Guild B"),
                 realm: RealmName::from("realm1"),
-                progress: "5/8 M".to_string(),
+                progress: Some("5/8 M".to_string()),
                 rank: Some(crate::types::WorldRank::from(100)),
                 best_percent: 85.0,
                 pull_count: Some(50),
                 defeated_at: None,
+                current_boss: None,
             },
             GuildData {
                 name: GuildName::from("Guild A"),
                 realm: RealmName::from("realm1"),
-                progress: "8/8 M".to_string(),
+                progress: Some("8/8 M".to_string()),
                 rank: Some(crate::types::WorldRank::from(50)),
                 best_percent: 100.0,
                 pull_count: Some(120),
                 defeated_at: None,
+                current_boss: None,
             },
         ];
 
@@ -444,6 +697,74 @@ mod tests {
         assert_eq!(sorted[1].name.to_string(), "Guild B");
     }
 
+    #[test]
+    fn test_sort_guilds_breaks_full_ties_by_name_then_realm() {
+        let tied = |name: &str, realm: &str| GuildData {
+            name: GuildName::from(name),
+            realm: RealmName::from(realm),
+            progress: Some("6/8 M".to_string()),
+            rank: Some(crate::types::WorldRank::from(250)),
+            best_percent: 42.0,
+            pull_count: Some(80),
+            defeated_at: None,
+            current_boss: None,
+        };
+
+        for _ in 0..5 {
+            let sorted = sort_guilds(vec![
+                tied("Zeta", "realm1"),
+                tied("Alpha", "realm2"),
+                tied("Alpha", "realm1"),
+            ]);
+            let order: Vec<(&str, &str)> = sorted.iter().map(|g| (g.name.as_str(), g.realm.as_str())).collect();
+            assert_eq!(order, vec![("Alpha", "realm1"), ("Alpha", "realm2"), ("Zeta", "realm1")]);
+        }
+    }
+
+    #[test]
+    fn test_complete_guild_sorts_before_in_progress_at_same_boss_count() {
+        let guild = |name: &str, progress: &str, best_percent: f64, pull_count: Option<u32>| GuildData {
+            best_percent,
+            pull_count,
+            ..progressed(name, progress, None)
+        };
+        let names = |sorted: Vec<GuildData>| sorted.into_iter().map(|g| g.name.to_string()).collect::<Vec<_>>();
+
+        // 100.0 without pulls is "Complete" and beats any wipe percentage
+        let sorted = sort_guilds(vec![
+            guild("Wiping", "6/8 H", 85.5, Some(40)),
+            guild("Done", "6/8 H", 100.0, None),
+            guild("Close", "6/8 H", 3.2, Some(90)),
+        ]);
+        assert_eq!(names(sorted), vec!["Done", "Close", "Wiping"]);
+
+        // Unranked Mythic guilds follow the same rule
+        let sorted = sort_guilds(vec![guild("Wiping", "4/8 M", 12.0, Some(200)), guild("Done", "4/8 M", 100.0, None)]);
+        assert_eq!(names(sorted), vec!["Done", "Wiping"]);
+
+        // A pulled boss at 100% is just a bad attempt, not a clear
+        let sorted = sort_guilds(vec![guild("Fresh", "6/8 H", 100.0, Some(3)), guild("Wiping", "6/8 H", 85.5, Some(40))]);
+        assert_eq!(names(sorted), vec!["Wiping", "Fresh"]);
+    }
+
+    #[test]
+    fn test_filter_by_difficulty_keeps_only_matching_guilds() {
+        let names = |guilds: Vec<GuildData>| guilds.into_iter().map(|g| g.name.to_string()).collect::<Vec<_>>();
+        let sorted = sort_guilds(vec![
+            progressed("Heroic Two", "7/8 H", None),
+            progressed("Mythic One", "3/8 M", None),
+            progressed("Normal", "8/8 N", None),
+            GuildData { progress: None, ..progressed("Idle", "", None) },
+            progressed("Finder", "8/8 LFR", None),
+            progressed("Mythic Two", "1/8 M", None),
+            progressed("Heroic One", "8/8 H", None),
+        ]);
+
+        assert_eq!(names(filter_by_difficulty(sorted.clone(), RaidDifficulty::Mythic)), vec!["Mythic One", "Mythic Two"]);
+        assert_eq!(names(filter_by_difficulty(sorted.clone(), RaidDifficulty::Heroic)), vec!["Heroic One", "Heroic Two"]);
+        assert_eq!(names(filter_by_difficulty(sorted, RaidDifficulty::Normal)), vec!["Normal"]);
+    }
+
     #[test]
     fn test_difficulty_aware_ranking() {
         // Test the specific case: 8/8 N should rank LOWER than 2/8 H
@@ -451,20 +772,22 @@ mod tests {
             GuildData {
                 name: GuildName::from("Normal Guild"),
                 realm: RealmName::from("realm1"),
-                progress: "8/8 N".to_string(),  // Full normal clear
+                progress: Some("8/8 N".to_string()),  // Full normal clear
                 rank: None,  // No world rank
                 best_percent: 100.0,
                 pull_count: None,
                 defeated_at: None,
+                current_boss: None,
             },
             GuildData {
                 name: GuildName::from("Heroic Guild"),
                 realm: RealmName::from("realm1"),
-                progress: "2/8 H".to_string(),  // 2 heroic bosses
+                progress: Some("2/8 H".to_string()),  // 2 heroic bosses
                 rank: None,  // No world rank
                 best_percent: 25.0,
                 pull_count: None,
                 defeated_at: None,
+                current_boss: None,
             },
         ];
 
@@ -481,38 +804,42 @@ mod tests {
             GuildData {
                 name: GuildName::from("LFR Guild"),
                 realm: RealmName::from("realm1"),
-                progress: "8/8 LFR".to_string(),
+                progress: Some("8/8 LFR".to_string()),
                 rank: None,
                 best_percent: 100.0,
                 pull_count: None,
                 defeated_at: None,
+                current_boss: None,
             },
             GuildData {
                 name: GuildName::from("Normal Guild"),
                 realm: RealmName::from("realm1"),
-                progress: "1/8 N".to_string(),
+                progress: Some("1/8 N".to_string()),
                 rank: None,
                 best_percent: 12.5,
                 pull_count: None,
                 defeated_at: None,
+                current_boss: None,
             },
             GuildData {
                 name: GuildName::from("Heroic Guild"),
                 realm: RealmName::from("realm1"),
-                progress: "1/8 H".to_string(),
+                progress: Some("1/8 H".to_string()),
                 rank: None,
                 best_percent: 12.5,
                 pull_count: None,
                 defeated_at: None,
+                current_boss: None,
             },
             GuildData {
                 name: GuildName::from("Mythic Guild"),
                 realm: RealmName::from("realm1"),
-                progress: "1/8 M".to_string(),
+                progress: Some("1/8 M".to_string()),
                 rank: None,
                 best_percent: 12.5,
                 pull_count: None,
                 defeated_at: None,
+                current_boss: None,
             },
         ];
 
@@ -531,20 +858,22 @@ mod tests {
             GuildData {
                 name: GuildName::from("3 Heroic"),
                 realm: RealmName::from("realm1"),
-                progress: "3/8 H".to_string(),
+                progress: Some("3/8 H".to_string()),
                 rank: None,
                 best_percent: 37.5,
                 pull_count: None,
                 defeated_at: None,
+                current_boss: None,
             },
             GuildData {
                 name: GuildName::from("5 Heroic"),
                 realm: RealmName::from("realm1"),
-                progress: "5/8 H".to_string(),
+                progress: Some("5/8 H".to_string()),
                 rank: None,
                 best_percent: 62.5,
                 pull_count: None,
                 defeated_at: None,
+                current_boss: None,
             },
         ];
 
@@ -565,65 +894,72 @@ mod tests {
             GuildData {
                 name: GuildName::from("8/8 Normal"),
                 realm: RealmName::from("realm1"),
-                progress: "8/8 N".to_string(),
+                progress: Some("8/8 N".to_string()),
                 rank: None,
                 best_percent: 100.0,
                 pull_count: None,
                 defeated_at: None,
+                current_boss: None,
             },
             GuildData {
                 name: GuildName::from("2/8 Heroic"),
                 realm: RealmName::from("realm1"),
-                progress: "2/8 H".to_string(),
+                progress: Some("2/8 H".to_string()),
                 rank: None,
                 best_percent: 25.0,
                 pull_count: None,
                 defeated_at: None,
+                current_boss: None,
             },
             GuildData {
                 name: GuildName::from("1/8 Mythic"),
                 realm: RealmName::from("realm1"),
-                progress: "1/8 M".to_string(),
+                progress: Some("1/8 M".to_string()),
                 rank: None,
                 best_percent: 12.5,
                 pull_count: None,
                 defeated_at: None,
+                current_boss: None,
             },
             GuildData {
                 name: GuildName::from("8/8 Mythic Good Rank"),
                 realm: RealmName::from("realm1"),
-                progress: "8/8 M".to_string(),
+                progress: Some("8/8 M".to_string()),
                 rank: Some(crate::types::WorldRank::from(100)),
                 best_percent: 100.0,
                 pull_count: None,
                 defeated_at: None,
+                current_boss: None,
             },
             GuildData {
                 name: GuildName::from("8/8 Mythic Bad Rank"),
                 realm: RealmName::from("realm1"),
-                progress: "8/8 M".to_string(),
+                progress: Some("8/8 M".to_string()),
                 rank: Some(crate::types::WorldRank::from(500)),
                 best_percent: 100.0,
                 pull_count: None,
                 defeated_at: None,
+                current_boss: None,
             },
             GuildData {
                 name: GuildName::from("7/8 Heroic Better Percent"),
                 realm: RealmName::from("realm1"),
-                progress: "7/8 H".to_string(),
+                progress: Some("7/8 H".to_string()),
                 rank: Some(crate::types::WorldRank::from(1)), // World rank should be ignored for non-8/8M
                 best_percent: 87.5,
                 pull_count: Some(50),
                 defeated_at: None,
+                current_boss: None,
             },
             GuildData {
                 name: GuildName::from("7/8 Heroic Worse Percent"),
                 realm: RealmName::from("realm1"),
-                progress: "7/8 H".to_string(),
+                progress: Some("7/8 H".to_string()),
                 rank: Some(crate::types::WorldRank::from(1000)), // World rank should be ignored for non-8/8M
                 best_percent: 90.0,
                 pull_count: Some(100),
                 defeated_at: None,
+                current_boss: None,
             },
         ];
 
@@ -654,29 +990,32 @@ mod tests {
             GuildData {
                 name: GuildName::from("6/8 Heroic Good Rank"),
                 realm: RealmName::from("realm1"),
-                progress: "6/8 H".to_string(),
+                progress: Some("6/8 H".to_string()),
                 rank: Some(crate::types::WorldRank::from(100)), // Good world rank
                 best_percent: 75.0,
                 pull_count: Some(50),
                 defeated_at: None,
+                current_boss: None,
             },
             GuildData {
                 name: GuildName::from("8/8 Heroic Bad Rank"),
                 realm: RealmName::from("realm1"),
-                progress: "8/8 H".to_string(),
+                progress: Some("8/8 H".to_string()),
                 rank: Some(crate::types::WorldRank::from(5000)), // Bad world rank
                 best_percent: 100.0,
                 pull_count: None,
                 defeated_at: None,
+                current_boss: None,
             },
             GuildData {
                 name: GuildName::from("8/8 Heroic No Rank"),
                 realm: RealmName::from("realm1"),
-                progress: "8/8 H".to_string(),
+                progress: Some("8/8 H".to_string()),
                 rank: None, // No world rank
                 best_percent: 100.0,
                 pull_count: None,
                 defeated_at: None,
+                current_boss: None,
             },
         ];
 
@@ -699,20 +1038,22 @@ mod tests {
             GuildData {
                 name: GuildName::from("8/8 H Guild"),
                 realm: RealmName::from("realm1"),
-                progress: "8/8 H".to_string(),
+                progress: Some("8/8 H".to_string()),
                 rank: None, // No world rank
                 best_percent: 100.0,
                 pull_count: None,
                 defeated_at: None,
+                current_boss: None,
             },
             GuildData {
                 name: GuildName::from("6/8 H Guild"),
                 realm: RealmName::from("realm1"),
-                progress: "6/8 H".to_string(),
+                progress: Some("6/8 H".to_string()),
                 rank: Some(crate::types::WorldRank::from(100)), // Has mythic world rank
                 best_percent: 75.0,
                 pull_count: Some(50),
                 defeated_at: None,
+                current_boss: None,
             },
         ];
 
@@ -724,7 +1065,7 @@ mod tests {
             println!("  {}: {} - {} (rank: {:?})", 
                 i + 1, 
                 guild.name.to_string(), 
-                guild.progress,
+                guild.progress_label(),
                 guild.rank
             );
         }
@@ -741,29 +1082,32 @@ mod tests {
             GuildData {
                 name: GuildName::from("6/8 H Good Rank"),
                 realm: RealmName::from("realm1"),
-                progress: "6/8 H".to_string(),
+                progress: Some("6/8 H".to_string()),
                 rank: Some(crate::types::WorldRank::from(50)), // Very good world rank
                 best_percent: 75.0,
                 pull_count: Some(100),
                 defeated_at: None,
+                current_boss: None,
             },
             GuildData {
                 name: GuildName::from("8/8 H No Rank"),
                 realm: RealmName::from("realm1"), 
-                progress: "8/8 H".to_string(),
+                progress: Some("8/8 H".to_string()),
                 rank: None, // No world rank
                 best_percent: 100.0,
                 pull_count: None,
                 defeated_at: None,
+                current_boss: None,
             },
             GuildData {
                 name: GuildName::from("6/8 H Worse Progress"),
                 realm: RealmName::from("realm1"),
-                progress: "6/8 H".to_string(), 
+                progress: Some("6/8 H".to_string()), 
                 rank: Some(crate::types::WorldRank::from(10)), // Even better world rank
                 best_percent: 60.0,
                 pull_count: Some(50),
                 defeated_at: None,
+                current_boss: None,
             },
         ];
 
@@ -774,7 +1118,7 @@ mod tests {
             println!("  {}: {} - {} (rank: {:?}, percent: {}%)", 
                 i + 1, 
                 guild.name.to_string(), 
-                guild.progress,
+                guild.progress_label(),
                 guild.rank.as_ref().map(|r| r.value()),
                 guild.best_percent
             );
@@ -798,20 +1142,22 @@ mod tests {
             GuildData {
                 name: GuildName::from("1/8 M No Rank"),
                 realm: RealmName::from("realm1"),
-                progress: "1/8 M".to_string(),
+                progress: Some("1/8 M".to_string()),
                 rank: None, // No world rank
                 best_percent: 12.5,
                 pull_count: Some(100),
                 defeated_at: None,
+                current_boss: None,
             },
             GuildData {
                 name: GuildName::from("8/8 H Good Rank"),
                 realm: RealmName::from("realm1"),
-                progress: "8/8 H".to_string(),
+                progress: Some("8/8 H".to_string()),
                 rank: Some(crate::types::WorldRank::from(50)), // Very good world rank
                 best_percent: 100.0,
                 pull_count: None,
                 defeated_at: None,
+                current_boss: None,
             },
         ];
 
@@ -822,7 +1168,7 @@ mod tests {
             println!("  {}: {} - {} (rank: {:?})", 
                 i + 1, 
                 guild.name.to_string(), 
-                guild.progress,
+                guild.progress_label(),
                 guild.rank.as_ref().map(|r| r.value())
             );
         }
@@ -839,20 +1185,22 @@ mod tests {
             GuildData {
                 name: GuildName::from("Should Rank FIRST"),
                 realm: RealmName::from("realm1"),
-                progress: "8/8 H".to_string(), // Full heroic clear
+                progress: Some("8/8 H".to_string()), // Full heroic clear
                 rank: None, // No world rank
                 best_percent: 100.0,
                 pull_count: None,
                 defeated_at: None,
+                current_boss: None,
             },
             GuildData {
                 name: GuildName::from("Should Rank SECOND"),
                 realm: RealmName::from("realm1"),
-                progress: "6/8 H".to_string(), // Partial heroic
+                progress: Some("6/8 H".to_string()), // Partial heroic
                 rank: Some(crate::types::WorldRank::from(1)), // Rank #1 world (very good!)
                 best_percent: 75.0,
                 pull_count: Some(50),
                 defeated_at: None,
+                current_boss: None,
             },
         ];
 
@@ -863,7 +1211,7 @@ mod tests {
             println!("Position {}: {} - {} (world rank: {:?})", 
                 i + 1, 
                 guild.name.to_string(), 
-                guild.progress,
+                guild.progress_label(),
                 guild.rank.as_ref().map(|r| r.value())
             );
         }
@@ -886,29 +1234,32 @@ mod tests {
             GuildData {
                 name: GuildName::from("Later Kill"),
                 realm: RealmName::from("realm1"),
-                progress: "3/8 M".to_string(),
+                progress: Some("3/8 M".to_string()),
                 rank: None,
                 best_percent: 37.5,
                 pull_count: Some(100),
                 defeated_at: Some("2024-01-02T10:00:00Z".to_string()), // Later kill
+                current_boss: None,
             },
             GuildData {
                 name: GuildName::from("Earlier Kill"),
                 realm: RealmName::from("realm1"),
-                progress: "3/8 M".to_string(),
+                progress: Some("3/8 M".to_string()),
                 rank: None,
                 best_percent: 37.5,
                 pull_count: Some(100),
                 defeated_at: Some("2024-01-01T10:00:00Z".to_string()), // Earlier kill
+                current_boss: None,
             },
             GuildData {
                 name: GuildName::from("No Kill Time"),
                 realm: RealmName::from("realm1"),
-                progress: "3/8 M".to_string(),
+                progress: Some("3/8 M".to_string()),
                 rank: None,
                 best_percent: 30.0, // Better percent but no kill time
                 pull_count: Some(50),
                 defeated_at: None,
+                current_boss: None,
             },
         ];
 
@@ -919,7 +1270,7 @@ mod tests {
             println!("  {}: {} - {} (kill time: {:?})", 
                 i + 1, 
                 guild.name.to_string(), 
-                guild.progress,
+                guild.progress_label(),
                 guild.defeated_at
             );
         }
@@ -941,56 +1292,62 @@ mod tests {
             GuildData {
                 name: GuildName::from("Arey"),
                 realm: RealmName::from("Terokkar"),
-                progress: "2/8 M".to_string(),
+                progress: Some("2/8 M".to_string()),
                 rank: Some(crate::types::WorldRank::from(1102)),
                 best_percent: 25.0,
                 pull_count: None,
                 defeated_at: None,
+                current_boss: None,
             },
             GuildData {
                 name: GuildName::from("Thorned Horde"),
                 realm: RealmName::from("Tarren Mill"),
-                progress: "2/8 M".to_string(),
+                progress: Some("2/8 M".to_string()),
                 rank: Some(crate::types::WorldRank::from(1176)),
                 best_percent: 25.0,
                 pull_count: None,
                 defeated_at: None,
+                current_boss: None,
             },
             GuildData {
                 name: GuildName::from("Nomads TM"),
                 realm: RealmName::from("Tarren Mill"),
-                progress: "2/8 M".to_string(),
+                progress: Some("2/8 M".to_string()),
                 rank: Some(crate::types::WorldRank::from(925)),
                 best_percent: 25.0,
                 pull_count: None,
                 defeated_at: None,
+                current_boss: None,
             },
             GuildData {
                 name: GuildName::from("Tauren Milfs"),
                 realm: RealmName::from("Tarren Mill"),
-                progress: "2/8 M".to_string(),
+                progress: Some("2/8 M".to_string()),
                 rank: Some(crate::types::WorldRank::from(942)),
                 best_percent: 25.0,
                 pull_count: None,
                 defeated_at: None,
+                current_boss: None,
             },
             GuildData {
                 name: GuildName::from("Wrong Tactics Folks"),
                 realm: RealmName::from("Tarren Mill"),
-                progress: "2/8 M".to_string(),
+                progress: Some("2/8 M".to_string()),
                 rank: Some(crate::types::WorldRank::from(1116)),
                 best_percent: 25.0,
                 pull_count: None,
                 defeated_at: None,
+                current_boss: None,
             },
             GuildData {
                 name: GuildName::from("Нехай Щастить"),
                 realm: RealmName::from("Tarren Mill"),
-                progress: "2/8 M".to_string(),
+                progress: Some("2/8 M".to_string()),
                 rank: Some(crate::types::WorldRank::from(746)),
                 best_percent: 25.0,
                 pull_count: None,
                 defeated_at: None,
+                current_boss: None,
             },
         ];
 
@@ -1001,7 +1358,7 @@ mod tests {
             println!("  {}: {} - {} - rank: {:?}", 
                 i + 1, 
                 guild.name.to_string(), 
-                guild.progress,
+                guild.progress_label(),
                 guild.rank.as_ref().map(|r| r.value())
             );
         }
@@ -1014,4 +1371,197 @@ mod tests {
         assert_eq!(sorted[4].name.to_string(), "Wrong Tactics Folks"); // #1116
         assert_eq!(sorted[5].name.to_string(), "Thorned Horde"); // #1176
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_zero_world_rank_sorts_as_unranked() {
+        let guild = |name: &str, rank: Option<WorldRank>, best_percent: f64| GuildData {
+            rank,
+            best_percent,
+            ..progressed(name, "5/8 M", None)
+        };
+
+        // raider.io's 0 ("unranked") becomes None rather than Some(0)
+        assert_eq!(WorldRank::ranked(0), None);
+
+        let sorted = sort_guilds(vec![
+            guild("Zero Rank", WorldRank::ranked(0), 30.0),
+            guild("Ranked", WorldRank::ranked(500), 40.0),
+            guild("No Rank", None, 20.0),
+        ]);
+        let names: Vec<String> = sorted.iter().map(|g| g.name.to_string()).collect();
+        assert_eq!(names, vec!["Ranked", "No Rank", "Zero Rank"]);
+    }
+
+    #[test]
+    fn test_progression_parse() {
+        let parse = |s: &str| s.parse::<Progression>();
+
+        let mythic = parse("8/8 M").unwrap();
+        assert_eq!(mythic, Progression { difficulty: Difficulty::Mythic, bosses: 8, total: 8 });
+        assert!(mythic.is_full_clear());
+
+        let lfr = parse("8/8 LFR").unwrap();
+        assert_eq!(lfr.difficulty, Difficulty::Lfr);
+        assert!(lfr.is_full_clear());
+
+        // Raids with other boss counts clear at their own total, not at 8
+        let nine = parse("6/9 M").unwrap();
+        assert_eq!((nine.bosses, nine.total), (6, 9));
+        assert!(!nine.is_full_clear());
+        assert!(!parse("8/9 M").unwrap().is_full_clear());
+        assert!(parse("9/9 M").unwrap().is_full_clear());
+
+        let heroic = parse(" 3/9 H ").unwrap();
+        assert_eq!((heroic.bosses, heroic.total, heroic.difficulty), (3, 9, Difficulty::Heroic));
+        assert!(!heroic.is_full_clear());
+        assert_eq!(parse("0/8 N").unwrap().difficulty, Difficulty::Normal);
+
+        for invalid in ["No progress", "", "8/8", "8/8 X", "x/8 M", "M", "9/8 M", "8 M"] {
+            assert!(parse(invalid).is_err(), "{:?} should not parse", invalid);
+        }
+    }
+
+    #[test]
+    fn test_progression_display_round_trips() {
+        for summary in ["8/8 M", "0/8 N", "5/9 H", "8/8 LFR"] {
+            assert_eq!(summary.parse::<Progression>().unwrap().to_string(), summary);
+        }
+    }
+
+    #[test]
+    fn test_progression_orders_by_difficulty_then_bosses() {
+        let parse = |s: &str| s.parse::<Progression>().unwrap();
+        assert!(parse("1/8 M") > parse("8/8 H"));
+        assert!(parse("8/8 H") > parse("7/8 H"));
+        assert!(parse("8/8 N") > parse("8/8 LFR"));
+    }
+
+    #[test]
+    fn test_difficulty_api_name() {
+        assert_eq!(Difficulty::Mythic.api_name(), "mythic");
+        assert_eq!(Difficulty::Heroic.api_name(), "heroic");
+        assert_eq!(Difficulty::Lfr.api_name(), "normal");
+    }
+
+    #[test]
+    fn test_estimate_best_percent() {
+        let cases = [
+            ("8/8 M", 100.0),
+            ("8/8 H", 100.0),
+            ("3/8 M", 37.5),
+            ("0/8 M", 0.0),
+            ("M", 75.0),
+            ("x/8 M", 75.0),
+            ("5/8 H", 25.0),
+            ("3/8 N", 10.0),
+            ("0/8 N", 0.0),
+            ("No progress", 0.0),
+            ("garbage", 10.0),
+            ("6/9 M", (6.0 / 9.0) * 100.0),
+            ("8/9 M", (8.0 / 9.0) * 100.0),
+            ("9/9 M", 100.0),
+            ("9/9 N", 100.0),
+        ];
+        for (progress, expected) in cases {
+            assert_eq!(estimate_best_percent(progress), (expected, None), "progress {:?}", progress);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fetch_all_guild_data_reuses_open_database() {
+        let database = Database::open_temp().await;
+        database.clear_guilds().await;
+        let migrations = database.get_migrations().await.unwrap().len();
+
+        // Reconnecting through the configured URL would fail on this path
+        let mut config = AppConfig::default();
+        config.database.url = "sqlite:///nonexistent-dir/wow_guild_bot.db".to_string();
+
+        let client = RaiderIOClient::from_config(&config).unwrap();
+        let fetch = fetch_all_guild_data(RaidTier::from(3), &config, &database, &client).await.unwrap();
+        assert!(fetch.guilds.is_empty());
+        assert!(!fetch.timed_out);
+        assert_eq!(database.get_migrations().await.unwrap().len(), migrations);
+    }
+
+    #[tokio::test]
+    async fn test_collect_within_returns_partial_results_on_timeout() {
+        let delays = [0u64, 5, 10, 5_000];
+        let slow = stream::iter(delays).then(|ms| async move {
+            tokio::time::sleep(Duration::from_millis(ms)).await;
+            Some(ms)
+        });
+
+        let (partial, timed_out) = collect_within(slow, Some(Duration::from_millis(500))).await;
+        assert!(timed_out);
+        assert_eq!(partial, vec![0, 5, 10]);
+
+        let fast = stream::iter([Some(1), None, Some(3)]);
+        let (complete, timed_out) = collect_within(fast, None).await;
+        assert!(!timed_out);
+        assert_eq!(complete, vec![1, 3]);
+    }
+
+    #[test]
+    fn test_group_standings_buckets_by_difficulty() {
+        let sections = group_standings(vec![
+            progressed("Heroic Two", "5/8 H", None),
+            progressed("Mythic Low", "2/8 M", Some(900)),
+            progressed("Normal Clear", "8/8 N", None),
+            progressed("Heroic Clear", "8/8 H", None),
+            progressed("Mythic High", "6/8 M", Some(120)),
+        ]);
+
+        let layout: Vec<(&str, Vec<&str>)> = sections
+            .iter()
+            .map(|section| (section.title, section.guilds.iter().map(|g| g.name.as_str()).collect()))
+            .collect();
+        assert_eq!(
+            layout,
+            vec![
+                ("Mythic", vec!["Mythic High", "Mythic Low"]),
+                ("Heroic", vec!["Heroic Clear", "Heroic Two"]),
+                ("Normal", vec!["Normal Clear"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_not_started_guilds_are_shown_distinctly() {
+        let not_started = GuildData { progress: None, best_percent: 0.0, ..progressed("Fresh Start", "", None) };
+        let sorted = sort_guilds(vec![not_started.clone(), progressed("Zero Kills", "0/8 N", None)]);
+        let names: Vec<&str> = sorted.iter().map(|g| g.name.as_str()).collect();
+        assert_eq!(names, vec!["Zero Kills", "Fresh Start"]);
+
+        let table = format_guild_list(&sorted, None, true).concat();
+        let row = table.lines().find(|line| line.contains("Fresh Start")).unwrap();
+        assert!(row.contains("Not started"), "row: {}", row);
+        assert!(row.trim_end().ends_with(" -"), "row: {}", row);
+        assert!(table.lines().any(|line| line.contains("Zero Kills") && line.contains("0/8 N")));
+
+        // Standings only list guilds with progression
+        let sections = group_standings(vec![not_started, progressed("Zero Kills", "0/8 N", None)]);
+        assert_eq!(sections.len(), 1);
+        assert_eq!(sections[0].guilds.len(), 1);
+    }
+
+    #[test]
+    fn test_group_standings_skips_empty_sections() {
+        let sections = group_standings(vec![progressed("Only Heroic", "3/8 H", None)]);
+        assert_eq!(sections.len(), 1);
+        assert_eq!(sections[0].title, "Heroic");
+    }
+
+    #[test]
+    fn test_format_standings_section_fits_limit() {
+        let guilds: Vec<GuildData> = (0..60).map(|i| progressed(&format!("Guild {}", i), "4/8 H", None)).collect();
+        let text = format_standings_section(&guilds, 1024);
+
+        assert!(text.chars().count() <= 1024);
+        assert!(text.starts_with("1. Guild 0 (Tarren Mill) — 4/8 H"));
+        assert!(text.ends_with("more"));
+
+        let short = format_standings_section(&guilds[..2], 1024);
+        assert_eq!(short, "1. Guild 0 (Tarren Mill) — 4/8 H\n2. Guild 1 (Tarren Mill) — 4/8 H");
+    }
+}
@@ -0,0 +1,224 @@
+/// Localized response strings (English/Ukrainian)
+use std::fmt;
+
+/// Language used for command responses
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Locale {
+    #[default]
+    En,
+    Uk,
+}
+
+impl Locale {
+    /// Parse a locale code such as Discord's `en-US` or `uk`
+    pub fn from_code(code: &str) -> Option<Self> {
+        let language = code.trim().split(['-', '_']).next()?.to_lowercase();
+        match language.as_str() {
+            "en" => Some(Locale::En),
+            "uk" | "ua" => Some(Locale::Uk),
+            _ => None,
+        }
+    }
+
+    fn messages(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Locale::En => EN,
+            Locale::Uk => UK,
+        }
+    }
+}
+
+const EN: &[(&str, &str)] = &[
+    ("guilds.no_progression", "At the moment, there are no guilds with progression in season {season}."),
+    ("guilds.fetch_error", "An error occurred while fetching guild data: {error}. Please check that uaguildlist.txt exists and contains valid guild URLs."),
+    ("guilds.limit_too_small", "Error: The value of limit must be at least 1."),
+    ("guilds.invalid_season", "Error: Season must be 1, 2, 3 or previous."),
+    ("guilds.limit_invalid", "Error: Invalid limit '{limit}'. Use a number between 1 and {max} or 'all'."),
+    ("guilds.unknown_difficulty", "Error: Unknown difficulty '{difficulty}'. Use all, mythic, heroic or normal."),
+    ("guilds.no_difficulty", "No guilds are progressing on {difficulty} in season {season}."),
+    ("guilds.fetching", "Fetching data for {count} guilds, this may take a moment..."),
+    ("guilds.partial", "_Fetching guild data took longer than {secs}s (likely rate limiting); showing only the guilds loaded so far._"),
+    ("standings.title", "Raid Standings (Season {season})"),
+    ("topguild.details", "**Top guild in season {season}: {name}** ({realm})\nProgress: {progress}\nWorld rank: {rank}\nBest: {best}"),
+    ("topguild.current_boss", "Currently on: {boss}, {percent}% best"),
+    ("rank.top_range", "Error: The value of top must be between 1 and 50 inclusive."),
+    ("rank.rio_range", "Error: The value of rio must be between 0 and 3500 inclusive."),
+    ("rank.rio_max_range", "Error: The value of rio_max must be between rio and 3500 inclusive."),
+    ("rank.unknown_class", "Class '{class}' does not exist. Use the valid classes: {valid}."),
+    ("rank.unknown_role", "Role '{role}' does not exist. Use the valid roles: all, dps, healer, tank."),
+    ("rank.no_players", "No players found matching the criteria."),
+    ("rank.no_members", "No member data has been collected yet. Run the parser first."),
+    ("rank.no_data", "No data to process: {error}. Check that the database contains member data."),
+    ("rank.header", "**Player Rankings (Top {top} | Classes: {classes} | Guilds: {guilds} | Realms: {realms} | Role: {role} | RIO {rio}):**"),
+    ("rank.continued", "**Player Rankings (continued - {from} to {to}):**"),
+    ("help.title", "Available Commands"),
+    ("help.required", "required"),
+    ("lastupdated.refreshed", "Member data was refreshed {ago} ({time})."),
+    ("lastupdated.never", "Member data has not been collected yet."),
+    ("rank.last_updated", "_Data refreshed {ago}._"),
+    ("time.just_now", "just now"),
+    ("cooldown.wait", "Please wait {secs}s before using /{command} again."),
+    ("command.timeout", "This is taking longer than expected, please try again."),
+];
+
+const UK: &[(&str, &str)] = &[
+    ("guilds.no_progression", "Наразі немає гільдій з прогресом у сезоні {season}."),
+    ("guilds.fetch_error", "Під час отримання даних гільдій сталася помилка: {error}. Перевірте, що uaguildlist.txt існує та містить коректні посилання на гільдії."),
+    ("guilds.limit_too_small", "Помилка: значення limit має бути щонайменше 1."),
+    ("guilds.invalid_season", "Помилка: сезон має бути 1, 2, 3 або previous."),
+    ("guilds.limit_invalid", "Помилка: некоректний limit '{limit}'. Вкажіть число від 1 до {max} або 'all'."),
+    ("guilds.unknown_difficulty", "Помилка: невідома складність '{difficulty}'. Вкажіть all, mythic, heroic або normal."),
+    ("guilds.no_difficulty", "Немає гільдій з прогресом на складності {difficulty} у сезоні {season}."),
+    ("guilds.fetching", "Отримуємо дані {count} гільдій, це може зайняти трохи часу..."),
+    ("guilds.partial", "_Отримання даних гільдій тривало довше за {secs} с (ймовірно, через обмеження запитів); показано лише гільдії, завантажені на цей момент._"),
+    ("standings.title", "Рейдовий залік (сезон {season})"),
+    ("topguild.details", "**Найкраща гільдія сезону {season}: {name}** ({realm})\nПрогрес: {progress}\nСвітовий рейтинг: {rank}\nНайкраща спроба: {best}"),
+    ("topguild.current_boss", "Поточний бос: {boss}, найкраща спроба {percent}%"),
+    ("rank.top_range", "Помилка: значення top має бути від 1 до 50 включно."),
+    ("rank.rio_range", "Помилка: значення rio має бути від 0 до 3500 включно."),
+    ("rank.rio_max_range", "Помилка: значення rio_max має бути від rio до 3500 включно."),
+    ("rank.unknown_class", "Класу '{class}' не існує. Доступні класи: {valid}."),
+    ("rank.unknown_role", "Ролі '{role}' не існує. Доступні ролі: all, dps, healer, tank."),
+    ("rank.no_players", "Не знайдено гравців, що відповідають критеріям."),
+    ("rank.no_members", "Дані учасників ще не зібрано. Спершу запустіть парсер."),
+    ("rank.no_data", "Немає даних для обробки: {error}. Перевірте, що база даних містить дані учасників."),
+    ("rank.header", "**Рейтинг гравців (Топ {top} | Класи: {classes} | Гільдії: {guilds} | Сервери: {realms} | Роль: {role} | RIO {rio}):**"),
+    ("rank.continued", "**Рейтинг гравців (продовження - з {from} по {to}):**"),
+    ("help.title", "Доступні команди"),
+    ("help.required", "обов'язково"),
+    ("help.guilds", "Рейтинг гільдій у рейдах"),
+    ("help.topguild", "Гільдія-лідер рейдового прогресу"),
+    ("help.standings", "Прогрес гільдій, згрупований за складністю"),
+    ("help.classdistribution", "Склад гільдії за класами та ролями"),
+    ("help.rank", "Рейтинг гравців у Mythic+"),
+    ("help.player", "Профіль гравця в Mythic+"),
+    ("help.search", "Пошук гравців за частиною імені"),
+    ("help.classtop", "Найкращий гравець кожного класу за RIO"),
+    ("help.trackedguilds", "Усі гільдії, які відстежує бот, за серверами"),
+    ("help.about_us", "Про нас"),
+    ("help.rules", "Правила"),
+    ("help.help", "Інформація про доступні команди"),
+    ("help.lastupdated", "Коли востаннє оновлювалися дані гравців"),
+    ("help.refreshguild", "Оновити склад і RIO однієї гільдії зараз"),
+    ("help.import", "Додати гільдії для відстеження зі списку"),
+    ("help.maintenance", "Стиснути базу даних і оновити її статистику"),
+    ("lastupdated.refreshed", "Дані гравців оновлено {ago} ({time})."),
+    ("lastupdated.never", "Дані гравців ще не збиралися."),
+    ("rank.last_updated", "_Дані оновлено {ago}._"),
+    ("time.just_now", "щойно"),
+    ("cooldown.wait", "Зачекайте {secs} с, перш ніж знову використати /{command}."),
+    ("command.timeout", "Це триває довше, ніж очікувалося, спробуйте ще раз."),
+];
+
+/// Look up a message, falling back to English when the locale has no entry
+pub fn lookup(key: &str, locale: Locale) -> Option<&'static str> {
+    resolve(locale.messages(), key)
+}
+
+fn resolve(messages: &'static [(&'static str, &'static str)], key: &str) -> Option<&'static str> {
+    let find = |messages: &'static [(&'static str, &'static str)]| {
+        messages.iter().find(|(k, _)| *k == key).map(|(_, message)| *message)
+    };
+    find(messages).or_else(|| find(EN))
+}
+
+/// Describe elapsed time relative to now, e.g. "3 hours ago"
+pub fn time_ago(elapsed: chrono::Duration, locale: Locale) -> String {
+    let minutes = elapsed.num_minutes();
+    if minutes < 1 {
+        return t("time.just_now", locale);
+    }
+
+    let (count, unit) = if minutes < 60 {
+        (minutes, 0)
+    } else if minutes < 60 * 24 {
+        (minutes / 60, 1)
+    } else {
+        (minutes / (60 * 24), 2)
+    };
+
+    match locale {
+        Locale::En => {
+            let unit = ["minute", "hour", "day"][unit];
+            let plural = if count == 1 { "" } else { "s" };
+            format!("{} {}{} ago", count, unit, plural)
+        }
+        Locale::Uk => {
+            let forms = [
+                ["хвилину", "хвилини", "хвилин"],
+                ["годину", "години", "годин"],
+                ["день", "дні", "днів"],
+            ][unit];
+            let form = match (count % 10, count % 100) {
+                (1, n) if n != 11 => forms[0],
+                (2..=4, n) if !(12..=14).contains(&n) => forms[1],
+                _ => forms[2],
+            };
+            format!("{} {} тому", count, form)
+        }
+    }
+}
+
+/// Translate a message key; unknown keys are returned as-is
+pub fn t(key: &str, locale: Locale) -> String {
+    lookup(key, locale).unwrap_or(key).to_string()
+}
+
+/// Translate a message key and substitute its `{name}` placeholders
+pub fn t_args(key: &str, locale: Locale, args: &[(&str, &dyn fmt::Display)]) -> String {
+    args.iter().fold(t(key, locale), |message, (name, value)| {
+        message.replace(&format!("{{{}}}", name), &value.to_string())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locale_from_code() {
+        assert_eq!(Locale::from_code("uk"), Some(Locale::Uk));
+        assert_eq!(Locale::from_code("en-US"), Some(Locale::En));
+        assert_eq!(Locale::from_code("en_GB"), Some(Locale::En));
+        assert_eq!(Locale::from_code("de"), None);
+    }
+
+    #[test]
+    fn test_key_resolves_in_both_locales() {
+        assert_eq!(t("rank.no_players", Locale::En), "No players found matching the criteria.");
+        assert_eq!(t("rank.no_players", Locale::Uk), "Не знайдено гравців, що відповідають критеріям.");
+    }
+
+    #[test]
+    fn test_missing_key_falls_back_to_english() {
+        const PARTIAL: &[(&str, &str)] = &[("help.title", "Доступні команди")];
+        assert_eq!(resolve(PARTIAL, "help.title"), Some("Доступні команди"));
+        assert_eq!(resolve(PARTIAL, "rank.no_players"), Some("No players found matching the criteria."));
+        assert_eq!(resolve(PARTIAL, "no.such.key"), None);
+        assert_eq!(t("no.such.key", Locale::Uk), "no.such.key");
+
+        // Locale-only keys (e.g. help descriptions) have no English entry
+        assert_eq!(lookup("help.guilds", Locale::En), None);
+    }
+
+    #[test]
+    fn test_time_ago() {
+        use chrono::Duration;
+        assert_eq!(time_ago(Duration::seconds(20), Locale::En), "just now");
+        assert_eq!(time_ago(Duration::minutes(1), Locale::En), "1 minute ago");
+        assert_eq!(time_ago(Duration::minutes(185), Locale::En), "3 hours ago");
+        assert_eq!(time_ago(Duration::days(2), Locale::En), "2 days ago");
+        assert_eq!(time_ago(Duration::hours(1), Locale::Uk), "1 годину тому");
+        assert_eq!(time_ago(Duration::hours(3), Locale::Uk), "3 години тому");
+        assert_eq!(time_ago(Duration::hours(11), Locale::Uk), "11 годин тому");
+        assert_eq!(time_ago(Duration::days(21), Locale::Uk), "21 день тому");
+    }
+
+    #[test]
+    fn test_placeholders_are_substituted() {
+        assert_eq!(
+            t_args("guilds.limit_invalid", Locale::En, &[("limit", &"ten"), ("max", &50)]),
+            "Error: Invalid limit 'ten'. Use a number between 1 and 50 or 'all'."
+        );
+    }
+}
@@ -60,24 +60,25 @@ pub fn init_logging(config: &LoggingConfig) -> Result<()> {
             .with_writer(summary_writer)
             .with_filter(summary_filter);
         
-        // Initialize with console and summary layers (detailed errors in individual files)
+        // Initialize with console and summary layers (detailed errors in individual files).
+        // Console logs go to stderr so command output such as `db-status --json` stays parseable.
         let subscriber = tracing_subscriber::registry()
             .with(summary_layer);
             
         match config.format {
             LogFormat::Json => {
                 subscriber
-                    .with(fmt::layer().json().with_filter(console_filter))
+                    .with(fmt::layer().json().with_writer(std::io::stderr).with_filter(console_filter))
                     .init();
             },
             LogFormat::Pretty => {
                 subscriber
-                    .with(fmt::layer().pretty().with_filter(console_filter))
+                    .with(fmt::layer().pretty().with_writer(std::io::stderr).with_filter(console_filter))
                     .init();
             },
             LogFormat::Compact => {
                 subscriber
-                    .with(fmt::layer().compact().with_filter(console_filter))
+                    .with(fmt::layer().compact().with_writer(std::io::stderr).with_filter(console_filter))
                     .init();
             },
         }
@@ -89,18 +90,21 @@ pub fn init_logging(config: &LoggingConfig) -> Result<()> {
             LogFormat::Json => {
                 tracing_subscriber::fmt()
                     .json()
+                    .with_writer(std::io::stderr)
                     .with_env_filter(console_filter)
                     .init();
             },
             LogFormat::Pretty => {
                 tracing_subscriber::fmt()
                     .pretty()
+                    .with_writer(std::io::stderr)
                     .with_env_filter(console_filter)
                     .init();
             },
             LogFormat::Compact => {
                 tracing_subscriber::fmt()
                     .compact()
+                    .with_writer(std::io::stderr)
                     .with_env_filter(console_filter)
                     .init();
             },
@@ -200,6 +204,30 @@ macro_rules! log_data_processing {
     };
 }
 
+/// Delete the oldest files in `dir` until at most `keep` remain, returning how many
+/// were removed. Age is the modification time, with the (timestamped) file name as
+/// a tie-break. Uses async file IO, since it runs before every error dump.
+pub async fn prune_error_dir(dir: &Path, keep: usize) -> std::io::Result<usize> {
+    let mut files = Vec::new();
+    let mut entries = tokio::fs::read_dir(dir).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        let metadata = entry.metadata().await?;
+        if metadata.is_file() {
+            files.push((metadata.modified()?, entry.file_name(), entry.path()));
+        }
+    }
+    if files.len() <= keep {
+        return Ok(0);
+    }
+
+    files.sort();
+    let excess = files.len() - keep;
+    for (_, _, path) in &files[..excess] {
+        tokio::fs::remove_file(path).await?;
+    }
+    Ok(excess)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -222,5 +250,34 @@ mod tests {
         assert_eq!(config.format, LogFormat::Pretty);
         assert!(config.file_enabled);
         assert!(config.file_path.is_some());
+        assert_eq!(config.max_error_files, 1000);
+    }
+
+    #[tokio::test]
+    async fn test_prune_error_dir_removes_oldest_files() {
+        let dir = std::env::temp_dir().join(format!("wow_guild_bot_errors_{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        // Same-second writes share an mtime, so the timestamped names decide the order
+        for i in 0..5 {
+            fs::write(dir.join(format!("20250101_00000{}_000_attempt_1.json", i)), "{}").unwrap();
+        }
+
+        assert_eq!(prune_error_dir(&dir, 3).await.unwrap(), 2);
+        let mut remaining: Vec<String> = fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        remaining.sort();
+        assert_eq!(
+            remaining,
+            vec![
+                "20250101_000002_000_attempt_1.json",
+                "20250101_000003_000_attempt_1.json",
+                "20250101_000004_000_attempt_1.json",
+            ]
+        );
+        assert_eq!(prune_error_dir(&dir, 3).await.unwrap(), 0);
+
+        fs::remove_dir_all(&dir).unwrap();
     }
 }
\ No newline at end of file
@@ -1,29 +1,41 @@
 /// WoW Guild Discord Bot - A Rust implementation for guild progression tracking
 use serenity::async_trait;
+use serenity::builder::CreateEmbed;
+use serenity::model::application::interaction::application_command::ApplicationCommandInteraction;
 use serenity::model::application::interaction::{Interaction, InteractionResponseType};
 use serenity::model::application::command::Command;
 use serenity::model::gateway::Ready;
 use serenity::model::guild::Member;
-use serenity::model::id::RoleId;
+use serenity::model::id::{MessageId, RoleId};
 use serenity::prelude::*;
 use std::env;
+use std::future::Future;
 use tracing::{error, info, warn};
 
 // Module declarations
+mod command_stats;
 mod commands;
 mod config;
+mod cooldown;
 mod database;
 mod error;
 mod guild_data;
+mod locale;
 mod logging;
 mod parser;
 mod raider_io;
+mod responder;
+mod table;
 mod types;
 
 // Re-exports for convenience
-use crate::config::AppConfig;
+use crate::command_stats::CommandStats;
+use crate::config::{intents_without_unapproved, AppConfig};
+use crate::cooldown::CommandCooldowns;
 use crate::database::Database;
+use crate::raider_io::RaiderIOClient;
 use crate::error::{BotError, Result};
+use crate::responder::{edit_followups, send_followups, split_to_discord_limit, InteractionResponder, Responder};
 
 // Logging macros
 macro_rules! log_api_request {
@@ -52,11 +64,152 @@ macro_rules! log_discord_command {
 struct Handler {
     config: AppConfig,
     database: Database,
+    /// Shared by every command so the circuit breaker's state outlives a single call
+    raider_io: RaiderIOClient,
+    /// Whether the privileged GUILD_MEMBERS intent was requested
+    member_events: bool,
+    /// Per-user cooldowns for expensive commands
+    cooldowns: CommandCooldowns,
+    /// Invocation counts per command
+    command_stats: CommandStats,
 }
 
 impl Handler {
-    fn new(config: AppConfig, database: Database) -> Self {
-        Self { config, database }
+    fn new(config: AppConfig, database: Database, raider_io: RaiderIOClient, intents: GatewayIntents) -> Self {
+        let member_events = intents.contains(GatewayIntents::GUILD_MEMBERS);
+        let cooldowns = CommandCooldowns::new(&config.discord.command_cooldowns);
+        let command_stats = CommandStats::new(commands::COMMANDS.iter().map(|meta| meta.name));
+        Self { config, database, raider_io, member_events, cooldowns, command_stats }
+    }
+
+    /// Best-effort lookup of a guild-specific role from the member's nickname
+    async fn resolve_guild_role(&self, member: &Member) -> Option<RoleId> {
+        let display_name = member.nick.as_deref().unwrap_or(&member.user.name);
+        let character_name = character_name_from_nick(display_name)?;
+
+        let characters = match self.database.find_members_by_name(&character_name).await {
+            Ok(characters) => characters,
+            Err(e) => {
+                warn!(nick = %display_name, error = %e, "Failed to look up character for guild role");
+                return None;
+            }
+        };
+
+        let role_id = characters
+            .iter()
+            .filter_map(|c| c.guild_name.as_deref())
+            .find_map(|guild| self.config.discord.guild_role_for(guild))?;
+
+        info!(
+            nick = %display_name,
+            character = %character_name,
+            role_id = role_id.value(),
+            "Matched new member to tracked guild character"
+        );
+        Some(RoleId(role_id.value()))
+    }
+}
+
+/// Log and build the reply for a deferred command that hit `COMMAND_TIMEOUT_SECS`
+fn timed_out(command_name: &str, locale: locale::Locale) -> Vec<String> {
+    warn!(command = %command_name, secs = commands::COMMAND_TIMEOUT_SECS, "Command timed out");
+    vec![locale::t("command.timeout", locale)]
+}
+
+/// Interaction responses shared by the command handlers
+impl Handler {
+    /// Answer a quick command with plain text; anything past Discord's limit follows as
+    /// extra follow-up messages
+    async fn respond_text(&self, ctx: &Context, command: &ApplicationCommandInteraction, content: String) {
+        let chunks = split_to_discord_limit(&content);
+        let (first, rest) = chunks.split_first().map_or(("", &[][..]), |(first, rest)| (first.as_str(), rest));
+        if let Err(why) = command
+            .create_interaction_response(&ctx.http, |response| {
+                response
+                    .kind(InteractionResponseType::ChannelMessageWithSource)
+                    .interaction_response_data(|message| message.content(first))
+            })
+            .await
+        {
+            error!(command = %command.data.name, error = %why, "Cannot respond to slash command");
+        } else if let Err(why) = send_followups(&InteractionResponder::new(&ctx.http, command), rest).await {
+            error!(command = %command.data.name, error = %why, "Failed to send follow-up");
+        } else {
+            info!(command = %command.data.name, user = command.user.id.0, response_length = content.len(), "Command completed successfully");
+        }
+    }
+
+    /// Answer a quick command with an embed, or with its error text
+    async fn respond_embed(&self, ctx: &Context, command: &ApplicationCommandInteraction, response: std::result::Result<CreateEmbed, String>) {
+        if let Err(why) = command
+            .create_interaction_response(&ctx.http, |response_builder| {
+                response_builder
+                    .kind(InteractionResponseType::ChannelMessageWithSource)
+                    .interaction_response_data(|message| match response {
+                        Ok(embed) => message.add_embed(embed),
+                        Err(content) => message.content(content),
+                    })
+            })
+            .await
+        {
+            error!(command = %command.data.name, error = %why, "Cannot respond to slash command");
+        } else {
+            info!(command = %command.data.name, user = command.user.id.0, "Command completed successfully");
+        }
+    }
+
+    /// Acknowledge a slow command so Discord waits for its follow-ups; false if that failed
+    async fn defer(&self, ctx: &Context, command: &ApplicationCommandInteraction) -> bool {
+        if let Err(why) = command
+            .create_interaction_response(&ctx.http, |response| {
+                response.kind(InteractionResponseType::DeferredChannelMessageWithSource)
+            })
+            .await
+        {
+            error!(command = %command.data.name, error = %why, "Failed to defer response");
+            return false;
+        }
+        info!("Executing command: {}", command.data.name);
+        true
+    }
+
+    /// Defer, then run `messages` (futures are lazy, so the handler only starts once
+    /// Discord has been told to wait) and send its result as follow-ups
+    async fn respond_deferred(&self, ctx: &Context, command: &ApplicationCommandInteraction, messages: impl Future<Output = Vec<String>>) {
+        if self.defer(ctx, command).await {
+            let messages = messages.await;
+            self.send_messages(ctx, command, None, &messages).await;
+        }
+    }
+
+    /// Send a deferred command's messages, replacing `placeholder` with the first one if given.
+    /// Long responses are split into chunks, each sent as its own follow-up.
+    async fn send_messages(&self, ctx: &Context, command: &ApplicationCommandInteraction, placeholder: Option<MessageId>, messages: &[String]) {
+        let responder = InteractionResponder::new(&ctx.http, command);
+        let sent = match placeholder {
+            Some(message_id) => edit_followups(&responder, message_id, messages).await,
+            None => send_followups(&responder, messages).await,
+        };
+        if let Err(why) = sent {
+            error!(command = %command.data.name, error = %why, "Failed to send follow-up");
+        } else {
+            let total_length: usize = messages.iter().map(|m| m.len()).sum();
+            info!(command = %command.data.name, user = command.user.id.0, messages_sent = messages.len(), total_length = total_length, "Command completed successfully");
+        }
+    }
+}
+
+/// Extract a likely character name from a Discord nickname (e.g. "Name-Realm", "Name (Guild)")
+fn character_name_from_nick(nick: &str) -> Option<String> {
+    let name = nick
+        .split(|c: char| c == '-' || c == '(' || c == '[' || c == '|' || c == '/' || c.is_whitespace())
+        .next()?
+        .trim();
+
+    if name.is_empty() {
+        None
+    } else {
+        Some(name.to_string())
     }
 }
 
@@ -66,12 +219,10 @@ impl EventHandler for Handler {
         info!(bot_name = %ready.user.name, "Discord bot connected and ready");
 
         let commands = Command::set_global_application_commands(&ctx.http, |commands| {
+            for meta in commands::COMMANDS {
+                commands.create_application_command(|command| commands::register_command(command, meta));
+            }
             commands
-                .create_application_command(|command| commands::guilds_command(command))
-                .create_application_command(|command| commands::rank_command(command))
-                .create_application_command(|command| commands::about_us_command(command))
-                .create_application_command(|command| commands::rules_command(command))
-                .create_application_command(|command| commands::help_command(command))
         })
         .await;
 
@@ -89,137 +240,188 @@ impl EventHandler for Handler {
     }
 
     async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
+        if let Interaction::Autocomplete(autocomplete) = &interaction {
+            let choices = commands::autocomplete_choices(autocomplete);
+            if let Err(why) = autocomplete
+                .create_autocomplete_response(&ctx.http, |response| {
+                    for choice in &choices {
+                        response.add_string_choice(choice, choice);
+                    }
+                    response
+                })
+                .await
+            {
+                warn!(command = %autocomplete.data.name, error = %why, "Cannot respond to autocomplete");
+            }
+            return;
+        }
+
         if let Interaction::ApplicationCommand(command) = interaction {
             let command_name = &command.data.name;
             let user_id = command.user.id;
 
+            let locale = commands::response_locale(&command, &self.config);
+
             crate::log_discord_command!(command_name, user_id.0);
-            
-            // For simple commands, respond immediately
-            let content = match command_name.as_str() {
-                "about_us" => commands::handle_about_us_command().await,
-                "rules" => commands::handle_rules_command(&self.config).await,
-                "help" => commands::handle_help_command().await,
-                _ => {
-                    // For complex commands that might take time, defer the response
+
+            let total = self.command_stats.record(command_name);
+            if total.is_multiple_of(command_stats::SUMMARY_EVERY) {
+                info!(total, usage = %self.command_stats.summary(), "Command usage summary");
+            }
+
+            if let Some(remaining) = self.cooldowns.check(user_id, command_name) {
+                let content = locale::t_args(
+                    "cooldown.wait",
+                    locale,
+                    &[("secs", &cooldown::remaining_secs(remaining)), ("command", command_name)],
+                );
+                if let Err(why) = command
+                    .create_interaction_response(&ctx.http, |response| {
+                        response
+                            .kind(InteractionResponseType::ChannelMessageWithSource)
+                            .interaction_response_data(|message| message.content(content).ephemeral(true))
+                    })
+                    .await
+                {
+                    error!(command = %command_name, error = %why, "Cannot respond to slash command");
+                }
+                info!(command = %command_name, user = user_id.0, "Command rejected: on cooldown");
+                return;
+            }
+
+            let Some(meta) = commands::find_command(command_name) else {
+                warn!(command = %command_name, "Unknown command received");
+                self.respond_text(&ctx, &command, "❓ Unknown command".to_string()).await;
+                return;
+            };
+
+            use commands::CommandHandler as H;
+            match meta.handler {
+                // Quick commands are answered right away
+                H::ClassDistribution => {
+                    let response = commands::handle_classdistribution_command(&command, &self.database).await;
+                    self.respond_embed(&ctx, &command, response).await;
+                }
+                H::Help => {
+                    let embed = commands::handle_help_command(locale).await;
+                    self.respond_embed(&ctx, &command, Ok(embed)).await;
+                }
+                H::AboutUs => self.respond_text(&ctx, &command, commands::handle_about_us_command().await).await,
+                H::Rules => self.respond_text(&ctx, &command, commands::handle_rules_command(&self.config).await).await,
+                H::LastUpdated => {
+                    let content = commands::handle_lastupdated_command(&self.database, locale).await;
+                    self.respond_text(&ctx, &command, content).await;
+                }
+                H::Whois => {
+                    let content = commands::handle_whois_command(&command, &self.database).await;
+                    self.respond_text(&ctx, &command, content).await;
+                }
+                H::Search => {
+                    let content = commands::handle_search_command(&command, &self.database).await;
+                    self.respond_text(&ctx, &command, content).await;
+                }
+                H::ClassTop => self.respond_text(&ctx, &command, commands::handle_classtop_command(&self.database).await).await,
+                H::TrackedGuilds => {
+                    let content = commands::handle_trackedguilds_command(&self.database).await;
+                    self.respond_text(&ctx, &command, content).await;
+                }
+
+                // Commands that might take time defer the response and answer in follow-ups
+                H::Standings => {
+                    if !self.defer(&ctx, &command).await {
+                        return;
+                    }
+                    let response = commands::handle_standings_command(&command, &self.config, &self.database, &self.raider_io, locale).await;
                     if let Err(why) = command
-                        .create_interaction_response(&ctx.http, |response| {
-                            response
-                                .kind(InteractionResponseType::DeferredChannelMessageWithSource)
+                        .create_followup_message(&ctx.http, |message| match response {
+                            Ok(embed) => message.add_embed(embed),
+                            Err(content) => message.content(content),
                         })
                         .await
                     {
-                        error!(command = %command_name, error = %why, "Failed to defer response");
+                        error!(command = %command_name, error = %why, "Failed to send follow-up");
+                    } else {
+                        info!(command = %command_name, user = user_id.0, "Command completed successfully");
+                    }
+                }
+                H::Guilds => {
+                    if !self.defer(&ctx, &command).await {
                         return;
                     }
-
-                    info!("Executing command: {}", command_name);
-
-                    match command_name.as_str() {
-                        "guilds" => {
-                            info!("Executing guilds command...");
-                            let content = commands::handle_guilds_command(&command, &self.config).await;
-                            
-                            // Send follow-up response
-                            if let Err(why) = command
-                                .create_followup_message(&ctx.http, |response| {
-                                    response.content(&content)
-                                })
-                                .await
-                            {
-                                error!(command = %command_name, error = %why, "Failed to send follow-up");
-                            } else {
-                                info!(command = %command_name, user = user_id.0, response_length = content.len(), "Command completed successfully");
-                            }
-                        },
-                        "rank" => {
-                            let messages = commands::handle_rank_command_multi(&command, &self.database).await;
-                            
-                            // Send first message as follow-up
-                            if let Some(first_message) = messages.first() {
-                                if let Err(why) = command
-                                    .create_followup_message(&ctx.http, |response| {
-                                        response.content(first_message)
-                                    })
-                                    .await
-                                {
-                                    error!(command = %command_name, error = %why, "Failed to send follow-up");
-                                    return;
-                                }
-                            }
-                            
-                            // Send additional messages as separate follow-ups
-                            for (i, message) in messages.iter().skip(1).enumerate() {
-                                if let Err(why) = command
-                                    .create_followup_message(&ctx.http, |response| {
-                                        response.content(message)
-                                    })
-                                    .await
-                                {
-                                    error!(command = %command_name, message_index = i + 2, error = %why, "Failed to send additional follow-up message");
-                                } else {
-                                    info!(command = %command_name, message_index = i + 2, "Additional follow-up message sent successfully");
-                                }
-                            }
-                            
-                            let total_length: usize = messages.iter().map(|m| m.len()).sum();
-                            info!(command = %command_name, user = user_id.0, messages_sent = messages.len(), total_length = total_length, "Command completed successfully");
-                        },
-                        _ => {
-                            warn!(command = %command_name, "Unknown command received");
-                            let content = "❓ Unknown command".to_string();
-                            
-                            // Send follow-up response
-                            if let Err(why) = command
-                                .create_followup_message(&ctx.http, |response| {
-                                    response.content(&content)
-                                })
-                                .await
-                            {
-                                error!(command = %command_name, error = %why, "Failed to send follow-up");
-                            } else {
-                                info!(command = %command_name, user = user_id.0, response_length = content.len(), "Command completed successfully");
-                            }
+                    // /guilds can take a while under rate limiting; post a placeholder that
+                    // the result replaces
+                    let mut guilds = self.database.get_all_guilds().await.unwrap_or_default();
+                    self.config.data.retain_allowed_realms(&mut guilds);
+                    let content = locale::t_args("guilds.fetching", locale, &[("count", &guilds.len())]);
+                    let placeholder = match InteractionResponder::new(&ctx.http, &command).follow_up(&content).await {
+                        Ok(message_id) => Some(message_id),
+                        Err(why) => {
+                            warn!(command = %command_name, error = %why, "Failed to send progress message");
+                            None
                         }
                     };
-                    return;
-                }
-            };
 
-            // Immediate response for simple commands
-            if let Err(why) = command
-                .create_interaction_response(&ctx.http, |response| {
-                    response
-                        .kind(InteractionResponseType::ChannelMessageWithSource)
-                        .interaction_response_data(|message| message.content(&content))
-                })
-                .await
-            {
-                error!(command = %command_name, error = %why, "Cannot respond to slash command");
-            } else {
-                info!(command = %command_name, user = user_id.0, response_length = content.len(), "Command completed successfully");
+                    let handler = commands::handle_guilds_command(&command, &self.config, &self.database, &self.raider_io, locale);
+                    let messages = commands::with_command_timeout(handler, commands::COMMAND_TIMEOUT_SECS)
+                        .await
+                        .unwrap_or_else(|| timed_out(command_name, locale));
+                    self.send_messages(&ctx, &command, placeholder, &messages).await;
+                }
+                H::Rank => {
+                    let handler = commands::handle_rank_command_multi(&command, &self.database, locale);
+                    self.respond_deferred(&ctx, &command, async {
+                        commands::with_command_timeout(handler, commands::COMMAND_TIMEOUT_SECS)
+                            .await
+                            .unwrap_or_else(|| timed_out(command_name, locale))
+                    })
+                    .await;
+                }
+                H::TopGuild => {
+                    let handler = commands::handle_topguild_command(&command, &self.config, &self.database, &self.raider_io, locale);
+                    self.respond_deferred(&ctx, &command, async { vec![handler.await] }).await;
+                }
+                H::Player => {
+                    let handler = commands::handle_player_command(&command, &self.database, &self.raider_io);
+                    self.respond_deferred(&ctx, &command, async { vec![handler.await] }).await;
+                }
+                H::RefreshGuild => {
+                    let handler = commands::handle_refreshguild_command(&command, &self.config, &self.database, &self.raider_io);
+                    self.respond_deferred(&ctx, &command, async { vec![handler.await] }).await;
+                }
+                H::Import => {
+                    let handler = commands::handle_import_command(&command, &self.config, &self.database);
+                    self.respond_deferred(&ctx, &command, async { vec![handler.await] }).await;
+                }
+                H::Maintenance => {
+                    let handler = commands::handle_maintenance_command(&command, &self.config, &self.database);
+                    self.respond_deferred(&ctx, &command, async { vec![handler.await] }).await;
+                }
             }
         }
     }
 
     async fn guild_member_addition(&self, ctx: Context, mut new_member: Member) {
         // Check if auto-role assignment is enabled
-        if !self.config.discord.auto_role_enabled {
+        if !self.config.discord.auto_role_enabled || !self.member_events {
             return;
         }
 
-        // Get the role ID from config
-        let Some(role_id_str) = &self.config.discord.auto_role_id else {
-            warn!("Auto-role is enabled but no role ID configured");
-            return;
+        // Prefer a guild-specific role when the nickname matches a tracked character
+        let guild_role_id = if self.config.discord.auto_role_guild_matching {
+            self.resolve_guild_role(&new_member).await
+        } else {
+            None
         };
 
-        // Parse role ID
-        let role_id = match role_id_str.parse::<u64>() {
-            Ok(id) => RoleId(id),
-            Err(e) => {
-                error!("Failed to parse auto-role ID '{}': {}", role_id_str, e);
-                return;
+        let role_id = match guild_role_id {
+            Some(role_id) => role_id,
+            None => {
+                // Get the role ID from config
+                let Some(role_id) = self.config.discord.auto_role_id else {
+                    warn!("Auto-role is enabled but no role ID configured");
+                    return;
+                };
+                RoleId(role_id.value())
             }
         };
 
@@ -275,14 +477,15 @@ async fn main() -> Result<()> {
     info!("WoW Guild Bot starting up...");
 
     // Initialize database (migrations will populate guild data automatically)
-    let database = Database::new(&config.database.url).await?;
+    let database = Database::new(&config.database).await?;
 
     let args: Vec<String> = env::args().collect();
     
     // Check if user wants to run the parser
     if args.len() > 1 && args[1] == "parse" {
-        info!("Running parser to generate members.json...");
-        match parser::generate_members_data().await {
+        let options = parser::ParseOptions::from_args(&args[2..]);
+        info!(dry_run = options.dry_run, resume = options.resume, guilds = ?options.guilds, "Running parser to generate members.json...");
+        match parser::generate_members_data(options).await {
             Ok(()) => {
                 info!("Parser completed successfully!");
                 Ok(())
@@ -294,7 +497,7 @@ async fn main() -> Result<()> {
         }
     } else if args.len() > 1 && args[1] == "db-status" {
         // Show database status and migrations
-        show_database_status(&database).await?;
+        show_database_status(&database, args[2..].iter().any(|arg| arg == "--json")).await?;
         Ok(())
     } else {
         // Run Discord bot
@@ -302,25 +505,31 @@ async fn main() -> Result<()> {
     }
 }
 
-/// Show database status and migrations
-async fn show_database_status(database: &Database) -> Result<()> {
+/// Show database status and migrations; with `json` print a single JSON object to stdout
+async fn show_database_status(database: &Database, json: bool) -> Result<()> {
+    let status = database.status().await?;
+
+    if json {
+        let output = serde_json::to_string(&status)
+            .map_err(|e| BotError::application(format!("Failed to serialize database status: {}", e)))?;
+        println!("{}", output);
+        return Ok(());
+    }
+
     info!("=== Database Status ===");
+    info!("📊 Guilds: {}", status.guild_count);
+    info!("👥 Members: {}", status.member_count);
+    match status.last_updated {
+        Some(updated) => info!("🕒 Last updated: {}", updated.format("%Y-%m-%d %H:%M:%S UTC")),
+        None => info!("🕒 Last updated: never"),
+    }
     
-    // Get stats
-    let (guild_count, member_count) = database.get_stats().await?;
-    info!("📊 Guilds: {}", guild_count);
-    info!("👥 Members: {}", member_count);
-    
-    info!("\n=== Executed Migrations ===");
-    match database.get_migrations().await {
-        Ok(migrations) => {
-            for (name, executed_at) in migrations {
-                info!("✅ {} (executed: {})", name, executed_at.format("%Y-%m-%d %H:%M:%S UTC"));
-            }
-        },
-        Err(e) => {
-            warn!("Could not fetch migrations: {}", e);
-        }
+    info!("\n=== Migrations ===");
+    for migration in &status.migrations {
+        info!("✅ {} (executed: {})", migration.name, migration.executed_at.format("%Y-%m-%d %H:%M:%S UTC"));
+    }
+    for name in &status.pending_migrations {
+        info!("⏳ {} (pending)", name);
     }
     
     info!("\n=== Database Tables ===");
@@ -336,12 +545,34 @@ async fn show_database_status(database: &Database) -> Result<()> {
 async fn run_discord_bot(config: AppConfig, database: Database) -> Result<()> {
     info!("Starting Discord bot...");
 
-    let intents = GatewayIntents::GUILD_MESSAGES 
-        | GatewayIntents::DIRECT_MESSAGES 
-        | GatewayIntents::GUILD_MEMBERS;  // Enable after setting up intents in Discord Portal
+    let intents = config.discord.gateway_intents();
+    if !intents.contains(GatewayIntents::GUILD_MEMBERS) && config.discord.auto_role_enabled {
+        warn!("GUILD_MEMBERS intent not requested, auto-role assignment on join is disabled");
+    }
+    info!(intents = ?intents, "Using Discord gateway intents");
+
+    if let Some(scheduler) = parser::RefreshScheduler::from_config(&config) {
+        info!(interval_hours = config.data.refresh_interval_hours, "Scheduled member data refresh enabled");
+        scheduler.spawn();
+    }
+
+    let raider_io = RaiderIOClient::from_config(&config)?;
+    match start_client(&config, &database, &raider_io, intents).await {
+        Err(e) => match intents_without_unapproved(intents, &e) {
+            Some(intents) => {
+                warn!("GUILD_MEMBERS intent not approved; auto-role disabled. Enable the Server Members Intent in the Discord developer portal to restore it");
+                start_client(&config, &database, &raider_io, intents).await
+            }
+            None => Err(e),
+        },
+        ok => ok,
+    }
+}
 
+/// Connect with the given intents and run the event loop until it stops
+async fn start_client(config: &AppConfig, database: &Database, raider_io: &RaiderIOClient, intents: GatewayIntents) -> Result<()> {
     let mut client = Client::builder(&config.discord.token, intents)
-        .event_handler(Handler::new(config, database))
+        .event_handler(Handler::new(config.clone(), database.clone(), raider_io.clone(), intents))
         .await
         .map_err(|e| BotError::Discord(e))?;
 
@@ -1,30 +1,115 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
-use crate::config::AppConfig;
+use crate::config::{AppConfig, DataConfig, Region};
 use crate::database::{Database, DbMember};
-use crate::error::Result;
+use crate::error::{BotError, Result};
 use serde_json;
 use crate::raider_io::{RaiderIOClient, PlayerData};
-use crate::types::{PlayerName, RealmName, GuildName, MythicPlusScore};
+use crate::types::{PlayerName, RealmName, GuildName, GuildUrl, MythicPlusScore};
 use futures::stream::{self, StreamExt};
-use tracing::{info, error, warn};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+use tracing::{debug, info, error, warn};
 
-pub async fn generate_members_data() -> Result<()> {
+/// Command-line options for the `parse` subcommand
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParseOptions {
+    /// Only fetch guild rosters and report the cost of a full run
+    pub dry_run: bool,
+    /// Keep characters already stored in `members_tmp` and fetch only the rest
+    pub resume: bool,
+    /// Run even when fetching many characters without an API key
+    pub allow_unauthenticated: bool,
+    /// Only refresh these guilds (`--guilds "A,B"`), upserting their members in place
+    pub guilds: Vec<String>,
+}
+
+impl ParseOptions {
+    /// Parse options from the arguments following `parse`
+    pub fn from_args(args: &[String]) -> Self {
+        let guilds = args
+            .iter()
+            .position(|arg| arg == "--guilds")
+            .and_then(|i| args.get(i + 1))
+            .map(|list| list.split(',').map(str::trim).filter(|name| !name.is_empty()).map(str::to_string).collect())
+            .unwrap_or_default();
+
+        Self {
+            dry_run: args.iter().any(|arg| arg == "--dry-run"),
+            resume: args.iter().any(|arg| arg == "--resume"),
+            allow_unauthenticated: args.iter().any(|arg| arg == "--allow-unauthenticated"),
+            guilds,
+        }
+    }
+}
+
+/// Keep the guilds named in `names`, compared case-insensitively
+fn retain_named_guilds(guilds: &mut Vec<GuildUrl>, names: &[String]) {
+    let names: HashSet<String> = names.iter().map(|name| name.to_lowercase()).collect();
+    guilds.retain(|guild| names.contains(&guild.name.as_str().to_lowercase()));
+}
+
+/// Refuse a parser run that would fetch more than `limit` characters without an API key;
+/// raider.io throttles unauthenticated clients so heavily that such runs take hours
+fn check_unauthenticated_run(api_key: Option<&str>, characters: usize, limit: usize, allow: bool) -> std::result::Result<(), String> {
+    let has_key = api_key.is_some_and(|key| !key.trim().is_empty());
+    if has_key || allow || limit == 0 || characters <= limit {
+        return Ok(());
+    }
+    Err(format!(
+        "{} characters to fetch without a raider.io API key (limit {}). Set RAIDERIO_API_KEY, \
+         or pass --allow-unauthenticated to run anyway.",
+        characters, limit
+    ))
+}
+
+/// Run the parser. With `dry_run` only guild rosters are fetched and the cost of a
+/// full run is reported; no player requests are made and `members_tmp` is untouched.
+/// With `resume` characters already stored in `members_tmp` by a previous, interrupted
+/// run are skipped. Without an API key, runs larger than
+/// `raider_io.unauthenticated_character_limit` are refused unless `allow_unauthenticated` is set.
+/// With `guilds`, or with `data.realm_allowlist` set, only those guilds are refreshed, in
+/// place via [`refresh_guild`], so other guilds' members are left alone.
+pub async fn generate_members_data(options: ParseOptions) -> Result<()> {
+    let ParseOptions { dry_run, resume, allow_unauthenticated, guilds } = options;
     let config = AppConfig::load()?;
     info!("Starting member data generation with database workflow...");
     
     let client = RaiderIOClient::from_config(&config)?;
     let mut data_dict: HashMap<(String, String), PlayerData> = HashMap::new();
+    // Region of the guild each character was found in, for the profile lookup
+    let mut player_regions: HashMap<(String, String), Region> = HashMap::new();
     
     // Initialize database
-    let database = Database::new(&config.database.url).await?;
-    
-    // Clear temporary table for fresh start
-    database.clear_temp_members().await?;
-    info!("Cleared temporary members table");
+    let database = Database::new(&config.database).await?;
     
     // Get guild URLs from database instead of file
-    let guild_urls = database.get_all_guilds().await?.into_iter().map(|url| url.to_query_string()).collect::<Vec<_>>();
+    let mut guild_urls = database.get_all_guilds().await?;
+    config.data.retain_allowed_realms(&mut guild_urls);
+    if !guilds.is_empty() {
+        retain_named_guilds(&mut guild_urls, &guilds);
+        if guild_urls.is_empty() {
+            return Err(BotError::invalid_input(format!("No tracked guild matches --guilds \"{}\"", guilds.join(","))));
+        }
+    }
+
+    // A targeted run upserts into `members` directly; the tmp/swap flow would drop
+    // every guild that wasn't refreshed
+    if refreshes_in_place(&config.data, &guilds) && !dry_run {
+        refresh_guilds_in_place(&config, &database, &client, &guild_urls).await?;
+        return Ok(());
+    }
+
+    // Clear temporary table for fresh start
+    if !dry_run && !resume {
+        database.clear_temp_members().await?;
+        info!("Cleared temporary members table");
+    }
+
     info!("Processing {} guilds from database...", guild_urls.len());
     
     // Process guilds to get member lists
@@ -39,36 +124,16 @@ pub async fn generate_members_data() -> Result<()> {
             url
         );
         
-        if let Ok(guild_data) = fetch_guild_members(&client, &url).await {
+        if let Ok(guild_data) = client.fetch_guild_roster(url).await {
             if let Some(members) = guild_data.get("members").and_then(|m| m.as_array()) {
                 let guild_name = guild_data.get("name").and_then(|n| n.as_str()).unwrap_or("Unknown");
                 
-                for member in members {
-                    if let Some(character) = member.get("character") {
-                        let realm = character.get("realm").and_then(|r| r.as_str()).unwrap_or("Unknown").to_string();
-                        let name = character.get("name").and_then(|n| n.as_str()).unwrap_or("Unknown").to_string();
-                        let class = character.get("class").and_then(|c| c.as_str()).map(|s| s.to_string());
-                        let active_spec_name = character.get("active_spec_name").and_then(|a| a.as_str()).map(|s| s.to_string());
-                        
-                        if !name.is_empty() && name != "Unknown" {
-                            let player_key = (realm.clone(), name.clone());
-                            data_dict.insert(player_key, PlayerData {
-                                name: PlayerName::from(name),
-                                realm: RealmName::from(realm),
-                                guild: Some(GuildName::from(guild_name.to_string())),
-                                class,
-                                active_spec_name,
-                                rio_all: MythicPlusScore::zero(),
-                                rio_dps: MythicPlusScore::zero(),
-                                rio_healer: MythicPlusScore::zero(),
-                                rio_tank: MythicPlusScore::zero(),
-                                spec_0: MythicPlusScore::zero(),
-                                spec_1: MythicPlusScore::zero(),
-                                spec_2: MythicPlusScore::zero(),
-                                spec_3: MythicPlusScore::zero(),
-                            });
-                        }
+                for player in roster_players(&guild_data) {
+                    let key = member_key(&player.realm, &player.name);
+                    if let Some(region) = url.region {
+                        player_regions.insert(key.clone(), region);
                     }
+                    data_dict.insert(key, player);
                 }
                 info!(
                     guild = guild_name,
@@ -90,25 +155,74 @@ pub async fn generate_members_data() -> Result<()> {
     
     info!("Collected {} unique players from guild rosters", data_dict.len());
     crate::log_data_processing!("collecting players from rosters", data_dict.len(), data_dict.len());
+
+    if dry_run {
+        let estimate = estimate_fetch_duration(data_dict.len(), config.rate_limiting.requests_per_second);
+        println!("Dry run: {} guilds, {} unique characters would be queried", guild_urls.len(), data_dict.len());
+        println!(
+            "Estimated fetch time at {} requests/second: {}",
+            config.rate_limiting.requests_per_second,
+            format_duration(estimate)
+        );
+        info!(
+            guilds = guild_urls.len(),
+            characters = data_dict.len(),
+            estimated_secs = estimate.as_secs(),
+            "Dry run complete, no player data fetched"
+        );
+        return Ok(());
+    }
     
+    check_unauthenticated_run(
+        config.raider_io.api_key.as_deref(),
+        data_dict.len(),
+        config.raider_io.unauthenticated_character_limit,
+        allow_unauthenticated,
+    )
+    .map_err(BotError::invalid_input)?;
+
     // Database will be used instead of JSON file
     info!("Storing member data in temporary database table...");
     
     // Fetch RIO data for all players with proper rate limiting and incremental writing
-    let players: Vec<_> = data_dict.keys().cloned().collect();
+    let all_players: Vec<_> = data_dict.keys().cloned().collect();
+    let players = if resume {
+        let stored = database.temp_member_keys().await?;
+        let pending = pending_players(&all_players, &stored);
+        info!(
+            already_stored = all_players.len() - pending.len(),
+            remaining = pending.len(),
+            "Resuming previous parser run"
+        );
+        pending
+    } else {
+        all_players.clone()
+    };
     let total_players = players.len();
     let mut successful_fetches = 0;
     let mut failed_fetches = 0;
     let mut final_players = Vec::new();
     let mut players_written = 0;
+    // Characters deliberately left out of members_tmp, so they don't count as missing
+    let mut below_min_rio: HashSet<(String, String)> = HashSet::new();
     
     info!("Starting RIO data fetch for {} players at 10 requests/second (writing every 100 players)...", total_players);
     crate::log_data_processing!("starting RIO data fetch", 0, total_players);
+
+    let realm_limiter = RealmLimiter::new(config.rate_limiting.per_realm_concurrency);
+    if let Some(limit) = config.rate_limiting.per_realm_concurrency {
+        info!(per_realm_concurrency = limit, "Pacing player requests per realm");
+    }
     
     let mut results = stream::iter(players.into_iter().enumerate().map(|(i, (realm, name))| {
         let client = &client;
         let data_dict = &data_dict;
+        let player_regions = &player_regions;
+        let realm_limiter = &realm_limiter;
         async move {
+            // Hold the realm's permit for the whole fetch, including retries
+            let _realm_permit = realm_limiter.acquire(&realm).await;
+
             // Rate limiting: 10 requests per second = 100ms per request
             if i > 0 {
                 tokio::time::sleep(std::time::Duration::from_millis(100)).await;
@@ -117,119 +231,11 @@ pub async fn generate_members_data() -> Result<()> {
             // Log concise progress for each player
             println!("[{}/{}] Fetching RIO data for {}-{}", i + 1, total_players, name, realm);
             
-            let guild = data_dict.get(&(realm.clone(), name.clone()))
-                .and_then(|p| p.guild.clone());
-                
-            // Retry logic for rate limiting
-            let mut attempts = 0;
-            let max_attempts = 10;
-            
-            loop {
-                match client.fetch_player_data(&RealmName::from(realm.clone()), &PlayerName::from(name.clone()), guild.clone()).await {
-                    Ok(Some(player_data)) => {
-                        println!("[{}/{}] ✓ {}-{} (RIO: {:.1})", i + 1, total_players, player_data.name, player_data.realm, player_data.rio_all.value());
-                        if (i + 1) % 100 == 0 {
-                            crate::log_data_processing!("fetching player RIO data", i + 1, total_players);
-                        }
-                        return Some((player_data, true, i));
-                    }
-                    Ok(None) => {
-                        println!("[{}/{}] - {}-{} (No RIO data)", i + 1, total_players, name, realm);
-                        if (i + 1) % 500 == 0 {
-                            crate::log_data_processing!("fetching player RIO data (with missing data)", i + 1, total_players);
-                        }
-                        return Some((PlayerData {
-                            name: PlayerName::from(name.clone()),
-                            realm: RealmName::from(realm.clone()), 
-                            guild: guild.clone(),
-                            class: data_dict.get(&(realm.clone(), name.clone())).and_then(|p| p.class.clone()),
-                            active_spec_name: data_dict.get(&(realm.clone(), name.clone())).and_then(|p| p.active_spec_name.clone()),
-                            rio_all: MythicPlusScore::zero(),
-                            rio_dps: MythicPlusScore::zero(),
-                            rio_healer: MythicPlusScore::zero(),
-                            rio_tank: MythicPlusScore::zero(),
-                            spec_0: MythicPlusScore::zero(),
-                            spec_1: MythicPlusScore::zero(),
-                            spec_2: MythicPlusScore::zero(),
-                            spec_3: MythicPlusScore::zero(),
-                        }, false, i));
-                    }
-                    Err(e) => {
-                        attempts += 1;
-                        let error_msg = e.to_string();
-                        
-                        // Check if it's a rate limit error
-                        if error_msg.contains("429") || error_msg.contains("rate") || error_msg.contains("limit") {
-                            if attempts < max_attempts {
-                                warn!(
-                                    player = %name,
-                                    realm = %realm,
-                                    attempt = attempts,
-                                    max_attempts = max_attempts,
-                                    progress = i + 1,
-                                    total = total_players,
-                                    "Rate limited, waiting 10 seconds before retry"
-                                );
-                                crate::log_rate_limit!("raider.io", 10000);
-                                
-                                println!("[{}/{}] Rate limited on {}-{}, waiting 10 seconds (attempt {}/{})", i + 1, total_players, name, realm, attempts + 1, max_attempts);
-                                for j in 1..=10 {
-                                    tokio::time::sleep(std::time::Duration::from_secs(1)).await;
-                                    if j % 2 == 0 {
-                                        println!("  [Rate Limited] {}s remaining...", 10 - j);
-                                    }
-                                }
-                                continue;
-                            }
-                        }
-                        
-                        // Check if it's a server error (5xx)
-                        if error_msg.contains("500") || error_msg.contains("502") || error_msg.contains("503") {
-                            if attempts < max_attempts {
-                                warn!(
-                                    player = %name,
-                                    realm = %realm,
-                                    attempt = attempts,
-                                    max_attempts = max_attempts,
-                                    progress = i + 1,
-                                    total = total_players,
-                                    error = %error_msg,
-                                    "Server error, retrying in 10 seconds"
-                                );
-                                tokio::time::sleep(std::time::Duration::from_secs(10)).await;
-                                continue;
-                            }
-                        }
-                        
-                        println!("[{}/{}] ✗ {}-{} (Failed: {})", i + 1, total_players, name, realm, e);
-                        error!(
-                            player = %name,
-                            realm = %realm,
-                            attempts = attempts,
-                            progress = i + 1,
-                            total = total_players,
-                            error = %e,
-                            "Failed to fetch RIO data after max attempts"
-                        );
-                        
-                        return Some((PlayerData {
-                            name: PlayerName::from(name.clone()),
-                            realm: RealmName::from(realm.clone()),
-                            guild: guild.clone(),
-                            class: data_dict.get(&(realm.clone(), name.clone())).and_then(|p| p.class.clone()),
-                            active_spec_name: data_dict.get(&(realm.clone(), name.clone())).and_then(|p| p.active_spec_name.clone()),
-                            rio_all: MythicPlusScore::zero(),
-                            rio_dps: MythicPlusScore::zero(),
-                            rio_healer: MythicPlusScore::zero(),
-                            rio_tank: MythicPlusScore::zero(),
-                            spec_0: MythicPlusScore::zero(),
-                            spec_1: MythicPlusScore::zero(),
-                            spec_2: MythicPlusScore::zero(),
-                            spec_3: MythicPlusScore::zero(),
-                        }, false, i));
-                    }
-                }
-            }
+            let key = (realm.clone(), name.clone());
+            let roster_entry = data_dict.get(&key);
+            let region = player_regions.get(&key).copied();
+            let (player, success) = fetch_player_rio(client, &realm, &name, roster_entry, region, i, total_players).await;
+            Some((player, success, i))
         }
     }))
     .buffer_unordered(5); // 5 concurrent requests at 100ms intervals for 10 req/sec
@@ -257,32 +263,14 @@ pub async fn generate_members_data() -> Result<()> {
                 crate::log_data_processing!("writing to database", final_players.len(), total_players);
                 
                 // Convert and store batch in temporary table
-                for player in final_players.iter().skip(players_written) {
-                    let db_member = DbMember {
-                        id: 0, // Will be auto-generated
-                        name: player.name.to_string(),
-                        realm: player.realm.to_string(),
-                        guild_name: player.guild.as_ref().map(|g| g.to_string()),
-                        guild_realm: Some(player.realm.to_string()), // Use player's realm as guild realm
-                        class: player.class.clone(),
-                        spec: player.active_spec_name.clone(),
-                        rio_score: Some(player.rio_all.value() as f64), // Legacy field - kept for compatibility
-                        ilvl: None, // Could be added later from character data
-                        // Complete RIO data matching PlayerData structure
-                        rio_all: player.rio_all.value() as f64,
-                        rio_dps: player.rio_dps.value() as f64,
-                        rio_healer: player.rio_healer.value() as f64,
-                        rio_tank: player.rio_tank.value() as f64,
-                        spec_0: player.spec_0.value() as f64,
-                        spec_1: player.spec_1.value() as f64,
-                        spec_2: player.spec_2.value() as f64,
-                        spec_3: player.spec_3.value() as f64,
-                        updated_at: chrono::Utc::now(),
-                    };
-                    
-                    if let Err(e) = database.insert_temp_member(&db_member).await {
-                        error!("Failed to insert member {}-{}: {}", player.name, player.realm, e);
-                    }
+                let (batch, skipped) = storable_members(&final_players[players_written..], &config.data);
+                if !skipped.is_empty() {
+                    info!(skipped = skipped.len(), min_stored_rio = config.data.min_stored_rio, "Skipping players below min_stored_rio");
+                }
+                below_min_rio.extend(skipped);
+
+                if let Err(e) = database.insert_temp_members_batch(&batch).await {
+                    error!("Failed to insert batch of {} members: {}", batch.len(), e);
                 }
                 
                 players_written = final_players.len();
@@ -296,6 +284,19 @@ pub async fn generate_members_data() -> Result<()> {
         }
     }
     
+    // Only promote the temporary table once every collected character has been stored
+    let missing = pending_players(&all_players, &database.temp_member_keys().await?)
+        .iter()
+        .filter(|(realm, name)| !below_min_rio.contains(&member_key(realm, name)))
+        .count();
+    if missing > 0 {
+        error!(missing = missing, "Not all characters were stored, keeping the current members table");
+        return Err(BotError::application(format!(
+            "{} characters are missing from members_tmp; re-run with `parse --resume` to finish",
+            missing
+        )));
+    }
+
     // Swap temporary table with active members table
     info!("Swapping temporary table with active members table...");
     database.swap_members_tables().await?;
@@ -330,14 +331,575 @@ pub async fn generate_members_data() -> Result<()> {
     Ok(())
 }
 
-async fn fetch_guild_members(client: &RaiderIOClient, guild_url: &str) -> Result<serde_json::Value> {
-    let url = format!("http://raider.io/api/v1/guilds/profile?region=eu&{}&fields=members", guild_url);
-    // Since add_api_key is private, we'll handle the API key ourselves
-    // TODO: We should create a public method for this or use a different approach
-    
-    let http_client = reqwest::Client::new();
-    let response = http_client.get(&url).send().await?;
-    let guild_data: serde_json::Value = response.json().await.map_err(|e| crate::error::BotError::Application(format!("Failed to parse guild JSON: {}", e)))?;
-    
-    Ok(guild_data)
-}
\ No newline at end of file
+/// Fetch one character's RIO data, returning it and whether the fetch succeeded.
+/// Rate limits, server errors and network failures are retried inside the client, so
+/// an error here is final; the character is kept from its roster entry with zero scores.
+async fn fetch_player_rio(
+    client: &RaiderIOClient,
+    realm: &str,
+    name: &str,
+    roster_entry: Option<&PlayerData>,
+    region: Option<Region>,
+    i: usize,
+    total_players: usize,
+) -> (PlayerData, bool) {
+    let guild = roster_entry.and_then(|p| p.guild.clone());
+    match client.fetch_player_data(&RealmName::from(realm), &PlayerName::from(name), guild, region).await {
+        Ok(Some(mut player_data)) => {
+            // The roster knows the guild's realm even when the profile doesn't
+            if let Some(guild_realm) = roster_entry.and_then(|p| p.guild_realm.clone()) {
+                player_data.guild_realm = Some(guild_realm);
+            }
+            println!("[{}/{}] ✓ {}-{} (RIO: {:.1})", i + 1, total_players, player_data.name, player_data.realm, player_data.rio_all.value());
+            if (i + 1).is_multiple_of(100) {
+                crate::log_data_processing!("fetching player RIO data", i + 1, total_players);
+            }
+            (player_data, true)
+        }
+        Ok(None) => {
+            println!("[{}/{}] - {}-{} (No RIO data)", i + 1, total_players, name, realm);
+            if (i + 1).is_multiple_of(500) {
+                crate::log_data_processing!("fetching player RIO data (with missing data)", i + 1, total_players);
+            }
+            (unscored_player(realm, name, roster_entry), false)
+        }
+        Err(e) => {
+            println!("[{}/{}] ✗ {}-{} (Failed: {})", i + 1, total_players, name, realm, e);
+            error!(
+                player = %name,
+                realm = %realm,
+                progress = i + 1,
+                total = total_players,
+                error = %e,
+                "Failed to fetch RIO data"
+            );
+            (unscored_player(realm, name, roster_entry), false)
+        }
+    }
+}
+
+/// A character without RIO data: guild, class and spec from its roster entry, zero scores
+fn unscored_player(realm: &str, name: &str, roster_entry: Option<&PlayerData>) -> PlayerData {
+    PlayerData {
+        name: PlayerName::from(name),
+        realm: RealmName::from(realm),
+        guild: roster_entry.and_then(|p| p.guild.clone()),
+        guild_realm: roster_entry.and_then(|p| p.guild_realm.clone()),
+        class: roster_entry.and_then(|p| p.class.clone()),
+        active_spec_name: roster_entry.and_then(|p| p.active_spec_name.clone()),
+        ilvl: None,
+        rio_all: MythicPlusScore::zero(),
+        rio_dps: MythicPlusScore::zero(),
+        rio_healer: MythicPlusScore::zero(),
+        rio_tank: MythicPlusScore::zero(),
+        spec_0: MythicPlusScore::zero(),
+        spec_1: MythicPlusScore::zero(),
+        spec_2: MythicPlusScore::zero(),
+        spec_3: MythicPlusScore::zero(),
+        spec_0_name: None,
+        spec_1_name: None,
+        spec_2_name: None,
+        spec_3_name: None,
+    }
+}
+
+/// Periodically re-runs the parser inside the bot process so /rank stays fresh
+pub struct RefreshScheduler {
+    interval: Duration,
+    running: Arc<AtomicBool>,
+}
+
+impl RefreshScheduler {
+    /// Build a scheduler from `data.refresh_interval_hours`; `None` when disabled (0)
+    pub fn from_config(config: &AppConfig) -> Option<Self> {
+        match config.data.refresh_interval_hours {
+            0 => None,
+            hours => Some(Self {
+                interval: Duration::from_secs(hours * 60 * 60),
+                running: Arc::new(AtomicBool::new(false)),
+            }),
+        }
+    }
+
+    /// Spawn the scheduler loop. The first refresh happens one interval after startup,
+    /// and a tick is skipped while the previous run is still going.
+    pub fn spawn(self) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(self.interval);
+            ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
+            ticker.tick().await;
+
+            loop {
+                ticker.tick().await;
+
+                if self.running.swap(true, Ordering::SeqCst) {
+                    warn!("Previous scheduled member refresh still running, skipping this interval");
+                    continue;
+                }
+
+                let running = Arc::clone(&self.running);
+                tokio::spawn(async move {
+                    info!("Starting scheduled member data refresh");
+                    match generate_members_data(ParseOptions::default()).await {
+                        Ok(()) => info!("Scheduled member data refresh finished"),
+                        Err(e) => error!(error = %e, "Scheduled member data refresh failed"),
+                    }
+                    running.store(false, Ordering::SeqCst);
+                });
+            }
+        })
+    }
+}
+
+/// Whether a run only covers some guilds, so it must refresh them in place rather than
+/// rebuild `members` through `members_tmp`
+fn refreshes_in_place(data: &DataConfig, guilds: &[String]) -> bool {
+    !guilds.is_empty() || data.restricts_realms()
+}
+
+/// Refresh each of `guild_urls` in place, returning the number of characters stored
+async fn refresh_guilds_in_place(config: &AppConfig, database: &Database, client: &RaiderIOClient, guild_urls: &[GuildUrl]) -> Result<usize> {
+    let mut stored = 0;
+    for guild in guild_urls {
+        stored += refresh_guild(config, database, client, guild).await?;
+    }
+    info!(guilds = guild_urls.len(), stored = stored, "Targeted guild refresh complete");
+    Ok(stored)
+}
+
+/// Refresh a single guild in place: fetch its roster and each member's RIO scores and
+/// upsert them into `members`, bypassing the `members_tmp` swap so other guilds' rows are
+/// kept. Characters whose request fails or that have no raider.io profile keep their
+/// stored row. Returns the number stored.
+pub async fn refresh_guild(config: &AppConfig, database: &Database, client: &RaiderIOClient, guild: &GuildUrl) -> Result<usize> {
+    let guild_data = client.fetch_guild_roster(guild).await?;
+    let roster = roster_players(&guild_data);
+    if roster.is_empty() {
+        return Err(BotError::application(format!("No roster found for guild {}", guild)));
+    }
+
+    info!(guild = %guild, members_count = roster.len(), "Refreshing guild roster");
+
+    // Stored as by `DbMember::from`, so the prune below finds exactly this guild's rows
+    let stored_guild = roster.first().map(|member| {
+        let name = member.guild.as_ref().map(|g| g.to_string()).unwrap_or_default();
+        let realm = member.guild_realm.as_ref().unwrap_or(&member.realm).to_string();
+        (name, realm)
+    });
+    let roster_keys: HashSet<(String, String)> = roster.iter().map(|member| member_key(&member.realm, &member.name)).collect();
+
+    let mut stored = 0;
+    for (i, member) in roster.into_iter().enumerate() {
+        // Same pacing as the full parser: 10 requests per second
+        if i > 0 {
+            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        }
+
+        let player = match client.fetch_player_data(&member.realm, &member.name, member.guild.clone(), guild.region).await {
+            Ok(Some(player)) => PlayerData { guild_realm: member.guild_realm.clone().or(player.guild_realm), ..player },
+            Ok(None) => {
+                // Storing the roster stub would overwrite the stored scores with zeros
+                debug!(player = %member.name, realm = %member.realm, "No raider.io profile, keeping stored data");
+                continue;
+            }
+            Err(e) => {
+                warn!(player = %member.name, realm = %member.realm, error = %e, "Failed to refresh member, keeping stored data");
+                continue;
+            }
+        };
+
+        if !config.data.stores_rio(player.rio_all.value()) {
+            debug!(player = %player.name, realm = %player.realm, rio = player.rio_all.value(), "Below min_stored_rio, not storing");
+            continue;
+        }
+        database.upsert_member(&DbMember::from(&player)).await?;
+        stored += 1;
+    }
+
+    // Upserts never remove anyone, so drop this guild's characters that have left its
+    // roster and haven't been refreshed for a while
+    if let Some((guild_name, guild_realm)) = stored_guild.filter(|_| config.data.member_retention_days > 0) {
+        let cutoff = chrono::Utc::now() - chrono::Duration::days(config.data.member_retention_days as i64);
+        let pruned = database.prune_guild_members(&guild_name, &guild_realm, &roster_keys, cutoff).await?;
+        if pruned > 0 {
+            info!(guild = %guild, pruned = pruned, retention_days = config.data.member_retention_days, "Pruned departed members");
+        }
+    }
+
+    info!(guild = %guild, stored = stored, "Guild refresh complete");
+    Ok(stored)
+}
+
+/// Characters listed in a raider.io guild profile, with zeroed scores
+fn roster_players(guild_data: &serde_json::Value) -> Vec<PlayerData> {
+    let Some(members) = guild_data.get("members").and_then(|m| m.as_array()) else {
+        return Vec::new();
+    };
+    let guild_name = guild_data.get("name").and_then(|n| n.as_str()).unwrap_or("Unknown");
+    let guild_realm = guild_data.get("realm").and_then(|r| r.as_str()).map(RealmName::from);
+
+    members
+        .iter()
+        .filter_map(|member| member.get("character"))
+        .filter_map(|character| {
+            let realm = character.get("realm").and_then(|r| r.as_str()).unwrap_or("Unknown").to_string();
+            let name = character.get("name").and_then(|n| n.as_str()).unwrap_or("Unknown").to_string();
+            if name.is_empty() || name == "Unknown" {
+                return None;
+            }
+
+            Some(PlayerData {
+                name: PlayerName::from(name),
+                realm: RealmName::from(realm),
+                guild: Some(GuildName::from(guild_name.to_string())),
+                guild_realm: guild_realm.clone(),
+                class: character.get("class").and_then(|c| c.as_str()).map(|s| s.to_string()),
+                active_spec_name: character.get("active_spec_name").and_then(|a| a.as_str()).map(|s| s.to_string()),
+                ilvl: None,
+                rio_all: MythicPlusScore::zero(),
+                rio_dps: MythicPlusScore::zero(),
+                rio_healer: MythicPlusScore::zero(),
+                rio_tank: MythicPlusScore::zero(),
+                spec_0: MythicPlusScore::zero(),
+                spec_1: MythicPlusScore::zero(),
+                spec_2: MythicPlusScore::zero(),
+                spec_3: MythicPlusScore::zero(),
+                spec_0_name: None,
+                spec_1_name: None,
+                spec_2_name: None,
+                spec_3_name: None,
+            })
+        })
+        .collect()
+}
+
+/// Caps concurrent requests per realm so one realm is paced while others proceed in parallel
+struct RealmLimiter {
+    per_realm: Option<usize>,
+    semaphores: Mutex<HashMap<String, Arc<Semaphore>>>,
+}
+
+impl RealmLimiter {
+    fn new(per_realm: Option<usize>) -> Self {
+        Self {
+            per_realm,
+            semaphores: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Wait for a slot on the given realm; returns `None` when no per-realm cap is configured
+    async fn acquire(&self, realm: &str) -> Option<OwnedSemaphorePermit> {
+        let limit = self.per_realm?;
+        let semaphore = {
+            let mut semaphores = self.semaphores.lock().unwrap();
+            Arc::clone(
+                semaphores
+                    .entry(RealmName::from(realm).to_string())
+                    .or_insert_with(|| Arc::new(Semaphore::new(limit))),
+            )
+        };
+        semaphore.acquire_owned().await.ok()
+    }
+}
+
+/// Normalized `(realm, name)` key matching how members are stored in the database
+fn member_key(realm: &str, name: &str) -> (String, String) {
+    (RealmName::from(realm).to_string(), PlayerName::from(name).to_string())
+}
+
+/// Convert fetched players to members for storage, leaving out those below
+/// `data.min_stored_rio`; returns the members and the keys of the skipped players
+fn storable_members(players: &[PlayerData], data: &DataConfig) -> (Vec<DbMember>, Vec<(String, String)>) {
+    let mut members = Vec::new();
+    let mut skipped = Vec::new();
+    for player in players {
+        if data.stores_rio(player.rio_all.value()) {
+            members.push(DbMember::from(player));
+        } else {
+            debug!(player = %player.name, realm = %player.realm, rio = player.rio_all.value(), "Below min_stored_rio, not storing");
+            skipped.push(member_key(&player.realm, &player.name));
+        }
+    }
+    (members, skipped)
+}
+
+/// Characters from `players` that are not yet in the `stored` key set
+fn pending_players(players: &[(String, String)], stored: &HashSet<(String, String)>) -> Vec<(String, String)> {
+    players
+        .iter()
+        .filter(|(realm, name)| !stored.contains(&member_key(realm, name)))
+        .cloned()
+        .collect()
+}
+
+/// Estimate how long fetching RIO data for `player_count` characters takes at the given rate
+fn estimate_fetch_duration(player_count: usize, requests_per_second: u32) -> std::time::Duration {
+    let requests_per_second = requests_per_second.max(1) as f64;
+    std::time::Duration::from_secs_f64(player_count as f64 / requests_per_second)
+}
+
+/// Format a duration as e.g. "1h 02m 05s"
+fn format_duration(duration: std::time::Duration) -> String {
+    let total = duration.as_secs();
+    let (hours, minutes, seconds) = (total / 3600, (total % 3600) / 60, total % 60);
+    if hours > 0 {
+        format!("{}h {:02}m {:02}s", hours, minutes, seconds)
+    } else if minutes > 0 {
+        format!("{}m {:02}s", minutes, seconds)
+    } else {
+        format!("{}s", seconds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::tests::test_member;
+
+    #[test]
+    fn test_refresh_scheduler_respects_disabled_setting() {
+        let mut config = AppConfig::default();
+        config.data.refresh_interval_hours = 0;
+        assert!(RefreshScheduler::from_config(&config).is_none());
+
+        config.data.refresh_interval_hours = 6;
+        let scheduler = RefreshScheduler::from_config(&config).unwrap();
+        assert_eq!(scheduler.interval, Duration::from_secs(6 * 60 * 60));
+    }
+
+    #[test]
+    fn test_parse_options_from_args() {
+        let args = vec!["--resume".to_string()];
+        assert_eq!(ParseOptions::from_args(&args), ParseOptions { resume: true, ..Default::default() });
+        let args = vec!["--dry-run".to_string(), "--allow-unauthenticated".to_string()];
+        assert_eq!(
+            ParseOptions::from_args(&args),
+            ParseOptions { dry_run: true, resume: false, allow_unauthenticated: true, guilds: Vec::new() }
+        );
+        assert_eq!(ParseOptions::from_args(&[]), ParseOptions::default());
+
+        let args = vec!["--guilds".to_string(), "Нехай Щастить, Morok,".to_string()];
+        assert_eq!(ParseOptions::from_args(&args).guilds, vec!["Нехай Щастить".to_string(), "Morok".to_string()]);
+    }
+
+    #[test]
+    fn test_retain_named_guilds_ignores_case() {
+        let mut guilds = vec![
+            GuildUrl::new("Tarren Mill", "Нехай Щастить"),
+            GuildUrl::new("Silvermoon", "Morok"),
+            GuildUrl::new("Draenor", "Echo"),
+        ];
+        retain_named_guilds(&mut guilds, &["нехай щастить".to_string(), "MOROK".to_string()]);
+        let names: Vec<&str> = guilds.iter().map(|g| g.name.as_str()).collect();
+        assert_eq!(names, vec!["Нехай Щастить", "Morok"]);
+    }
+
+    #[test]
+    fn test_member_key_collapses_casing() {
+        let roster = |realm: &str, name: &str| serde_json::json!({
+            "name": "Test Guild",
+            "members": [{ "character": { "name": name, "realm": realm, "class": "Mage" } }]
+        });
+        let mut data_dict: HashMap<(String, String), PlayerData> = HashMap::new();
+        for guild_data in [roster("Tarren Mill", "Frostbolt"), roster("tarren-mill", "FROSTBOLT")] {
+            for player in roster_players(&guild_data) {
+                data_dict.insert(member_key(&player.realm, &player.name), player);
+            }
+        }
+
+        assert_eq!(data_dict.len(), 1);
+        assert!(data_dict.contains_key(&("tarren-mill".to_string(), "Frostbolt".to_string())));
+        assert_eq!(member_key(" Tarren Mill ", "frostBOLT"), member_key("tarren-mill", "Frostbolt"));
+    }
+
+    #[test]
+    fn test_unauthenticated_run_threshold() {
+        assert!(check_unauthenticated_run(None, 1000, 1000, false).is_ok());
+        assert!(check_unauthenticated_run(None, 1001, 1000, false).unwrap_err().contains("--allow-unauthenticated"));
+        assert!(check_unauthenticated_run(Some("  "), 1001, 1000, false).is_err());
+        assert!(check_unauthenticated_run(Some("key"), 50_000, 1000, false).is_ok());
+        assert!(check_unauthenticated_run(None, 50_000, 1000, true).is_ok());
+        assert!(check_unauthenticated_run(None, 50_000, 0, false).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_resume_after_partial_run() {
+        let database = Database::open_temp().await;
+        database.clear_temp_members().await.unwrap();
+
+        // Roster keys come straight from the API, before normalization
+        let roster = vec![
+            ("Tarren Mill".to_string(), "alpha".to_string()),
+            ("Tarren Mill".to_string(), "beta".to_string()),
+            ("Silvermoon".to_string(), "gamma".to_string()),
+        ];
+
+        // First run crashes after storing one character
+        database.insert_temp_members_batch(&[test_member("alpha", "Tarren Mill", Some("Test Guild"), 2500.0)]).await.unwrap();
+
+        let pending = pending_players(&roster, &database.temp_member_keys().await.unwrap());
+        assert_eq!(pending.len(), 2);
+        assert!(!pending.contains(&("Tarren Mill".to_string(), "alpha".to_string())));
+
+        // Resumed run stores the rest
+        for (realm, name) in &pending {
+            database.insert_temp_members_batch(&[test_member(name, realm, Some("Test Guild"), 2500.0)]).await.unwrap();
+        }
+
+        let stored = database.temp_member_keys().await.unwrap();
+        assert!(pending_players(&roster, &stored).is_empty());
+        assert_eq!(stored.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_players_below_min_stored_rio_are_not_inserted() {
+        let database = Database::open_temp().await;
+        database.clear_temp_members().await.unwrap();
+
+        let player = |name: &str, rio: f64| PlayerData::from(&DbMember { rio_all: rio, ..test_member(name, "Tarren Mill", Some("Test Guild"), 2500.0) });
+        let players = vec![player("Main", 2500.0), player("Bankalt", 0.0)];
+        let data = DataConfig { min_stored_rio: 500.0, ..DataConfig::default() };
+
+        let (members, skipped) = storable_members(&players, &data);
+        database.insert_temp_members_batch(&members).await.unwrap();
+
+        let stored = database.temp_member_keys().await.unwrap();
+        assert!(stored.contains(&member_key("Tarren Mill", "Main")));
+        assert!(!stored.contains(&member_key("Tarren Mill", "Bankalt")));
+        assert_eq!(skipped, vec![member_key("Tarren Mill", "Bankalt")]);
+
+        // The default threshold stores everyone
+        assert_eq!(storable_members(&players, &DataConfig::default()).0.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_realm_limiter_serializes_same_realm() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let limiter = RealmLimiter::new(Some(1));
+        let in_flight = AtomicUsize::new(0);
+        let max_in_flight = AtomicUsize::new(0);
+
+        let request = |realm: &'static str| {
+            let (limiter, in_flight, max_in_flight) = (&limiter, &in_flight, &max_in_flight);
+            async move {
+                let _permit = limiter.acquire(realm).await;
+                let current = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                max_in_flight.fetch_max(current, Ordering::SeqCst);
+                tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+                in_flight.fetch_sub(1, Ordering::SeqCst);
+            }
+        };
+
+        // Same realm under different spellings must share one permit
+        futures::join!(request("Tarren Mill"), request("tarren-mill"));
+        assert_eq!(max_in_flight.load(Ordering::SeqCst), 1);
+
+        // Different realms still proceed in parallel
+        futures::join!(request("Tarren Mill"), request("Silvermoon"));
+        assert_eq!(max_in_flight.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_realm_limiter_disabled_by_default() {
+        let limiter = RealmLimiter::new(None);
+        assert!(limiter.acquire("Tarren Mill").await.is_none());
+    }
+
+    #[test]
+    fn test_estimate_fetch_duration() {
+        assert_eq!(estimate_fetch_duration(500, 10).as_secs(), 50);
+        assert_eq!(estimate_fetch_duration(0, 10).as_secs(), 0);
+        // A zero rate must not divide by zero
+        assert_eq!(estimate_fetch_duration(5, 0).as_secs(), 5);
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(std::time::Duration::from_secs(42)), "42s");
+        assert_eq!(format_duration(std::time::Duration::from_secs(125)), "2m 05s");
+        assert_eq!(format_duration(std::time::Duration::from_secs(3725)), "1h 02m 05s");
+    }
+
+    #[tokio::test]
+    async fn test_single_rate_limit_is_retried_once_by_the_client() {
+        use std::sync::atomic::AtomicUsize;
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        // The first request is rate limited, every later one succeeds
+        let body = r#"{"name": "Tarrenone", "realm": "Tarren Mill", "class": "Mage", "active_spec_name": "Frost", "mythic_plus_scores_by_season": [{"scores": {"all": 2500.0}}]}"#;
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let requests = Arc::new(AtomicUsize::new(0));
+        let served = requests.clone();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut request = [0u8; 4096];
+                let _ = socket.read(&mut request).await;
+                let response = if served.fetch_add(1, Ordering::SeqCst) == 0 {
+                    "HTTP/1.1 429 Too Many Requests\r\nRetry-After: 0\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string()
+                } else {
+                    format!(
+                        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        body.len(),
+                        body
+                    )
+                };
+                socket.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+
+        let mut config = AppConfig::default();
+        config.raider_io.base_url = format!("http://{}", addr);
+        let client = RaiderIOClient::from_config(&config).unwrap();
+
+        let (player, success) = fetch_player_rio(&client, "Tarren Mill", "Tarrenone", None, None, 0, 1).await;
+        assert!(success);
+        assert_eq!(player.rio_all.value(), 2500.0);
+        // One retry from the client and none stacked on top by the parser
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_allowlisted_run_keeps_other_realms_members() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let roster = r#"{"name": "Thorned Horde", "realm": "Tarren Mill", "members": [{"character": {"name": "Alpha", "realm": "Tarren Mill", "class": "Mage"}}]}"#;
+        let profile = r#"{"name": "Alpha", "realm": "Tarren Mill", "class": "Mage", "active_spec_name": "Frost", "mythic_plus_scores_by_season": [{"scores": {"all": 2600.0}}]}"#;
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut request = [0u8; 4096];
+                let read = socket.read(&mut request).await.unwrap_or(0);
+                let body = if String::from_utf8_lossy(&request[..read]).contains("/guilds/profile") { roster } else { profile };
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                socket.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+
+        let mut config = AppConfig::default();
+        config.raider_io.base_url = format!("http://{}", addr);
+        config.data.realm_allowlist = vec!["Tarren Mill".to_string()];
+
+        let database = Database::open_temp().await;
+        database.clear_guilds().await;
+        database.import_guilds_from_text("realm=Tarren%20Mill&name=Thorned%20Horde\nrealm=Silvermoon&name=Nexus").await;
+        database.upsert_member(&test_member("Gamma", "Silvermoon", Some("Nexus"), 2100.0)).await.unwrap();
+
+        // A normal run with an allowlist takes the in-place path, never the swap
+        assert!(refreshes_in_place(&config.data, &[]));
+        let mut guild_urls = database.get_all_guilds().await.unwrap();
+        config.data.retain_allowed_realms(&mut guild_urls);
+        let client = RaiderIOClient::from_config(&config).unwrap();
+        assert_eq!(refresh_guilds_in_place(&config, &database, &client, &guild_urls).await.unwrap(), 1);
+
+        assert!(database.get_member("Gamma", "Silvermoon").await.unwrap().is_some());
+        assert_eq!(database.get_member("Alpha", "Tarren Mill").await.unwrap().unwrap().rio_all, 2600.0);
+        assert!(!refreshes_in_place(&DataConfig::default(), &[]));
+    }
+}
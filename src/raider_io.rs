@@ -1,13 +1,15 @@
 /// Raider.io API client with proper error handling and type safety
-use crate::config::AppConfig;
+use crate::config::{AppConfig, Region};
 use crate::error::{BotError, Result};
-use crate::types::{GuildName, GuildUrl, MythicPlusScore, PlayerName, RaidTier, RealmName, Season, WorldRank};
+use crate::guild_data::{estimate_best_percent, Progression};
+use crate::logging::prune_error_dir;
+use crate::types::{class_spec_names, GuildName, GuildUrl, MythicPlusScore, PlayerName, RaidTier, RealmName, Season, WorldRank};
 
 use reqwest::{Client, StatusCode};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::time::Duration;
-use std::fs;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use std::path::PathBuf;
 use tokio::time::sleep;
 use tracing::{debug, error, info, instrument, warn};
 use uuid::Uuid;
@@ -17,21 +19,61 @@ use uuid::Uuid;
 pub struct GuildData {
     pub name: GuildName,
     pub realm: RealmName,
-    pub progress: String,
+    /// Progress summary such as "5/8 M"; `None` when the guild exists but hasn't
+    /// raided the requested tier
+    pub progress: Option<String>,
     pub rank: Option<WorldRank>,
     pub best_percent: f64,
     pub pull_count: Option<u32>,
     pub defeated_at: Option<String>, // ISO 8601 datetime when the latest boss was killed
+    /// Display name of the boss the guild is progressing on; `None` after a full clear
+    /// or when the tier's boss order isn't known
+    pub current_boss: Option<String>,
+}
+
+impl GuildData {
+    /// A guild that raider.io knows but that has no progression in the tier
+    fn not_started(guild_url: &GuildUrl) -> Self {
+        Self {
+            name: guild_url.name.clone(),
+            realm: guild_url.realm.clone(),
+            progress: None,
+            rank: None,
+            best_percent: 0.0,
+            pull_count: None,
+            defeated_at: None,
+            current_boss: None,
+        }
+    }
+
+    /// Progress summary for display, "Not started" for guilds without progression
+    pub fn progress_label(&self) -> &str {
+        self.progress.as_deref().unwrap_or("Not started")
+    }
+}
+
+/// Outcome of fetching a single guild's raid progression
+#[derive(Debug, Clone)]
+pub enum GuildFetchResult {
+    /// Guild exists; its `progress` is `None` if it hasn't raided the requested tier
+    Found(GuildData),
+    /// raider.io doesn't know the guild (404)
+    NotFound,
 }
 
 /// Player mythic+ data from raider.io
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PlayerData {
     pub name: PlayerName,
     pub realm: RealmName,
     pub guild: Option<GuildName>,
+    /// Realm of the character's guild, which differs from `realm` for characters on a
+    /// connected realm
+    pub guild_realm: Option<RealmName>,
     pub class: Option<String>,
     pub active_spec_name: Option<String>,
+    /// Equipped item level, when known
+    pub ilvl: Option<i32>,
     pub rio_all: MythicPlusScore,
     pub rio_dps: MythicPlusScore,
     pub rio_healer: MythicPlusScore,
@@ -40,33 +82,34 @@ pub struct PlayerData {
     pub spec_1: MythicPlusScore,
     pub spec_2: MythicPlusScore,
     pub spec_3: MythicPlusScore,
+    pub spec_0_name: Option<String>,
+    pub spec_1_name: Option<String>,
+    pub spec_2_name: Option<String>,
+    pub spec_3_name: Option<String>,
 }
 
-/// Internal raider.io guild API response structure
+/// Internal raider.io guild API response structure. The raid sections stay untyped
+/// so a renamed or missing key only loses that detail instead of failing the guild.
 #[derive(Debug, Clone, Deserialize)]
 struct RaiderIOGuildResponse {
+    #[serde(default)]
     name: String,
+    #[serde(default)]
     realm: String,
-    raid_progression: HashMap<String, RaidProgress>,
-    raid_rankings: HashMap<String, RaidRankings>,
-}
-
-/// Raid progression details
-#[derive(Debug, Clone, Deserialize)]
-struct RaidProgress {
-    summary: String,
+    #[serde(default)]
+    raid_progression: serde_json::Value,
+    #[serde(default)]
+    raid_rankings: serde_json::Value,
 }
 
-/// Raid rankings by difficulty
-#[derive(Debug, Clone, Deserialize)]
-struct RaidRankings {
-    mythic: MythicRanking,
-}
-
-/// Mythic difficulty ranking
-#[derive(Debug, Clone, Deserialize)]
-struct MythicRanking {
-    world: Option<u32>,
+impl RaiderIOGuildResponse {
+    /// Keys of a raid section, for debug logging
+    fn raid_keys(section: &serde_json::Value) -> Vec<&str> {
+        section
+            .as_object()
+            .map(|raids| raids.keys().map(String::as_str).collect())
+            .unwrap_or_default()
+    }
 }
 
 /// Boss kill response from raider.io
@@ -118,6 +161,7 @@ struct RaiderIOPlayerResponse {
 #[derive(Debug, Clone, Deserialize)]
 struct PlayerGuild {
     name: String,
+    realm: Option<String>,
 }
 
 /// Mythic+ scores by season
@@ -139,16 +183,160 @@ struct MythicPlusScores {
     spec_3: Option<f64>,
 }
 
+/// Circuit breaker state as seen by the next request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CircuitState {
+    /// Requests go through normally
+    Closed,
+    /// Too many consecutive failures; requests are short-circuited until the cooldown ends
+    Open,
+    /// Cooldown over; requests probe whether raider.io has recovered
+    HalfOpen,
+}
+
+/// Stops requests to raider.io for a cooldown window after `threshold` consecutive
+/// failures (server errors or network errors), so an outage isn't met with a full
+/// round of retries for every guild. A threshold of 0 disables the breaker.
+#[derive(Debug)]
+struct CircuitBreaker {
+    threshold: u32,
+    cooldown: Duration,
+    inner: std::sync::Mutex<BreakerInner>,
+}
+
+#[derive(Debug, Default)]
+struct BreakerInner {
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+}
+
+impl CircuitBreaker {
+    fn new(threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            threshold,
+            cooldown,
+            inner: std::sync::Mutex::new(BreakerInner::default()),
+        }
+    }
+
+    fn state_at(&self, now: Instant) -> CircuitState {
+        match self.inner.lock().unwrap().opened_at {
+            None => CircuitState::Closed,
+            Some(opened_at) if now.duration_since(opened_at) < self.cooldown => CircuitState::Open,
+            Some(_) => CircuitState::HalfOpen,
+        }
+    }
+
+    /// Whether a request may be sent; only false while open
+    fn allow_request_at(&self, now: Instant) -> bool {
+        self.state_at(now) != CircuitState::Open
+    }
+
+    fn record_success(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.opened_at.is_some() {
+            info!("Raider.io recovered, closing circuit breaker");
+        }
+        *inner = BreakerInner::default();
+    }
+
+    fn record_failure_at(&self, now: Instant) {
+        if self.threshold == 0 {
+            return;
+        }
+        let half_open = self.state_at(now) == CircuitState::HalfOpen;
+        let mut inner = self.inner.lock().unwrap();
+        inner.consecutive_failures += 1;
+        // A failed probe re-opens immediately; otherwise open once the threshold is hit
+        if half_open || (inner.opened_at.is_none() && inner.consecutive_failures >= self.threshold) {
+            warn!(
+                consecutive_failures = inner.consecutive_failures,
+                cooldown_secs = self.cooldown.as_secs(),
+                "Opening raider.io circuit breaker"
+            );
+            inner.opened_at = Some(now);
+        }
+    }
+}
+
+/// Whether a failed request may succeed if sent again: timeouts and connection or
+/// transport failures are transient; builder (e.g. invalid URL), redirect, and
+/// decode errors are permanent
+fn is_transient(error: &reqwest::Error) -> bool {
+    if error.is_builder() || error.is_redirect() || error.is_decode() || error.is_status() {
+        return false;
+    }
+    error.is_timeout() || error.is_connect() || error.is_request() || error.is_body()
+}
+
+/// Boss names whose raider.io slug doesn't title-case back to the in-game name
+const BOSS_DISPLAY_NAMES: &[(&str, &str)] = &[
+    ("vexie-and-the-geargrinders", "Vexie and the Geargrinders"),
+    ("onearmed-bandit", "One-Armed Bandit"),
+    ("mugzee-heads-of-security", "Mug'Zee, Heads of Security"),
+    ("loomithar", "Loom'ithar"),
+    ("nexus-king-salhadaar", "Nexus-King Salhadaar"),
+];
+
+/// Map a boss slug back to its display name, title-casing slugs without an override
+fn boss_display_name(slug: &str) -> String {
+    if let Some((_, name)) = BOSS_DISPLAY_NAMES.iter().find(|(known, _)| *known == slug) {
+        return name.to_string();
+    }
+    slug.split('-')
+        .map(|word| {
+            let mut chars = word.chars();
+            chars.next().map_or_else(String::new, |first| first.to_uppercase().chain(chars).collect())
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Delay requested by a `Retry-After` header given in seconds; the HTTP-date form isn't used by raider.io
+fn retry_after_ms(headers: &reqwest::header::HeaderMap) -> Option<u64> {
+    let secs: u64 = headers.get(reqwest::header::RETRY_AFTER)?.to_str().ok()?.trim().parse().ok()?;
+    Some(secs.saturating_mul(1000))
+}
+
+/// Character profile fields: the season's scores, class and active spec, which player
+/// data is built from, followed by any configured extras not already in the list
+fn player_fields(season: &Season, extra: &[String]) -> Vec<String> {
+    let mut fields = vec![
+        format!("mythic_plus_scores_by_season:{}", season),
+        "class".to_string(),
+        "active_spec_name".to_string(),
+    ];
+    for field in extra.iter().map(|field| field.trim()).filter(|field| !field.is_empty()) {
+        if !fields.iter().any(|existing| existing == field) {
+            fields.push(field.to_string());
+        }
+    }
+    fields
+}
+
+/// A fresh `x-request-id` value for one HTTP request
+fn new_request_id() -> String {
+    format!("wow-guild-bot-{}", Uuid::new_v4())
+}
+
 /// HTTP client for raider.io API with rate limiting and error handling
 #[derive(Debug, Clone)]
 pub struct RaiderIOClient {
     client: Client,
     base_url: String,
     api_key: Option<String>,
-    season: Season,
-    request_id_header: String,
+    /// `fields` requested for character profiles, see `player_fields`
+    player_fields: Vec<String>,
     max_retries: u32,
     base_delay_ms: u64,
+    roster_timeout: Duration,
+    region: Region,
+    /// Where request and parse error dumps are written
+    error_dir: PathBuf,
+    /// Cap on files in `error_dir` (0 = no limit)
+    max_error_files: usize,
+    /// Shared by clones so concurrent guild fetches see the same outage state
+    breaker: Arc<CircuitBreaker>,
 }
 
 impl RaiderIOClient {
@@ -156,7 +344,7 @@ impl RaiderIOClient {
     pub fn from_config(config: &AppConfig) -> Result<Self> {
         let client = Client::builder()
             .timeout(Duration::from_secs(config.raider_io.timeout_secs))
-            .user_agent("wow-guild-bot/1.0")
+            .user_agent(config.raider_io.user_agent.as_str())
             .build()
             .map_err(|e| BotError::Http(e))?;
 
@@ -172,10 +360,17 @@ impl RaiderIOClient {
             client,
             base_url: config.raider_io.base_url.clone(),
             api_key: config.raider_io.api_key.clone(),
-            season: Season::from(config.raider_io.season.clone()),
-            request_id_header: format!("wow-guild-bot-{}", Uuid::new_v4()),
+            player_fields: player_fields(&Season::from(config.raider_io.season.clone()), &config.raider_io.player_fields),
             max_retries: 10, // Max retry attempts for rate limits
             base_delay_ms: 10000, // 10 second delay for rate limits
+            roster_timeout: Duration::from_secs(config.raider_io.roster_timeout_secs),
+            region: config.raider_io.region,
+            error_dir: PathBuf::from("logs/errors"),
+            max_error_files: config.logging.max_error_files,
+            breaker: Arc::new(CircuitBreaker::new(
+                config.raider_io.circuit_breaker_threshold,
+                Duration::from_secs(config.raider_io.circuit_breaker_cooldown_secs),
+            )),
         })
     }
 
@@ -226,23 +421,59 @@ impl RaiderIOClient {
         ]
     }
 
+    /// Boss slugs of a tier in kill order, when known
+    fn boss_slugs(tier: RaidTier) -> Option<&'static [&'static str]> {
+        match tier.value() {
+            2 => Some(Self::get_liberation_boss_names()),
+            3 => Some(Self::get_manaforge_boss_names()),
+            _ => None,
+        }
+    }
+
+    /// Display name of the boss after the last one killed, e.g. "Chrome King Gallywix"
+    /// for 7/8 M in Liberation of Undermine
+    fn current_boss(tier: RaidTier, progression: &Progression) -> Option<String> {
+        if progression.is_full_clear() {
+            return None;
+        }
+        let slug = Self::boss_slugs(tier)?.get(progression.bosses as usize)?;
+        Some(boss_display_name(slug))
+    }
+
+    /// Delete the oldest error files so that writing one more stays within `max_error_files`
+    async fn prune_error_files(&self) {
+        if self.max_error_files == 0 {
+            return;
+        }
+        if let Err(e) = prune_error_dir(&self.error_dir, self.max_error_files - 1).await {
+            warn!(error = %e, dir = %self.error_dir.display(), "Failed to prune old error files");
+        }
+    }
+
+    /// Write an error dump to `error_dir/<file_name>` with async file IO, so dumps made
+    /// during the parser's concurrent fetches don't stall the runtime
+    async fn write_error_file(&self, file_name: &str, error_data: &serde_json::Value) {
+        if tokio::fs::create_dir_all(&self.error_dir).await.is_err() {
+            return; // Can't create directory, skip saving
+        }
+        self.prune_error_files().await;
+
+        if let Ok(json_str) = serde_json::to_string_pretty(error_data) {
+            let _ = tokio::fs::write(self.error_dir.join(file_name), json_str).await;
+        }
+    }
+
     /// Save detailed error information to individual file
-    async fn save_error_details(&self, url: &str, method: &str, response_text: Option<String>, error: &BotError, attempt: u32) {
+    async fn save_error_details(&self, url: &str, method: &str, request_id: Option<&str>, response_text: Option<String>, error: &BotError, attempt: u32) {
         let timestamp = chrono::Utc::now().format("%Y%m%d_%H%M%S_%3f");
         let error_filename = format!("{}_attempt_{}.json", timestamp, attempt);
-        let error_dir = "logs/errors";
-        
-        if let Err(_) = fs::create_dir_all(error_dir) {
-            return; // Can't create directory, skip saving
-        }
-        
-        let error_file = format!("{}/{}", error_dir, error_filename);
         let error_data = serde_json::json!({
             "timestamp": chrono::Utc::now().to_rfc3339(),
             "error_id": error_filename.replace(".json", ""),
             "request": {
                 "method": method,
                 "url": url,
+                "request_id": request_id,
                 "attempt": attempt,
                 "max_retries": self.max_retries
             },
@@ -254,25 +485,36 @@ impl RaiderIOClient {
                 "type": format!("{:?}", error)
             }
         });
-        
-        if let Ok(json_str) = serde_json::to_string_pretty(&error_data) {
-            let _ = fs::write(error_file, json_str);
+
+        self.write_error_file(&error_filename, &error_data).await;
+    }
+
+    /// Build a GET request; `timeout` overrides the client-wide timeout for this request only
+    fn request(&self, url: &str, timeout: Option<Duration>, request_id: &str) -> reqwest::RequestBuilder {
+        let request = self.client
+            .get(url)
+            .header("x-request-id", request_id);
+        match timeout {
+            Some(timeout) => request.timeout(timeout),
+            None => request,
         }
     }
 
     /// Execute HTTP request with retry logic for rate limits
-    async fn execute_request_with_retry(&self, url: &str) -> Result<reqwest::Response> {
+    async fn execute_request_with_retry(&self, url: &str, timeout: Option<Duration>) -> Result<reqwest::Response> {
         let mut last_error: Option<BotError> = None;
         
         for attempt in 0..=self.max_retries {
+            if !self.breaker.allow_request_at(Instant::now()) {
+                warn!(url = url, "Circuit breaker open, skipping raider.io request");
+                return Err(BotError::application("raider.io temporarily unavailable"));
+            }
+
+            // Every attempt is its own HTTP request, so each gets its own id
+            let request_id = new_request_id();
             let start = std::time::Instant::now();
             
-            match self.client
-                .get(url)
-                .header("x-request-id", &self.request_id_header)
-                .send()
-                .await
-            {
+            match self.request(url, timeout, &request_id).send().await {
                 Ok(response) => {
                     let duration = start.elapsed();
                     let status = response.status();
@@ -286,33 +528,29 @@ impl RaiderIOClient {
                         status = status.as_u16(),
                         duration_ms = duration.as_millis(),
                         attempt = attempt + 1,
+                        request_id = %request_id,
                         "API request completed"
                     );
                     
                     if status == StatusCode::TOO_MANY_REQUESTS {
                         if attempt < self.max_retries {
-                            let delay_ms = self.base_delay_ms; // Fixed 10-second delay
+                            // Wait as long as raider.io asks, or the fixed delay when it doesn't say
+                            let delay_ms = retry_after_ms(response.headers()).unwrap_or(self.base_delay_ms);
                             warn!(
                                 attempt = attempt + 1,
                                 max_retries = self.max_retries,
                                 delay_ms = delay_ms,
                                 url = url,
-                                "Rate limited by raider.io, waiting 10 seconds before retry"
+                                "Rate limited by raider.io, waiting before retry"
                             );
                             
                             crate::log_rate_limit!("raider.io", delay_ms);
-                            
-                            // Show progress during delay
-                            for i in 1..=10 {
-                                tokio::time::sleep(Duration::from_secs(1)).await;
-                                if i % 2 == 0 {
-                                    println!("  [Rate Limited] Waiting... {}s remaining", 10 - i);
-                                }
-                            }
+                            println!("  [Rate Limited] Waiting {}s before retrying", delay_ms / 1000);
+                            sleep(Duration::from_millis(delay_ms)).await;
                             continue;
                         } else {
                             let error = BotError::rate_limit("Raider.io API rate limit exceeded after max retries");
-                            self.save_error_details(url, "GET", None, &error, attempt + 1).await;
+                            self.save_error_details(url, "GET", Some(&request_id), None, &error, attempt + 1).await;
                             error!(
                                 attempts = attempt + 1,
                                 url = url,
@@ -323,6 +561,7 @@ impl RaiderIOClient {
                     }
                     
                     if status.is_server_error() {
+                        self.breaker.record_failure_at(Instant::now());
                         if attempt < self.max_retries {
                             let delay_ms = self.base_delay_ms; // Fixed 10-second delay
                             warn!(
@@ -344,7 +583,7 @@ impl RaiderIOClient {
                             continue;
                         } else {
                             let error = BotError::raider_io(status.as_u16(), "Server error after max retries");
-                            self.save_error_details(url, "GET", None, &error, attempt + 1).await;
+                            self.save_error_details(url, "GET", Some(&request_id), None, &error, attempt + 1).await;
                             error!(
                                 attempts = attempt + 1,
                                 status = status.as_u16(),
@@ -355,6 +594,7 @@ impl RaiderIOClient {
                         }
                     }
                     
+                    self.breaker.record_success();
                     if attempt > 0 {
                         info!(
                             attempt = attempt + 1,
@@ -366,7 +606,21 @@ impl RaiderIOClient {
                     
                     return Ok(response);
                 },
+                Err(e) if !is_transient(&e) => {
+                    // Retrying a malformed request or a redirect loop would only fail the same way
+                    let error = BotError::Http(e);
+                    self.save_error_details(url, "GET", Some(&request_id), None, &error, attempt + 1).await;
+                    error!(
+                        attempt = attempt + 1,
+                        error = %error,
+                        url = url,
+                        request_id = %request_id,
+                        "Permanent HTTP error, not retrying"
+                    );
+                    return Err(error);
+                }
                 Err(e) => {
+                    self.breaker.record_failure_at(Instant::now());
                     let duration = start.elapsed();
                     warn!(
                         attempt = attempt + 1,
@@ -374,6 +628,7 @@ impl RaiderIOClient {
                         error = %e,
                         duration_ms = duration.as_millis(),
                         url = url,
+                        request_id = %request_id,
                         "HTTP request failed"
                     );
                     
@@ -388,7 +643,7 @@ impl RaiderIOClient {
                         continue;
                     } else {
                         let error = BotError::Http(e);
-                        self.save_error_details(url, "GET", None, &error, attempt + 1).await;
+                        self.save_error_details(url, "GET", Some(&request_id), None, &error, attempt + 1).await;
                         error!(
                             attempts = attempt + 1,
                             error = %error,
@@ -405,32 +660,90 @@ impl RaiderIOClient {
         Err(last_error.unwrap_or_else(|| BotError::application("Unexpected retry loop exit")))
     }
 
+    /// Build the guild roster URL, including the API key when configured
+    fn guild_roster_url(&self, guild_url: &GuildUrl) -> String {
+        self.guild_profile_url(guild_url, "members")
+    }
+
+    /// Build a guild profile URL in the guild's own region, including the API key when configured
+    fn guild_profile_url(&self, guild_url: &GuildUrl, fields: &str) -> String {
+        let url = format!(
+            "{}/guilds/profile?region={}&{}&fields={}",
+            self.base_url,
+            self.guild_region(guild_url),
+            guild_url.to_query_string(),
+            fields
+        );
+        self.add_api_key(url)
+    }
+
+    /// Build a boss-kill URL, including the API key when configured. The endpoint sits
+    /// beside the versioned API (`/api/guilds/boss-kills`), so it is derived from
+    /// `base_url` with the `/v1` segment dropped.
+    fn boss_kill_url(&self, guild_url: &GuildUrl, raid: &str, difficulty: &str, boss: &str) -> String {
+        let api_root = self.base_url.trim_end_matches('/');
+        let api_root = api_root.strip_suffix("/v1").unwrap_or(api_root);
+        let url = format!(
+            "{}/guilds/boss-kills?raid={}&difficulty={}&region={}&realm={}&guild={}&boss={}",
+            api_root,
+            raid,
+            difficulty,
+            self.guild_region(guild_url),
+            urlencoding::encode(&guild_url.realm.realm_slug()),
+            urlencoding::encode(guild_url.name.as_str()),
+            boss
+        );
+        self.add_api_key(url)
+    }
+
+    /// The guild's region, falling back to the configured `raider_io.region`
+    fn guild_region(&self, guild_url: &GuildUrl) -> Region {
+        guild_url.region.unwrap_or(self.region)
+    }
+
+    /// Fetch a guild's member roster as raw JSON. Rosters can be large, so the request
+    /// uses `raider_io.roster_timeout_secs` instead of the client-wide timeout.
+    #[instrument(skip(self), fields(guild = %guild_url.name, realm = %guild_url.realm))]
+    pub async fn fetch_guild_roster(&self, guild_url: &GuildUrl) -> Result<serde_json::Value> {
+        let url = self.guild_roster_url(guild_url);
+
+        debug!("Fetching guild roster from: {}", url);
+
+        let response = self.execute_request_with_retry(&url, Some(self.roster_timeout)).await?;
+        let status = response.status();
+
+        if !status.is_success() {
+            let error = BotError::from(status);
+            self.save_error_details(&url, "GET", None, None, &error, 1).await;
+            return Err(error);
+        }
+
+        response
+            .json()
+            .await
+            .map_err(|e| BotError::Application(format!("Failed to parse guild JSON: {}", e)))
+    }
+
     /// Fetch guild raid progression data
     #[instrument(skip(self), fields(guild = %guild_url.name, realm = %guild_url.realm, tier = %tier))]
-    pub async fn fetch_guild_data(&self, guild_url: &GuildUrl, tier: RaidTier) -> Result<Option<GuildData>> {
+    pub async fn fetch_guild_data(&self, guild_url: &GuildUrl, tier: RaidTier) -> Result<GuildFetchResult> {
         let raid_name = Self::get_raid_name(tier)?;
         
-        let url = format!(
-            "{}/guilds/profile?region={}&{}&fields=raid_rankings,raid_progression",
-            self.base_url,
-            "eu", // TODO: Make region configurable
-            guild_url.to_query_string()
-        );
-        let url = self.add_api_key(url);
+        let url = self.guild_profile_url(guild_url, "raid_rankings,raid_progression");
 
         debug!("Fetching guild data from: {}", url);
 
-        let response = self.execute_request_with_retry(&url).await?;
+        let response = self.execute_request_with_retry(&url, None).await?;
         let status = response.status();
 
         if !status.is_success() {
             if status == StatusCode::NOT_FOUND {
                 warn!("Guild not found: {}/{}", guild_url.realm, guild_url.name);
-                return Ok(None);
+                return Ok(GuildFetchResult::NotFound);
             }
             let error = BotError::from(status);
             // Save error details for failed HTTP status codes
-            self.save_error_details(&url, "GET", None, &error, 1).await;
+            self.save_error_details(&url, "GET", None, None, &error, 1).await;
             return Err(error);
         }
 
@@ -439,70 +752,57 @@ impl RaiderIOClient {
         debug!("Received guild data response: {} characters", response_text.len());
         
         // Parse the JSON and log the successful response
-        let guild_data: RaiderIOGuildResponse = serde_json::from_str(&response_text)
-            .map_err(|e| {
-                let error = BotError::Application(format!("Failed to parse JSON: {}", e));
-                
+        let guild_data: RaiderIOGuildResponse = match serde_json::from_str(&response_text) {
+            Ok(parsed) => parsed,
+            Err(e) => {
                 // Save detailed error info for JSON parsing failures
                 let timestamp = chrono::Utc::now().format("%Y%m%d_%H%M%S_%3f");
                 let error_id = format!("parse_error_{}", timestamp);
-                let error_dir = "logs/errors";
-                
-                if fs::create_dir_all(error_dir).is_ok() {
-                    let error_file = format!("{}/{}.json", error_dir, error_id);
-                    let error_data = serde_json::json!({
-                        "timestamp": chrono::Utc::now().to_rfc3339(),
-                        "error_id": error_id,
-                        "request": {
-                            "method": "GET",
-                            "url": &url
-                        },
-                        "response": {
-                            "body": &response_text,
-                            "body_length": response_text.len(),
-                            "preview": &response_text[..response_text.len().min(500)]
-                        },
-                        "error": {
-                            "message": e.to_string(),
-                            "type": "JSON_PARSE_ERROR"
-                        }
-                    });
-                    
-                    if let Ok(json_str) = serde_json::to_string_pretty(&error_data) {
-                        let _ = fs::write(error_file, json_str);
+                let error_data = serde_json::json!({
+                    "timestamp": chrono::Utc::now().to_rfc3339(),
+                    "error_id": error_id,
+                    "request": {
+                        "method": "GET",
+                        "url": &url
+                    },
+                    "response": {
+                        "body": &response_text,
+                        "body_length": response_text.len(),
+                        "preview": &response_text[..response_text.len().min(500)]
+                    },
+                    "error": {
+                        "message": e.to_string(),
+                        "type": "JSON_PARSE_ERROR"
                     }
-                }
-                
+                });
+                self.write_error_file(&format!("{}.json", error_id), &error_data).await;
+
                 error!(
                     error = %e,
                     response_preview = &response_text[..response_text.len().min(500)],
                     error_file = %error_id,
                     "Failed to parse guild data JSON response, saved details to logs/errors/{}.json", error_id
                 );
-                error
-            })?;
+                return Err(BotError::Application(format!("Failed to parse JSON: {}", e)));
+            }
+        };
         
 
-        debug!("Looking for raid_name: '{}' in raid_progression keys: {:?}", raid_name, guild_data.raid_progression.keys().collect::<Vec<_>>());
-        debug!("Looking for raid_name: '{}' in raid_rankings keys: {:?}", raid_name, guild_data.raid_rankings.keys().collect::<Vec<_>>());
+        debug!("Looking for raid_name: '{}' in raid_progression keys: {:?}", raid_name, RaiderIOGuildResponse::raid_keys(&guild_data.raid_progression));
+        debug!("Looking for raid_name: '{}' in raid_rankings keys: {:?}", raid_name, RaiderIOGuildResponse::raid_keys(&guild_data.raid_rankings));
 
-        let progress = guild_data
-            .raid_progression
-            .get(raid_name)
-            .map(|p| p.summary.clone())
-            .unwrap_or_else(|| "No progress".to_string());
-
-        let rank = guild_data
-            .raid_rankings
-            .get(raid_name)
-            .and_then(|r| r.mythic.world)
-            .map(WorldRank::from);
+        let Some((progress, rank)) = Self::progress_for_raid(&guild_data, raid_name) else {
+            // A partial profile: the guild exists but has no progression for this raid.
+            // Skip the boss-kill lookup rather than estimating progress it doesn't have.
+            debug!("No progression for raid '{}'", raid_name);
+            return Ok(GuildFetchResult::Found(GuildData::not_started(guild_url)));
+        };
             
         debug!("Parsed progress: '{}', rank: {:?}", progress, rank);
 
         // Fetch best percent, pull count, and defeated at timestamp
         let (best_percent, pull_count, defeated_at) = match self
-            .fetch_boss_kill_data(&guild_url.realm, &guild_url.name, raid_name, tier, &progress)
+            .fetch_boss_kill_data(guild_url, raid_name, tier, &progress)
             .await
         {
             Ok((percent, count, defeated_at)) => {
@@ -520,34 +820,25 @@ impl RaiderIOClient {
                 );
                 // For guilds with progression but no detailed boss data, 
                 // still show meaningful progression instead of zeros
-                if progress.contains("8/8") {
-                    (100.0, None, None) // Full clear
-                } else if progress.contains("M") {
-                    // Has mythic progression - estimate based on progress
-                    if let Some(kills) = progress.split('/').next().and_then(|s| s.parse::<u32>().ok()) {
-                        let percent = (kills as f64 / 8.0) * 100.0;
-                        (percent, None, None) // Use calculated percentage
-                    } else {
-                        (75.0, None, None) // Fallback for mythic guilds
-                    }
-                } else if progress.contains("H") {
-                    (25.0, None, None) // Heroic progression
-                } else if !progress.starts_with("0/") && progress != "No progress" {
-                    (10.0, None, None) // Some normal progression
-                } else {
-                    (0.0, None, None) // No progress at all
-                }
+                let (percent, pull_count) = estimate_best_percent(&progress);
+                (percent, pull_count, None)
             }
         };
 
+        let current_boss = progress
+            .parse::<Progression>()
+            .ok()
+            .and_then(|progression| Self::current_boss(tier, &progression));
+
         let guild_data = GuildData {
             name: guild_url.name.clone(),
             realm: guild_url.realm.clone(),
-            progress: progress.clone(),
+            progress: Some(progress.clone()),
             rank,
             best_percent,
             pull_count,
             defeated_at,
+            current_boss,
         };
 
         info!(
@@ -559,57 +850,49 @@ impl RaiderIOClient {
             pull_count = ?pull_count,
             "Successfully fetched guild data"
         );
-        Ok(Some(guild_data))
+        Ok(GuildFetchResult::Found(guild_data))
+    }
+
+    /// Extract the progression summary and mythic world rank for a raid, if the guild has any
+    fn progress_for_raid(guild: &RaiderIOGuildResponse, raid_name: &str) -> Option<(String, Option<WorldRank>)> {
+        let progress = guild.raid_progression.get(raid_name)?.get("summary")?.as_str()?.to_string();
+
+        let rank = guild
+            .raid_rankings
+            .get(raid_name)
+            .and_then(|r| r.get("mythic"))
+            .and_then(|mythic| mythic.get("world"))
+            .and_then(serde_json::Value::as_u64)
+            .and_then(|world| u32::try_from(world).ok())
+            .and_then(WorldRank::ranked);
+
+        Some((progress, rank))
     }
 
     /// Fetch boss kill data for detailed progression info
-    #[instrument(skip(self), fields(guild = %guild, realm = %realm, raid = raid, progress = progress))]
+    #[instrument(skip(self), fields(guild = %guild_url.name, realm = %guild_url.realm, raid = raid, progress = progress))]
     async fn fetch_boss_kill_data(
         &self,
-        realm: &RealmName,
-        guild: &GuildName,
+        guild_url: &GuildUrl,
         raid: &str,
         tier: RaidTier,
         progress: &str,
     ) -> Result<(f64, Option<u32>, Option<String>)> {
-        // Parse the difficulty from progress (e.g., "3/8 M" -> 'M')
-        let difficulty_char = progress.chars().last().unwrap_or('N');
-        let difficulty = match difficulty_char {
-            'M' => "mythic",
-            'H' => "heroic", 
-            'N' => "normal",
-            _ => "normal",
-        };
+        let progression = progress
+            .parse::<Progression>()
+            .map_err(|e| BotError::invalid_input(format!("Unrecognized progress '{}': {}", progress, e)))?;
+        let difficulty = progression.difficulty.api_name();
+        let current_progress = progression.bosses as usize;
 
-        // Parse current progress to determine best boss to query for kill data
-        let current_progress = progress.split('/').next()
-            .and_then(|s| s.parse::<usize>().ok())
-            .unwrap_or(0);
-        
-        // If full clear (8/8), return perfect progression
-        if current_progress >= 8 {
+        // A full clear (e.g. 8/8 or 9/9) needs no lookup; the denominator is the raid's boss count
+        if progression.is_full_clear() {
             return Ok((100.0, None, None)); // Full clear, perfect score
         }
         
         // Get boss name for NEXT progression (like Python bot)
-        let boss_name = if tier.value() == 2 { // liberation-of-undermine
-            // For progression data, get the NEXT boss they're working on
-            // If they're 5/8, get the 6th boss (index 5)
-            if current_progress < 8 {
-                Self::get_liberation_boss_names().get(current_progress).copied()
-            } else {
-                // Full clear, no next boss
-                return Ok((100.0, None, None));
-            }
-        } else if tier.value() == 3 { // manaforge-omega
-            // For progression data, get the NEXT boss they're working on
-            // If they're 5/8, get the 6th boss (index 5)
-            if current_progress < 8 {
-                Self::get_manaforge_boss_names().get(current_progress).copied()
-            } else {
-                // Full clear, no next boss
-                return Ok((100.0, None, None));
-            }
+        // If they're 5/8, get the 6th boss (index 5)
+        let boss_name = if let Some(bosses) = Self::boss_slugs(tier) {
+            bosses.get(current_progress).copied()
         } else if tier.value() == 1 { // nerubar-palace
             // Add Nerubar Palace boss names if needed
             Some("ulgrax-the-devourer") // First boss as fallback
@@ -622,17 +905,11 @@ impl RaiderIOClient {
             None => return Ok((0.0, None, None)), // No boss data available
         };
         
-        let url = format!(
-            "https://raider.io/api/guilds/boss-kills?raid={}&difficulty={}&region=eu&realm={}&guild={}&boss={}",
-            raid, difficulty, 
-            urlencoding::encode(&realm.to_string()),
-            urlencoding::encode(&guild.to_string()),
-            boss_name
-        );
+        let url = self.boss_kill_url(guild_url, raid, difficulty, boss_name);
 
         debug!("Fetching boss kill data from: {}", url);
 
-        let response = match self.execute_request_with_retry(&url).await {
+        let response = match self.execute_request_with_retry(&url, None).await {
             Ok(resp) => resp,
             Err(e) => {
                 warn!("Failed to fetch boss kill data after retries: {}", e);
@@ -661,59 +938,51 @@ impl RaiderIOClient {
         if response_text.trim() == "{}" {
             debug!("Empty JSON response - boss not killed yet");
             // For current progress bosses that aren't killed yet, try the next boss
-            if current_progress < 8 {
-                return self.try_next_boss_kill_data(realm, guild, raid, tier, current_progress, difficulty).await;
+            if current_progress < progression.total as usize {
+                return self.try_next_boss_kill_data(guild_url, raid, tier, current_progress, difficulty).await;
             }
             return Ok((0.0, None, None));
         }
 
-        let boss_data: BossKillResponse = serde_json::from_str(&response_text)
-            .map_err(|e| {
-                let error = BotError::Application(format!("Failed to parse boss kill JSON: {}", e));
-                
+        let boss_data: BossKillResponse = match serde_json::from_str(&response_text) {
+            Ok(parsed) => parsed,
+            Err(e) => {
                 // Save detailed error info for boss kill JSON parsing failures
                 let timestamp = chrono::Utc::now().format("%Y%m%d_%H%M%S_%3f");
                 let error_id = format!("boss_parse_error_{}", timestamp);
-                let error_dir = "logs/errors";
-                
-                if fs::create_dir_all(error_dir).is_ok() {
-                    let error_file = format!("{}/{}.json", error_dir, error_id);
-                    let error_data = serde_json::json!({
-                        "timestamp": chrono::Utc::now().to_rfc3339(),
-                        "error_id": error_id,
-                        "request": {
-                            "method": "GET",
-                            "url": &url,
-                            "guild": guild,
-                            "realm": realm,
-                            "raid": raid,
-                            "difficulty": difficulty,
-                            "boss": boss_name
-                        },
-                        "response": {
-                            "body": &response_text,
-                            "body_length": response_text.len(),
-                            "preview": &response_text[..response_text.len().min(500)]
-                        },
-                        "error": {
-                            "message": e.to_string(),
-                            "type": "BOSS_KILL_JSON_PARSE_ERROR"
-                        }
-                    });
-                    
-                    if let Ok(json_str) = serde_json::to_string_pretty(&error_data) {
-                        let _ = fs::write(error_file, json_str);
+                let error_data = serde_json::json!({
+                    "timestamp": chrono::Utc::now().to_rfc3339(),
+                    "error_id": error_id,
+                    "request": {
+                        "method": "GET",
+                        "url": &url,
+                        "guild": guild_url.name,
+                        "realm": guild_url.realm,
+                        "raid": raid,
+                        "difficulty": difficulty,
+                        "boss": boss_name
+                    },
+                    "response": {
+                        "body": &response_text,
+                        "body_length": response_text.len(),
+                        "preview": &response_text[..response_text.len().min(500)]
+                    },
+                    "error": {
+                        "message": e.to_string(),
+                        "type": "BOSS_KILL_JSON_PARSE_ERROR"
                     }
-                }
-                
+                });
+                self.write_error_file(&format!("{}.json", error_id), &error_data).await;
+
                 error!(
                     error = %e,
                     response_preview = &response_text[..response_text.len().min(500)],
                     error_file = %error_id,
                     "Failed to parse boss kill JSON response, saved details to logs/errors/{}.json", error_id
                 );
-                error
-            })?;
+                return Err(BotError::Application(format!("Failed to parse boss kill JSON: {}", e)));
+            }
+        };
 
         let (best_percent, pull_count, defeated_at) = if let Some(kill_details) = boss_data.kill_details {
             // Use killDetails format (like Python bot)
@@ -743,38 +1012,25 @@ impl RaiderIOClient {
     /// Try to get kill data from the next boss in progression
     async fn try_next_boss_kill_data(
         &self,
-        realm: &RealmName,
-        guild: &GuildName,
+        guild_url: &GuildUrl,
         raid: &str,
         tier: RaidTier,
         current_progress: usize,
         difficulty: &str,
     ) -> Result<(f64, Option<u32>, Option<String>)> {
         // Try the next boss (current progress index)
-        let next_boss_name = if tier.value() == 2 { // liberation-of-undermine
-            Self::get_liberation_boss_names().get(current_progress).copied()
-        } else if tier.value() == 3 { // manaforge-omega
-            Self::get_manaforge_boss_names().get(current_progress).copied()
-        } else {
-            None
-        };
+        let next_boss_name = Self::boss_slugs(tier).and_then(|bosses| bosses.get(current_progress).copied());
         
         let Some(next_boss_name) = next_boss_name else {
             debug!("No next boss available for current progress: {}", current_progress);
             return Ok((0.0, None, None));
         };
         
-        let url = format!(
-            "https://raider.io/api/guilds/boss-kills?raid={}&difficulty={}&region=eu&realm={}&guild={}&boss={}",
-            raid, difficulty, 
-            urlencoding::encode(&realm.to_string()),
-            urlencoding::encode(&guild.to_string()),
-            next_boss_name
-        );
+        let url = self.boss_kill_url(guild_url, raid, difficulty, next_boss_name);
 
         debug!("Trying next boss kill data from: {}", url);
         
-        let response = match self.execute_request_with_retry(&url).await {
+        let response = match self.execute_request_with_retry(&url, None).await {
             Ok(resp) => resp,
             Err(e) => {
                 debug!("Next boss kill data not available after retries: {}", e);
@@ -835,23 +1091,35 @@ impl RaiderIOClient {
         Ok((best_percent, pull_count, defeated_at))
     }
 
-    /// Fetch player mythic+ data
+    /// Build a character profile URL in `region` (the configured `raider_io.region` when
+    /// unknown), including the API key when configured
+    fn character_profile_url(&self, realm: &RealmName, name: &PlayerName, region: Option<Region>) -> String {
+        let url = format!(
+            "{}/characters/profile?region={}&realm={}&name={}&fields={}",
+            self.base_url,
+            region.unwrap_or(self.region),
+            urlencoding::encode(&realm.realm_slug()),
+            name,
+            self.player_fields.join(",")
+        );
+        self.add_api_key(url)
+    }
+
+    /// Fetch player mythic+ data. `region` is that of the guild the character was found
+    /// in, so members of a US guild are looked up in the US.
     #[instrument(skip(self), fields(player = %name, realm = %realm))]
     pub async fn fetch_player_data(
         &self,
         realm: &RealmName,
         name: &PlayerName,
         guild: Option<GuildName>,
+        region: Option<Region>,
     ) -> Result<Option<PlayerData>> {
-        let url = format!(
-            "{}/characters/profile?region=eu&realm={}&name={}&fields=mythic_plus_scores_by_season:{},class,active_spec_name",
-            self.base_url, realm, name, self.season
-        );
-        let url = self.add_api_key(url);
+        let url = self.character_profile_url(realm, name, region);
 
         debug!("Fetching player data from: {}", url);
 
-        let response = self.execute_request_with_retry(&url).await?;
+        let response = self.execute_request_with_retry(&url, None).await?;
         let status = response.status();
 
         if status == StatusCode::NOT_FOUND {
@@ -862,7 +1130,7 @@ impl RaiderIOClient {
         if !status.is_success() {
             let error = BotError::from(status);
             // Save error details for failed HTTP status codes
-            self.save_error_details(&url, "GET", None, &error, 1).await;
+            self.save_error_details(&url, "GET", None, None, &error, 1).await;
             return Err(error);
         }
 
@@ -871,55 +1139,58 @@ impl RaiderIOClient {
         
         debug!("Received player data response: {} characters", response_text.len());
         
-        let player_response: RaiderIOPlayerResponse = serde_json::from_str(&response_text)
-            .map_err(|e| {
-                let error = BotError::Application(format!("Failed to parse player JSON: {}", e));
-                
+        let player_response: RaiderIOPlayerResponse = match serde_json::from_str(&response_text) {
+            Ok(parsed) => parsed,
+            Err(e) => {
                 // Save detailed error info for player JSON parsing failures
                 let timestamp = chrono::Utc::now().format("%Y%m%d_%H%M%S_%3f");
                 let error_id = format!("player_parse_error_{}", timestamp);
-                let error_dir = "logs/errors";
-                
-                if fs::create_dir_all(error_dir).is_ok() {
-                    let error_file = format!("{}/{}.json", error_dir, error_id);
-                    let error_data = serde_json::json!({
-                        "timestamp": chrono::Utc::now().to_rfc3339(),
-                        "error_id": error_id,
-                        "request": {
-                            "method": "GET",
-                            "url": &url,
-                            "player": name,
-                            "realm": realm
-                        },
-                        "response": {
-                            "body": &response_text,
-                            "body_length": response_text.len(),
-                            "preview": &response_text[..response_text.len().min(500)]
-                        },
-                        "error": {
-                            "message": e.to_string(),
-                            "type": "PLAYER_JSON_PARSE_ERROR"
-                        }
-                    });
-                    
-                    if let Ok(json_str) = serde_json::to_string_pretty(&error_data) {
-                        let _ = fs::write(error_file, json_str);
+                let error_data = serde_json::json!({
+                    "timestamp": chrono::Utc::now().to_rfc3339(),
+                    "error_id": error_id,
+                    "request": {
+                        "method": "GET",
+                        "url": &url,
+                        "player": name,
+                        "realm": realm
+                    },
+                    "response": {
+                        "body": &response_text,
+                        "body_length": response_text.len(),
+                        "preview": &response_text[..response_text.len().min(500)]
+                    },
+                    "error": {
+                        "message": e.to_string(),
+                        "type": "PLAYER_JSON_PARSE_ERROR"
                     }
-                }
-                
+                });
+                self.write_error_file(&format!("{}.json", error_id), &error_data).await;
+
                 error!(
                     error = %e,
                     response_preview = &response_text[..response_text.len().min(500)],
                     error_file = %error_id,
                     "Failed to parse player data JSON response, saved details to logs/errors/{}.json", error_id
                 );
-                error
-            })?;
+                return Err(BotError::Application(format!("Failed to parse player JSON: {}", e)));
+            }
+        };
 
         let scores = player_response
             .mythic_plus_scores_by_season
             .and_then(|seasons| seasons.first().map(|s| s.scores.clone()));
 
+        // raider.io reports spec scores positionally, so label them from the class spec order
+        let spec_name = |index: usize| {
+            player_response
+                .class
+                .as_deref()
+                .and_then(class_spec_names)
+                .and_then(|specs| specs.get(index))
+                .map(|spec| spec.to_string())
+        };
+
+        let guild_realm = player_response.guild.as_ref().and_then(|g| g.realm.as_deref()).map(RealmName::from);
         let player_data = PlayerData {
             name: PlayerName::from(player_response.name),
             realm: RealmName::from(player_response.realm),
@@ -928,8 +1199,9 @@ impl RaiderIOClient {
                     .guild
                     .map(|g| GuildName::from(g.name))
             }),
-            class: player_response.class,
+            guild_realm,
             active_spec_name: player_response.active_spec_name,
+            ilvl: None,
             rio_all: scores.as_ref().and_then(|s| s.all).map(MythicPlusScore::from).unwrap_or(MythicPlusScore::zero()),
             rio_dps: scores.as_ref().and_then(|s| s.dps).map(MythicPlusScore::from).unwrap_or(MythicPlusScore::zero()),
             rio_healer: scores.as_ref().and_then(|s| s.healer).map(MythicPlusScore::from).unwrap_or(MythicPlusScore::zero()),
@@ -938,6 +1210,11 @@ impl RaiderIOClient {
             spec_1: scores.as_ref().and_then(|s| s.spec_1).map(MythicPlusScore::from).unwrap_or(MythicPlusScore::zero()),
             spec_2: scores.as_ref().and_then(|s| s.spec_2).map(MythicPlusScore::from).unwrap_or(MythicPlusScore::zero()),
             spec_3: scores.as_ref().and_then(|s| s.spec_3).map(MythicPlusScore::from).unwrap_or(MythicPlusScore::zero()),
+            spec_0_name: spec_name(0),
+            spec_1_name: spec_name(1),
+            spec_2_name: spec_name(2),
+            spec_3_name: spec_name(3),
+            class: player_response.class,
         };
 
         info!(
@@ -964,6 +1241,12 @@ mod tests {
             api_key: Some("test-key".to_string()),
             base_url: "https://raider.io/api/v1".to_string(),
             timeout_secs: 15,
+            roster_timeout_secs: 60,
+            circuit_breaker_threshold: 5,
+            circuit_breaker_cooldown_secs: 60,
+            unauthenticated_character_limit: 1000,
+            user_agent: "wow-guild-bot-test/1.0".to_string(),
+            player_fields: Vec::new(),
             season: "current".to_string(),
             region: crate::config::Region::Eu,
             default_season: 3,
@@ -971,6 +1254,48 @@ mod tests {
         config
     }
 
+    #[test]
+    fn test_circuit_breaker_transitions() {
+        let breaker = CircuitBreaker::new(3, Duration::from_secs(60));
+        let start = Instant::now();
+
+        // Closed: failures below the threshold still allow requests
+        breaker.record_failure_at(start);
+        breaker.record_failure_at(start);
+        assert_eq!(breaker.state_at(start), CircuitState::Closed);
+        assert!(breaker.allow_request_at(start));
+
+        // Open: the threshold-th consecutive failure short-circuits requests
+        breaker.record_failure_at(start);
+        assert_eq!(breaker.state_at(start), CircuitState::Open);
+        assert!(!breaker.allow_request_at(start + Duration::from_secs(59)));
+
+        // Half-open after the cooldown; a failed probe re-opens immediately
+        let after_cooldown = start + Duration::from_secs(60);
+        assert_eq!(breaker.state_at(after_cooldown), CircuitState::HalfOpen);
+        assert!(breaker.allow_request_at(after_cooldown));
+        breaker.record_failure_at(after_cooldown);
+        assert_eq!(breaker.state_at(after_cooldown), CircuitState::Open);
+
+        // A successful probe closes the breaker and resets the failure count
+        let probe = after_cooldown + Duration::from_secs(60);
+        assert_eq!(breaker.state_at(probe), CircuitState::HalfOpen);
+        breaker.record_success();
+        assert_eq!(breaker.state_at(probe), CircuitState::Closed);
+        breaker.record_failure_at(probe);
+        assert_eq!(breaker.state_at(probe), CircuitState::Closed);
+    }
+
+    #[test]
+    fn test_circuit_breaker_disabled_with_zero_threshold() {
+        let breaker = CircuitBreaker::new(0, Duration::from_secs(60));
+        let now = Instant::now();
+        for _ in 0..20 {
+            breaker.record_failure_at(now);
+        }
+        assert!(breaker.allow_request_at(now));
+    }
+
     #[test]
     fn test_client_creation() {
         let config = create_test_config();
@@ -978,6 +1303,21 @@ mod tests {
         assert!(client.is_ok());
     }
 
+    #[test]
+    fn test_player_fields_match_enabled_set() {
+        let season = Season::from("season-tww-3".to_string());
+        assert_eq!(
+            player_fields(&season, &[]).join(","),
+            "mythic_plus_scores_by_season:season-tww-3,class,active_spec_name"
+        );
+
+        let extra = vec!["gear".to_string(), " class ".to_string(), "".to_string(), "mythic_plus_best_runs".to_string()];
+        assert_eq!(
+            player_fields(&season, &extra).join(","),
+            "mythic_plus_scores_by_season:season-tww-3,class,active_spec_name,gear,mythic_plus_best_runs"
+        );
+    }
+
     #[test]
     fn test_add_api_key() {
         let config = create_test_config();
@@ -992,6 +1332,295 @@ mod tests {
         assert!(result.contains("&access_key=test-key"));
     }
 
+    #[test]
+    fn test_guild_roster_url_includes_access_key() {
+        let config = create_test_config();
+        let client = RaiderIOClient::from_config(&config).unwrap();
+
+        let guild = GuildUrl::new("Tarren Mill", "Thorned Horde");
+        let url = client.guild_roster_url(&guild);
+        assert!(url.starts_with("https://raider.io/api/v1/guilds/profile?"));
+        assert!(url.contains(&format!("{}&fields=members", guild.to_query_string())));
+        assert!(url.ends_with("&access_key=test-key"));
+
+        let mut config = create_test_config();
+        config.raider_io.api_key = None;
+        let client = RaiderIOClient::from_config(&config).unwrap();
+        assert!(!client.guild_roster_url(&guild).contains("access_key"));
+    }
+
+    #[test]
+    fn test_boss_kill_url_includes_access_key() {
+        let config = create_test_config();
+        let client = RaiderIOClient::from_config(&config).unwrap();
+
+        let guild = GuildUrl::new("Tarren Mill", "Thorned Horde");
+        let url = client.boss_kill_url(&guild, "manaforge-omega", "mythic", "plexus-sentinel");
+        assert_eq!(
+            url,
+            "https://raider.io/api/guilds/boss-kills?raid=manaforge-omega&difficulty=mythic&region=eu\
+             &realm=tarren-mill&guild=Thorned%20Horde&boss=plexus-sentinel&access_key=test-key"
+        );
+
+        let mut config = create_test_config();
+        config.raider_io.api_key = None;
+        config.raider_io.base_url = "http://127.0.0.1:8080/api/v1/".to_string();
+        let client = RaiderIOClient::from_config(&config).unwrap();
+        let url = client.boss_kill_url(&guild, "manaforge-omega", "mythic", "plexus-sentinel");
+        assert!(url.starts_with("http://127.0.0.1:8080/api/guilds/boss-kills?"));
+        assert!(!url.contains("access_key"));
+    }
+
+    #[test]
+    fn test_guild_profile_url_uses_guild_region() {
+        let config = create_test_config();
+        let client = RaiderIOClient::from_config(&config).unwrap();
+
+        let us_guild = GuildUrl { region: Some(crate::config::Region::Us), ..GuildUrl::new("Stormrage", "Liquid") };
+        assert!(client.guild_profile_url(&us_guild, "raid_progression").contains("region=us&"));
+
+        // Guilds without a stored region use the configured one
+        let guild = GuildUrl::new("Tarren Mill", "Thorned Horde");
+        assert!(client.guild_profile_url(&guild, "raid_progression").contains("region=eu&"));
+    }
+
+    #[test]
+    fn test_character_profile_url_uses_guild_region() {
+        let client = RaiderIOClient::from_config(&create_test_config()).unwrap();
+        let realm = RealmName::from("Stormrage");
+        let name = PlayerName::from("Liquidone");
+
+        let url = client.character_profile_url(&realm, &name, Some(crate::config::Region::Us));
+        assert!(url.contains("region=us&realm=stormrage&name=Liquidone&"), "{}", url);
+        // Characters without a known guild region use the configured one
+        assert!(client.character_profile_url(&realm, &name, None).contains("region=eu&"));
+    }
+
+    #[test]
+    fn test_roster_request_overrides_timeout() {
+        let mut config = create_test_config();
+        config.raider_io.timeout_secs = 5;
+        config.raider_io.roster_timeout_secs = 90;
+        let client = RaiderIOClient::from_config(&config).unwrap();
+
+        let request = client.request("https://raider.io/api/v1/guilds/profile", Some(client.roster_timeout), "test").build().unwrap();
+        assert_eq!(request.timeout(), Some(&Duration::from_secs(90)));
+
+        let request = client.request("https://raider.io/api/v1/characters/profile", None, "test").build().unwrap();
+        assert_eq!(request.timeout(), None);
+    }
+
+    #[test]
+    fn test_each_request_gets_its_own_request_id() {
+        let client = RaiderIOClient::from_config(&create_test_config()).unwrap();
+        let url = "https://raider.io/api/v1/characters/profile";
+
+        let first = client.request(url, None, &new_request_id()).build().unwrap();
+        let second = client.request(url, None, &new_request_id()).build().unwrap();
+        let id = |request: &reqwest::Request| request.headers()["x-request-id"].to_str().unwrap().to_string();
+
+        assert!(id(&first).starts_with("wow-guild-bot-"));
+        assert_ne!(id(&first), id(&second));
+    }
+
+    #[test]
+    fn test_builder_errors_are_permanent() {
+        let error = Client::new().get("not a url").build().unwrap_err();
+        assert!(error.is_builder());
+        assert!(!is_transient(&error));
+    }
+
+    #[tokio::test]
+    async fn test_save_error_details_writes_dump() {
+        let mut client = RaiderIOClient::from_config(&create_test_config()).unwrap();
+        client.error_dir = std::env::temp_dir().join(format!("wow_guild_bot_errors_{}", Uuid::new_v4()));
+
+        // The directory doesn't exist yet and is created on the first dump
+        let error = BotError::application("boom");
+        client.save_error_details("https://raider.io/api/v1/x", "GET", Some("req-1"), Some("oops".to_string()), &error, 2).await;
+
+        let files: Vec<std::path::PathBuf> = std::fs::read_dir(&client.error_dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect();
+        assert_eq!(files.len(), 1);
+        assert!(files[0].to_string_lossy().ends_with("_attempt_2.json"));
+        let dump: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&files[0]).unwrap()).unwrap();
+        assert_eq!(dump["request"]["request_id"], "req-1");
+        assert_eq!(dump["response"]["body"], "oops");
+
+        std::fs::remove_dir_all(&client.error_dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_connect_errors_are_transient() {
+        // Bind then drop a listener so the port is known to refuse connections
+        let port = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+        let error = Client::new().get(format!("http://127.0.0.1:{}/", port)).send().await.unwrap_err();
+        assert!(is_transient(&error));
+    }
+
+    #[tokio::test]
+    async fn test_timeouts_are_transient() {
+        // Accepts connections but never answers
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let _server = tokio::spawn(async move {
+            let mut held = Vec::new();
+            while let Ok((socket, _)) = listener.accept().await {
+                held.push(socket);
+            }
+        });
+
+        let error = Client::new()
+            .get(format!("http://{}/", addr))
+            .timeout(Duration::from_millis(50))
+            .send()
+            .await
+            .unwrap_err();
+        assert!(error.is_timeout());
+        assert!(is_transient(&error));
+    }
+
+    #[tokio::test]
+    async fn test_guild_without_progression_is_not_started() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        // Serves one 200 response for a guild that exists but hasn't raided
+        let body = r#"{"name": "Fresh Start", "realm": "Tarren Mill", "raid_progression": {}, "raid_rankings": {}}"#;
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = [0u8; 4096];
+            let _ = socket.read(&mut request).await;
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            socket.write_all(response.as_bytes()).await.unwrap();
+        });
+
+        let mut config = create_test_config();
+        config.raider_io.base_url = format!("http://{}", addr);
+        let client = RaiderIOClient::from_config(&config).unwrap();
+        let guild_url = GuildUrl::new(RealmName::from("Tarren Mill"), GuildName::from("Fresh Start"));
+
+        let result = client.fetch_guild_data(&guild_url, RaidTier::manaforge_omega()).await.unwrap();
+        let GuildFetchResult::Found(guild) = result else {
+            panic!("expected the guild to be found, got {:?}", result);
+        };
+        assert_eq!(guild.progress, None);
+        assert_eq!(guild.progress_label(), "Not started");
+        assert_eq!((guild.best_percent, guild.pull_count), (0.0, None));
+    }
+
+    #[test]
+    fn test_current_boss_is_the_next_unkilled_boss() {
+        let progression = |progress: &str| progress.parse::<Progression>().unwrap();
+
+        // 5/8 M in Manaforge Omega: the 6th boss is being progressed
+        assert_eq!(
+            RaiderIOClient::current_boss(RaidTier::manaforge_omega(), &progression("5/8 M")).as_deref(),
+            Some("Fractillus")
+        );
+        assert_eq!(
+            RaiderIOClient::current_boss(RaidTier::liberation_of_undermine(), &progression("7/8 H")).as_deref(),
+            Some("Chrome King Gallywix")
+        );
+        assert_eq!(
+            RaiderIOClient::current_boss(RaidTier::liberation_of_undermine(), &progression("5/8 M")).as_deref(),
+            Some("One-Armed Bandit")
+        );
+        assert_eq!(RaiderIOClient::current_boss(RaidTier::manaforge_omega(), &progression("8/8 M")), None);
+        // Boss order unknown for this tier
+        assert_eq!(RaiderIOClient::current_boss(RaidTier::nerubar_palace(), &progression("3/8 M")), None);
+    }
+
+    fn guild_response(json: &str) -> RaiderIOGuildResponse {
+        serde_json::from_str(json).expect("valid guild response")
+    }
+
+    #[test]
+    fn test_progress_for_raid_found() {
+        let guild = guild_response(r#"{
+            "name": "Thorned Horde",
+            "realm": "Tarren Mill",
+            "raid_progression": {"manaforge-omega": {"summary": "5/8 M"}},
+            "raid_rankings": {"manaforge-omega": {"mythic": {"world": 925}}}
+        }"#);
+
+        let (progress, rank) = RaiderIOClient::progress_for_raid(&guild, "manaforge-omega").unwrap();
+        assert_eq!(progress, "5/8 M");
+        assert_eq!(rank, Some(WorldRank::new(925)));
+    }
+
+    #[test]
+    fn test_progress_for_raid_no_progression() {
+        let guild = guild_response(r#"{
+            "name": "Thorned Horde",
+            "realm": "Tarren Mill",
+            "raid_progression": {"liberation-of-undermine": {"summary": "8/8 H"}},
+            "raid_rankings": {}
+        }"#);
+
+        assert!(RaiderIOClient::progress_for_raid(&guild, "manaforge-omega").is_none());
+    }
+
+    #[test]
+    fn test_progress_for_raid_zero_rank_is_unranked() {
+        let guild = guild_response(r#"{
+            "name": "Thorned Horde",
+            "realm": "Tarren Mill",
+            "raid_progression": {"manaforge-omega": {"summary": "1/8 M"}},
+            "raid_rankings": {"manaforge-omega": {"mythic": {"world": 0}}}
+        }"#);
+
+        let (_, rank) = RaiderIOClient::progress_for_raid(&guild, "manaforge-omega").unwrap();
+        assert_eq!(rank, None);
+    }
+
+    #[test]
+    fn test_progress_for_raid_without_ranking() {
+        let guild = guild_response(r#"{
+            "name": "Thorned Horde",
+            "realm": "Tarren Mill",
+            "raid_progression": {"manaforge-omega": {"summary": "3/8 H"}},
+            "raid_rankings": {}
+        }"#);
+
+        let (progress, rank) = RaiderIOClient::progress_for_raid(&guild, "manaforge-omega").unwrap();
+        assert_eq!(progress, "3/8 H");
+        assert_eq!(rank, None);
+    }
+
+    #[test]
+    fn test_progress_for_raid_missing_rankings_section() {
+        let guild = guild_response(r#"{
+            "name": "Thorned Horde",
+            "realm": "Tarren Mill",
+            "raid_progression": {"manaforge-omega": {"summary": "6/8 M"}}
+        }"#);
+
+        let (progress, rank) = RaiderIOClient::progress_for_raid(&guild, "manaforge-omega").unwrap();
+        assert_eq!(progress, "6/8 M");
+        assert_eq!(rank, None);
+    }
+
+    #[test]
+    fn test_progress_for_raid_tolerates_changed_ranking_shape() {
+        let guild = guild_response(r#"{
+            "name": "Thorned Horde",
+            "raid_progression": {"manaforge-omega": {"summary": "2/8 M", "total_bosses": 8}},
+            "raid_rankings": {"manaforge-omega": {"heroic": {"world": 12}}}
+        }"#);
+
+        let (progress, rank) = RaiderIOClient::progress_for_raid(&guild, "manaforge-omega").unwrap();
+        assert_eq!(progress, "2/8 M");
+        assert_eq!(rank, None);
+    }
+
     #[test]
     fn test_raid_name_mapping() {
         assert_eq!(RaiderIOClient::get_raid_name(RaidTier::from(1)).unwrap(), "nerubar-palace");
@@ -0,0 +1,256 @@
+/// Sending command responses, abstracted so the follow-up logic can be tested without Discord
+use serenity::async_trait;
+use serenity::http::Http;
+use serenity::model::application::interaction::application_command::ApplicationCommandInteraction;
+use serenity::model::id::MessageId;
+use crate::error::Result;
+
+/// Destination for the follow-up messages of a deferred command
+#[async_trait]
+pub trait Responder {
+    /// Send one follow-up message, returning its id
+    async fn follow_up(&self, content: &str) -> Result<MessageId>;
+
+    /// Replace the content of a follow-up sent earlier
+    async fn edit_follow_up(&self, message_id: MessageId, content: &str) -> Result<()>;
+}
+
+/// Sends follow-ups to a deferred slash command interaction
+pub struct InteractionResponder<'a> {
+    http: &'a Http,
+    command: &'a ApplicationCommandInteraction,
+}
+
+impl<'a> InteractionResponder<'a> {
+    pub fn new(http: &'a Http, command: &'a ApplicationCommandInteraction) -> Self {
+        Self { http, command }
+    }
+}
+
+#[async_trait]
+impl Responder for InteractionResponder<'_> {
+    async fn follow_up(&self, content: &str) -> Result<MessageId> {
+        let message = self
+            .command
+            .create_followup_message(self.http, |response| response.content(content))
+            .await?;
+        Ok(message.id)
+    }
+
+    async fn edit_follow_up(&self, message_id: MessageId, content: &str) -> Result<()> {
+        self.command
+            .edit_followup_message(self.http, message_id, |response| response.content(content))
+            .await?;
+        Ok(())
+    }
+}
+
+/// Longest message Discord accepts, in characters
+pub const DISCORD_MESSAGE_LIMIT: usize = 2000;
+
+const CODE_FENCE: &str = "```";
+
+/// Split a message that is over Discord's limit at line boundaries. A code block that
+/// spans a split is closed and reopened, so table chunks keep their monospace layout;
+/// a single line longer than the limit is cut mid-line. Messages within the limit come
+/// back unchanged.
+pub fn split_to_discord_limit(message: &str) -> Vec<String> {
+    if message.chars().count() <= DISCORD_MESSAGE_LIMIT {
+        return vec![message.to_string()];
+    }
+
+    // Room for a reopened fence ("```\n") and a closing one ("\n```") around any piece
+    let max_piece = DISCORD_MESSAGE_LIMIT - 2 * (CODE_FENCE.len() + 1);
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+    let mut in_code_block = false;
+
+    for line in message.split_inclusive('\n') {
+        let chars: Vec<char> = line.chars().collect();
+        for piece in chars.chunks(max_piece) {
+            let piece: String = piece.iter().collect();
+            let closing = if in_code_block { CODE_FENCE.len() + 1 } else { 0 };
+            if !current.is_empty() && current.chars().count() + piece.chars().count() + closing > DISCORD_MESSAGE_LIMIT {
+                if in_code_block {
+                    if !current.ends_with('\n') {
+                        current.push('\n');
+                    }
+                    current.push_str(CODE_FENCE);
+                }
+                chunks.push(std::mem::take(&mut current));
+                if in_code_block {
+                    current = format!("{}\n", CODE_FENCE);
+                }
+            }
+            if piece.matches(CODE_FENCE).count() % 2 == 1 {
+                in_code_block = !in_code_block;
+            }
+            current.push_str(&piece);
+        }
+    }
+    if !current.trim().is_empty() {
+        chunks.push(current);
+    }
+    chunks
+}
+
+/// Send every message as its own follow-up, in order, splitting any that are over
+/// Discord's limit. Stops at the first failure so later chunks of a split table aren't
+/// posted without the ones before them.
+pub async fn send_followups(responder: &(impl Responder + Sync), messages: &[String]) -> Result<()> {
+    for message in messages {
+        for chunk in split_to_discord_limit(message) {
+            responder.follow_up(&chunk).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Replace a placeholder follow-up (e.g. "Fetching data...") with the first message and
+/// send the rest as new follow-ups, so the result takes the placeholder's place
+pub async fn edit_followups(responder: &(impl Responder + Sync), placeholder: MessageId, messages: &[String]) -> Result<()> {
+    let chunks: Vec<String> = messages.iter().flat_map(|message| split_to_discord_limit(message)).collect();
+    let Some((first, rest)) = chunks.split_first() else {
+        return Ok(());
+    };
+    responder.edit_follow_up(placeholder, first).await?;
+    send_followups(responder, rest).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::BotError;
+    use std::sync::Mutex;
+
+    /// Test double that records sent messages, optionally failing from the nth send
+    #[derive(Default)]
+    struct RecordingResponder {
+        sent: Mutex<Vec<String>>,
+        fail_at: Option<usize>,
+    }
+
+    /// Message ids are positions in `sent`
+    #[async_trait]
+    impl Responder for RecordingResponder {
+        async fn follow_up(&self, content: &str) -> Result<MessageId> {
+            let mut sent = self.sent.lock().unwrap();
+            if self.fail_at == Some(sent.len()) {
+                return Err(BotError::application("send failed"));
+            }
+            sent.push(content.to_string());
+            Ok(MessageId(sent.len() as u64 - 1))
+        }
+
+        async fn edit_follow_up(&self, message_id: MessageId, content: &str) -> Result<()> {
+            let mut sent = self.sent.lock().unwrap();
+            let message = sent
+                .get_mut(message_id.0 as usize)
+                .ok_or_else(|| BotError::application("unknown message"))?;
+            *message = content.to_string();
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_send_followups_sends_every_chunk_in_order() {
+        let responder = RecordingResponder::default();
+        let messages = vec!["first".to_string(), "second".to_string(), "third".to_string()];
+
+        send_followups(&responder, &messages).await.unwrap();
+        assert_eq!(*responder.sent.lock().unwrap(), messages);
+    }
+
+    #[tokio::test]
+    async fn test_send_followups_stops_at_first_failure() {
+        let responder = RecordingResponder { fail_at: Some(1), ..Default::default() };
+        let messages = vec!["first".to_string(), "second".to_string(), "third".to_string()];
+
+        assert!(send_followups(&responder, &messages).await.is_err());
+        assert_eq!(*responder.sent.lock().unwrap(), vec!["first".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_edit_followups_replaces_placeholder() {
+        let responder = RecordingResponder::default();
+        let placeholder = responder.follow_up("Fetching data for 62 guilds...").await.unwrap();
+        let messages = vec!["table part 1".to_string(), "table part 2".to_string()];
+
+        edit_followups(&responder, placeholder, &messages).await.unwrap();
+        // The first chunk takes the placeholder's place; the rest follow it
+        assert_eq!(*responder.sent.lock().unwrap(), messages);
+
+        // An unknown placeholder fails before anything new is sent
+        let responder = RecordingResponder::default();
+        assert!(edit_followups(&responder, MessageId(5), &messages).await.is_err());
+        assert!(responder.sent.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_split_to_discord_limit_keeps_code_blocks_closed() {
+        let short = "**Header**\n```\nrow\n```".to_string();
+        assert_eq!(split_to_discord_limit(&short), vec![short.clone()]);
+
+        let rows: String = (0..60).map(|i| format!("#{:<3} {}\n", i, "Very Long Guild Name ".repeat(3))).collect();
+        let message = format!("**Header**\n```\n{}```", rows);
+        assert!(message.chars().count() > DISCORD_MESSAGE_LIMIT);
+
+        let chunks = split_to_discord_limit(&message);
+        assert!(chunks.len() > 1);
+        for chunk in &chunks {
+            assert!(chunk.chars().count() <= DISCORD_MESSAGE_LIMIT);
+            assert_eq!(chunk.matches("```").count() % 2, 0, "unbalanced fence in {:?}", chunk);
+        }
+        // Every row survives, in order
+        let rejoined: Vec<&str> = chunks.iter().flat_map(|chunk| chunk.lines()).filter(|line| line.starts_with('#')).collect();
+        assert_eq!(rejoined, rows.lines().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_split_to_discord_limit_cuts_overlong_line() {
+        let line = "ж".repeat(4500);
+        let chunks = split_to_discord_limit(&line);
+        assert_eq!(chunks.len(), 3);
+        assert!(chunks.iter().all(|chunk| chunk.chars().count() <= DISCORD_MESSAGE_LIMIT));
+        assert_eq!(chunks.concat(), line);
+    }
+
+    #[tokio::test]
+    async fn test_send_followups_splits_over_limit_message() {
+        let responder = RecordingResponder::default();
+        let message = "line of text\n".repeat(200);
+
+        send_followups(&responder, std::slice::from_ref(&message)).await.unwrap();
+        let sent = responder.sent.lock().unwrap();
+        assert_eq!(sent.len(), 2);
+        assert_eq!(sent.concat(), message);
+    }
+
+    #[tokio::test]
+    async fn test_chunked_guild_list_is_sent_as_multiple_followups() {
+        use crate::raider_io::GuildData;
+        use crate::types::{GuildName, RealmName, WorldRank};
+
+        let guilds: Vec<GuildData> = (0..62)
+            .map(|i| GuildData {
+                name: GuildName::from(format!("Guild {}", i)),
+                realm: RealmName::from("Tarren Mill"),
+                progress: Some("5/8 M".to_string()),
+                rank: Some(WorldRank::new(1000 + i)),
+                best_percent: 42.5,
+                pull_count: Some(150),
+                defeated_at: None,
+                current_boss: None,
+            })
+            .collect();
+        let messages = crate::guild_data::format_guild_list(&guilds, None, true);
+
+        let responder = RecordingResponder::default();
+        send_followups(&responder, &messages).await.unwrap();
+
+        let sent = responder.sent.lock().unwrap();
+        assert!(sent.len() > 1);
+        assert_eq!(*sent, messages);
+        assert!(sent.iter().all(|message| message.chars().count() < 2000));
+    }
+}
@@ -0,0 +1,108 @@
+//! Monospace table layout for the code-block tables in /guilds and /rank
+
+/// A table column: its title and the widest it may grow before cells are truncated
+#[derive(Debug, Clone, Copy)]
+pub struct Column {
+    pub title: &'static str,
+    pub max_width: usize,
+}
+
+impl Column {
+    pub const fn new(title: &'static str, max_width: usize) -> Self {
+        Self { title, max_width }
+    }
+}
+
+/// Column widths fitted to the rows being rendered
+#[derive(Debug, Clone)]
+pub struct TableLayout {
+    columns: Vec<(&'static str, usize)>,
+}
+
+impl TableLayout {
+    /// Size each column to its longest cell, never narrower than its title and
+    /// never wider than its `max_width`
+    pub fn fit(columns: &[Column], rows: &[Vec<String>]) -> Self {
+        let columns = columns
+            .iter()
+            .enumerate()
+            .map(|(i, column)| {
+                let widest = rows
+                    .iter()
+                    .filter_map(|row| row.get(i))
+                    .map(|cell| cell.chars().count())
+                    .max()
+                    .unwrap_or(0);
+                let title_width = column.title.chars().count();
+                (column.title, widest.min(column.max_width).max(title_width))
+            })
+            .collect();
+        Self { columns }
+    }
+
+    /// Title line and underline, each ending in a newline
+    pub fn header(&self) -> String {
+        let titles: Vec<String> = self.columns.iter().map(|(title, _)| title.to_string()).collect();
+        let rules: Vec<String> = self.columns.iter().map(|(_, width)| "─".repeat(*width)).collect();
+        format!("{}{}", self.row(&titles), rules.join(" ") + "\n")
+    }
+
+    /// One row with every cell fitted to its column; the last column isn't padded
+    pub fn row(&self, cells: &[String]) -> String {
+        let last = self.columns.len().saturating_sub(1);
+        let cells: Vec<String> = self
+            .columns
+            .iter()
+            .zip(cells)
+            .enumerate()
+            .map(|(i, ((_, width), cell))| {
+                let cell = truncate(cell, *width);
+                if i == last { cell } else { pad(&cell, *width) }
+            })
+            .collect();
+        cells.join(" ") + "\n"
+    }
+}
+
+/// Cut `s` to `width` characters, marking the cut with "..."
+fn truncate(s: &str, width: usize) -> String {
+    if s.chars().count() <= width {
+        return s.to_string();
+    }
+    let kept: String = s.chars().take(width.saturating_sub(3)).collect();
+    format!("{}...", kept)
+}
+
+fn pad(s: &str, width: usize) -> String {
+    let len = s.chars().count();
+    format!("{}{}", s, " ".repeat(width.saturating_sub(len)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COLUMNS: &[Column] = &[Column::new("Rank", 4), Column::new("Name", 12), Column::new("Score", 9)];
+
+    fn row(cells: &[&str]) -> Vec<String> {
+        cells.iter().map(|cell| cell.to_string()).collect()
+    }
+
+    #[test]
+    fn test_short_rows_get_tighter_columns() {
+        let short = TableLayout::fit(COLUMNS, &[row(&["#1", "Ann", "2500"])]);
+        let long = TableLayout::fit(COLUMNS, &[row(&["#1", "Annabelle", "2500"])]);
+
+        // Never narrower than the title
+        assert_eq!(short.row(&row(&["#1", "Ann", "2500"])), "#1   Ann  2500\n");
+        assert_eq!(long.row(&row(&["#1", "Ann", "2500"])), "#1   Ann       2500\n");
+        assert_eq!(short.header(), "Rank Name Score\n──── ──── ─────\n");
+    }
+
+    #[test]
+    fn test_cells_are_capped_at_max_width() {
+        let rows = [row(&["#1", "Володимир Великий", "2500"])];
+        let layout = TableLayout::fit(COLUMNS, &rows);
+        assert_eq!(layout.row(&rows[0]), "#1   Володимир... 2500\n");
+    }
+}
@@ -1,4 +1,6 @@
 /// Strong types for better type safety and API clarity
+use crate::config::Region;
+use crate::error::BotError;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::ops::Deref;
@@ -8,6 +10,18 @@ use std::str::FromStr;
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct GuildName(String);
 
+/// Realms whose raider.io slug isn't their normalized name with apostrophes removed,
+/// mostly accented and parenthesized EU realm names
+const REALM_SLUGS: &[(&str, &str)] = &[
+    ("aggra-(português)", "aggra-portugues"),
+    ("azjol-nerub", "azjolnerub"),
+    ("chants-éternels", "chants-eternels"),
+    ("confrérie-du-thorium", "confrerie-du-thorium"),
+    ("la-croisade-écarlate", "la-croisade-ecarlate"),
+    ("marécage-de-zangar", "marecage-de-zangar"),
+    ("pozzo-dell'eternità", "pozzo-delleternita"),
+];
+
 /// A realm name with validation
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct RealmName(String);
@@ -40,11 +54,19 @@ pub struct MythicPlusScore(f64);
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct WorldRank(u32);
 
+/// A Discord snowflake (server, channel, role or user ID). Config files give these as
+/// integers, environment variables as strings; both deserialize, and anything else
+/// fails when the config is loaded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub struct DiscordId(u64);
+
 /// Guild URL parameters for raider.io API
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct GuildUrl {
     pub realm: RealmName,
     pub name: GuildName,
+    /// The guild's region; `None` uses the configured `raider_io.region`
+    pub region: Option<Region>,
 }
 
 /// A unique identifier for a player (realm + name)
@@ -143,6 +165,15 @@ impl RealmName {
         &self.0
     }
 
+    /// The slug raider.io expects in API URLs, e.g. "kiljaeden" for Kil'jaeden. Known
+    /// irregular realms come from `REALM_SLUGS`; otherwise apostrophes are dropped.
+    pub fn realm_slug(&self) -> String {
+        match REALM_SLUGS.iter().find(|(name, _)| *name == self.0) {
+            Some((_, slug)) => slug.to_string(),
+            None => self.0.replace('\'', ""),
+        }
+    }
+
     /// Returns the realm name formatted for display with proper capitalization and spaces
     pub fn display_name(&self) -> String {
         self.0
@@ -163,6 +194,22 @@ impl RealmName {
     }
 }
 
+/// Realm display names for one render pass. Tables repeat a handful of realms across
+/// hundreds of rows, so each realm is title-cased once instead of once per row.
+#[derive(Debug, Default)]
+pub struct RealmDisplayNames {
+    names: std::collections::HashMap<RealmName, String>,
+}
+
+impl RealmDisplayNames {
+    pub fn get(&mut self, realm: &RealmName) -> &str {
+        if !self.names.contains_key(realm) {
+            self.names.insert(realm.clone(), realm.display_name());
+        }
+        &self.names[realm]
+    }
+}
+
 impl Deref for RealmName {
     type Target = str;
 
@@ -269,6 +316,42 @@ impl From<&str> for PlayerName {
     }
 }
 
+// Implementations for WowClass
+impl WowClass {
+    /// Every playable class, in canonical spelling
+    pub fn all() -> impl Iterator<Item = WowClass> {
+        CLASS_SPECS.iter().map(|(name, _)| Self(name.to_string()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Spec names in raider.io's `spec_N` slot order
+    pub fn spec_names(&self) -> &'static [&'static str] {
+        class_spec_names(&self.0).unwrap_or(&[])
+    }
+
+    /// Whether a class name from raider.io data is this class (case-insensitive)
+    pub fn matches(&self, class: &str) -> bool {
+        self.0.eq_ignore_ascii_case(class.trim())
+    }
+}
+
+impl fmt::Display for WowClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for WowClass {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::all().find(|class| class.matches(s)).ok_or("Unknown class")
+    }
+}
+
 // Implementations for Season
 impl Season {
     pub fn new(season: impl Into<String>) -> Self {
@@ -286,6 +369,22 @@ impl Season {
     pub fn previous() -> Self {
         Self("previous".to_string())
     }
+
+    /// The Mythic+ season that runs alongside a raid tier, so player scores and guild
+    /// raid progress refer to the same season:
+    /// - 1 (Nerub-ar Palace) -> `season-tww-1`
+    /// - 2 (Liberation of Undermine) -> `season-tww-2`
+    /// - 3 (Manaforge Omega) -> `season-tww-3`
+    ///
+    /// Tiers without a known season map to `current`.
+    pub fn for_tier(tier: RaidTier) -> Self {
+        match tier.value() {
+            1 => Self::from("season-tww-1"),
+            2 => Self::from("season-tww-2"),
+            3 => Self::from("season-tww-3"),
+            _ => Self::current(),
+        }
+    }
 }
 
 impl fmt::Display for Season {
@@ -323,6 +422,16 @@ impl MythicPlusScore {
     pub fn zero() -> Self {
         Self(0.0)
     }
+
+    /// Format for display: one decimal place, with a trailing ".0" dropped (2847.0 -> "2847")
+    pub fn display_rounded(&self) -> String {
+        // Round on the scaled value so halves like 2847.35 round up despite binary representation
+        let formatted = format!("{:.1}", (self.0 * 10.0).round() / 10.0);
+        match formatted.strip_suffix(".0") {
+            Some(whole) => whole.to_string(),
+            None => formatted,
+        }
+    }
 }
 
 impl PartialOrd<f64> for MythicPlusScore {
@@ -386,6 +495,11 @@ impl WorldRank {
         Self(rank)
     }
 
+    /// A rank reported by raider.io, where 0 means "unranked"
+    pub fn ranked(rank: u32) -> Option<Self> {
+        (rank > 0).then_some(Self(rank))
+    }
+
     pub fn value(&self) -> u32 {
         self.0
     }
@@ -403,16 +517,100 @@ impl From<u32> for WorldRank {
     }
 }
 
+// Implementations for DiscordId
+impl DiscordId {
+    pub fn value(&self) -> u64 {
+        self.0
+    }
+}
+
+impl fmt::Display for DiscordId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<u64> for DiscordId {
+    fn from(id: u64) -> Self {
+        Self(id)
+    }
+}
+
+impl FromStr for DiscordId {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().parse::<u64>() {
+            Ok(id) if id > 0 => Ok(Self(id)),
+            _ => Err(format!("invalid Discord ID '{}'", s)),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for DiscordId {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct DiscordIdVisitor;
+
+        impl serde::de::Visitor<'_> for DiscordIdVisitor {
+            type Value = DiscordId;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a Discord ID as an integer or a string of digits")
+            }
+
+            fn visit_u64<E: serde::de::Error>(self, id: u64) -> Result<DiscordId, E> {
+                if id == 0 {
+                    return Err(E::custom("invalid Discord ID '0'"));
+                }
+                Ok(DiscordId(id))
+            }
+
+            fn visit_i64<E: serde::de::Error>(self, id: i64) -> Result<DiscordId, E> {
+                let id = u64::try_from(id).map_err(|_| E::custom(format!("invalid Discord ID '{}'", id)))?;
+                self.visit_u64(id)
+            }
+
+            fn visit_str<E: serde::de::Error>(self, id: &str) -> Result<DiscordId, E> {
+                id.parse().map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_any(DiscordIdVisitor)
+    }
+}
+
 // Implementations for RaidTier
 impl RaidTier {
+    /// Tiers the bot knows raid names for
+    pub const KNOWN: std::ops::RangeInclusive<u8> = 1..=3;
+
     pub fn new(tier: u8) -> Self {
         Self(tier)
     }
 
+    /// Checked constructor for user input; rejects tiers outside `KNOWN`
+    pub fn try_new(tier: u8) -> crate::error::Result<Self> {
+        if Self::KNOWN.contains(&tier) {
+            Ok(Self(tier))
+        } else {
+            Err(BotError::invalid_input(format!(
+                "Season must be {}, got {}",
+                Self::KNOWN.map(|t| t.to_string()).collect::<Vec<_>>().join(", "),
+                tier
+            )))
+        }
+    }
+
     pub fn value(&self) -> u8 {
         self.0
     }
 
+    /// The tier before this one, if the bot knows it
+    pub fn previous(&self) -> Option<Self> {
+        let previous = self.0.checked_sub(1)?;
+        Self::KNOWN.contains(&previous).then_some(Self(previous))
+    }
+
     pub fn nerubar_palace() -> Self {
         Self(1)
     }
@@ -460,12 +658,62 @@ impl GuildUrl {
         Self {
             realm: realm.into(),
             name: name.into(),
+            region: None,
+        }
+    }
+
+    /// Parse a guild entry: a `realm=...&name=...[&region=...]` query (also accepting
+    /// `guild=` for the name, percent-encoding, and a full URL with a `?`), or `Realm/Guild`.
+    /// The error says why the entry was rejected.
+    pub fn parse(input: &str) -> std::result::Result<Self, String> {
+        const EXPECTED: &str = "expected realm=...&name=... or Realm/Guild";
+
+        let input = input.trim();
+        if !input.contains('=') {
+            let (realm, name) = input.split_once('/').ok_or(EXPECTED)?;
+            let (realm, name) = (realm.trim(), name.trim());
+            if realm.is_empty() || name.is_empty() {
+                return Err(EXPECTED.to_string());
+            }
+            return Ok(Self::new(realm, name));
+        }
+
+        let query = input.split_once('?').map_or(input, |(_, query)| query);
+        let mut realm = None;
+        let mut name = None;
+        let mut region = None;
+
+        for part in query.split('&') {
+            let Some((key, value)) = part.split_once('=') else {
+                continue;
+            };
+            let value = urlencoding::decode(&value.replace('+', " "))
+                .map(|decoded| decoded.trim().to_string())
+                .unwrap_or_else(|_| value.trim().to_string());
+            if value.is_empty() {
+                continue;
+            }
+            match key.trim() {
+                "realm" => realm = Some(RealmName::from(value)),
+                "name" | "guild" => name = Some(GuildName::from(value)),
+                "region" => {
+                    region = Some(Region::from_code(&value).ok_or_else(|| {
+                        format!("unknown region '{}' (expected us, eu, kr, tw or cn)", value)
+                    })?)
+                }
+                _ => {}
+            }
+        }
+
+        match (realm, name) {
+            (Some(realm), Some(name)) => Ok(Self { realm, name, region }),
+            _ => Err(EXPECTED.to_string()),
         }
     }
 
     pub fn to_query_string(&self) -> String {
         // URL encode the guild name to handle spaces and special characters
-        let realm_string = self.realm.to_string();
+        let realm_string = self.realm.realm_slug();
         let name_string = self.name.to_string();
         let encoded_realm = urlencoding::encode(&realm_string);
         let encoded_name = urlencoding::encode(&name_string);
@@ -490,6 +738,19 @@ impl fmt::Display for RaidDifficulty {
     }
 }
 
+impl FromStr for RaidDifficulty {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "normal" => Ok(RaidDifficulty::Normal),
+            "heroic" => Ok(RaidDifficulty::Heroic),
+            "mythic" => Ok(RaidDifficulty::Mythic),
+            _ => Err(format!("Unknown raid difficulty '{}'", s)),
+        }
+    }
+}
+
 // Implementations for Role
 impl fmt::Display for Role {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -501,10 +762,122 @@ impl fmt::Display for Role {
     }
 }
 
+impl Role {
+    /// The role a specialization plays; spec names are unambiguous across classes
+    pub fn for_spec(spec: &str) -> Self {
+        match spec.trim().to_lowercase().as_str() {
+            "blood" | "vengeance" | "guardian" | "brewmaster" | "protection" => Role::Tank,
+            "restoration" | "preservation" | "mistweaver" | "holy" | "discipline" => Role::Healer,
+            _ => Role::Dps,
+        }
+    }
+}
+
+/// Raid composition role, splitting damage dealers into melee and ranged
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CombatRole {
+    Tank,
+    Healer,
+    Melee,
+    Ranged,
+}
+
+impl CombatRole {
+    /// Role of one of `class`'s specializations; `None` if the spec isn't one of the class's
+    pub fn for_spec(class: &WowClass, spec: &str) -> Option<Self> {
+        let spec = class.spec_names().iter().find(|name| name.eq_ignore_ascii_case(spec.trim()))?;
+        Some(match Role::for_spec(spec) {
+            Role::Tank => CombatRole::Tank,
+            Role::Healer => CombatRole::Healer,
+            Role::Dps if MELEE_SPECS.contains(&(class.as_str(), *spec)) => CombatRole::Melee,
+            Role::Dps => CombatRole::Ranged,
+        })
+    }
+}
+
+/// Damage specs that fight in melee range; every other damage spec is ranged
+const MELEE_SPECS: &[(&str, &str)] = &[
+    ("Death Knight", "Frost"),
+    ("Death Knight", "Unholy"),
+    ("Demon Hunter", "Havoc"),
+    ("Druid", "Feral"),
+    ("Hunter", "Survival"),
+    ("Monk", "Windwalker"),
+    ("Paladin", "Retribution"),
+    ("Rogue", "Assassination"),
+    ("Rogue", "Outlaw"),
+    ("Rogue", "Subtlety"),
+    ("Shaman", "Enhancement"),
+    ("Warrior", "Arms"),
+    ("Warrior", "Fury"),
+];
+
+/// Specializations per class in raider.io's `spec_0..spec_3` order
+const CLASS_SPECS: &[(&str, &[&str])] = &[
+    ("Death Knight", &["Blood", "Frost", "Unholy"]),
+    ("Demon Hunter", &["Havoc", "Vengeance"]),
+    ("Druid", &["Balance", "Feral", "Guardian", "Restoration"]),
+    ("Evoker", &["Devastation", "Preservation", "Augmentation"]),
+    ("Hunter", &["Beast Mastery", "Marksmanship", "Survival"]),
+    ("Mage", &["Arcane", "Fire", "Frost"]),
+    ("Monk", &["Brewmaster", "Windwalker", "Mistweaver"]),
+    ("Paladin", &["Holy", "Protection", "Retribution"]),
+    ("Priest", &["Discipline", "Holy", "Shadow"]),
+    ("Rogue", &["Assassination", "Outlaw", "Subtlety"]),
+    ("Shaman", &["Elemental", "Enhancement", "Restoration"]),
+    ("Warlock", &["Affliction", "Demonology", "Destruction"]),
+    ("Warrior", &["Arms", "Fury", "Protection"]),
+];
+
+/// Spec names for a class (case-insensitive), ordered by `spec_N` slot
+pub fn class_spec_names(class: &str) -> Option<&'static [&'static str]> {
+    let class = class.trim();
+    CLASS_SPECS
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(class))
+        .map(|(_, specs)| *specs)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_raid_tier_try_new() {
+        for tier in 1..=3 {
+            assert_eq!(RaidTier::try_new(tier).unwrap().value(), tier);
+        }
+        assert!(RaidTier::try_new(0).is_err());
+        assert!(RaidTier::try_new(4).is_err());
+        assert!(RaidTier::try_new(255).is_err());
+    }
+
+    #[test]
+    fn test_raid_tier_previous() {
+        assert_eq!(RaidTier::manaforge_omega().previous(), Some(RaidTier::liberation_of_undermine()));
+        assert_eq!(RaidTier::nerubar_palace().previous(), None);
+        assert_eq!(RaidTier::new(0).previous(), None);
+    }
+
+    #[test]
+    fn test_role_for_spec() {
+        assert_eq!(Role::for_spec("Protection"), Role::Tank);
+        assert_eq!(Role::for_spec("holy"), Role::Healer);
+        assert_eq!(Role::for_spec("Mistweaver"), Role::Healer);
+        assert_eq!(Role::for_spec("Frost"), Role::Dps);
+        assert_eq!(Role::for_spec("Augmentation"), Role::Dps);
+    }
+
+    #[test]
+    fn test_combat_role_for_spec() {
+        let class = |name: &str| name.parse::<WowClass>().unwrap();
+        assert_eq!(CombatRole::for_spec(&class("Death Knight"), "Frost"), Some(CombatRole::Melee));
+        assert_eq!(CombatRole::for_spec(&class("mage"), "frost"), Some(CombatRole::Ranged));
+        assert_eq!(CombatRole::for_spec(&class("Warrior"), "Protection"), Some(CombatRole::Tank));
+        assert_eq!(CombatRole::for_spec(&class("Evoker"), "Preservation"), Some(CombatRole::Healer));
+        assert_eq!(CombatRole::for_spec(&class("Mage"), "Holy"), None);
+    }
+
     #[test]
     fn test_player_name_normalization() {
         assert_eq!(PlayerName::new("testplayer").as_str(), "Testplayer");
@@ -518,15 +891,126 @@ mod tests {
         assert_eq!(RealmName::new("TARREN MILL").as_str(), "tarren-mill");
     }
 
+    #[test]
+    fn test_realm_slug() {
+        assert_eq!(RealmName::new("Tarren Mill").realm_slug(), "tarren-mill");
+        assert_eq!(RealmName::new("Kil'jaeden").realm_slug(), "kiljaeden");
+        assert_eq!(RealmName::new("Pozzo dell'Eternità").realm_slug(), "pozzo-delleternita");
+        assert_eq!(RealmName::new("Aggra (Português)").realm_slug(), "aggra-portugues");
+
+        // The display name keeps the original spelling
+        assert_eq!(RealmName::new("Pozzo dell'Eternità").display_name(), "Pozzo Dell'eternità");
+    }
+
+    #[test]
+    fn test_realm_display_names_computed_once_per_realm() {
+        let realms = [RealmName::from("Tarren Mill"), RealmName::from("Silvermoon"), RealmName::from("Howling Fjord")];
+        let mut display_names = RealmDisplayNames::default();
+
+        // 300 rows over 3 realms title-case 3 names rather than 300
+        for i in 0..300 {
+            let realm = &realms[i % realms.len()];
+            assert_eq!(display_names.get(realm), realm.display_name());
+        }
+        assert_eq!(display_names.names.len(), 3);
+        assert_eq!(display_names.get(&realms[0]), "Tarren Mill");
+    }
+
     #[test]
     fn test_guild_url_query_string() {
         let guild_url = GuildUrl::new("tarren-mill", "Test Guild");
         assert_eq!(guild_url.to_query_string(), "realm=tarren-mill&name=Test Guild");
     }
 
+    #[test]
+    fn test_guild_url_parse_accepts_name_or_guild_key() {
+        let by_name = GuildUrl::parse("realm=tarren-mill&name=Thorned Horde").unwrap();
+        let by_guild = GuildUrl::parse("region=eu&realm=tarren-mill&guild=Thorned Horde").unwrap();
+
+        assert_eq!(by_name.name.as_str(), "Thorned Horde");
+        assert_eq!(by_guild.name.as_str(), "Thorned Horde");
+        assert_eq!(by_guild.realm, by_name.realm);
+        assert_eq!(by_guild.region, Some(Region::Eu));
+    }
+
+    #[test]
+    fn test_guild_url_parse_decodes_pasted_url() {
+        let guild = GuildUrl::parse(
+            " https://raider.io/api/v1/guilds/profile?region=us&realm=Tarren%20Mill&name=Thorned%20Horde ",
+        )
+        .unwrap();
+
+        assert_eq!(guild.name.as_str(), "Thorned Horde");
+        assert_eq!(guild.realm, RealmName::from("Tarren Mill"));
+        assert_eq!(guild.region, Some(Region::Us));
+    }
+
+    #[test]
+    fn test_guild_url_parse_rejects_incomplete_entries() {
+        assert!(GuildUrl::parse("realm=tarren-mill").is_err());
+        assert!(GuildUrl::parse("realm=tarren-mill&name=").is_err());
+        assert!(GuildUrl::parse("invalid-format").is_err());
+        assert!(GuildUrl::parse("region=xx&realm=tarren-mill&name=Thorned Horde").is_err());
+        assert_eq!(GuildUrl::parse("region=US&realm=Illidan&name=Liquid").unwrap().region, Some(Region::Us));
+        assert_eq!(GuildUrl::parse("Silvermoon/Nexus").unwrap().name.as_str(), "Nexus");
+    }
+
+    #[test]
+    fn test_wow_class_parsing() {
+        assert_eq!("death knight".parse::<WowClass>().unwrap().as_str(), "Death Knight");
+        assert_eq!(" MAGE ".parse::<WowClass>().unwrap().as_str(), "Mage");
+        assert!("bard".parse::<WowClass>().is_err());
+        assert!("all".parse::<WowClass>().is_err());
+        assert!("".parse::<WowClass>().is_err());
+    }
+
+    #[test]
+    fn test_wow_class_all_matches_spec_table() {
+        let classes: Vec<WowClass> = WowClass::all().collect();
+        assert_eq!(classes.len(), 13);
+        assert!(classes.iter().all(|class| !class.spec_names().is_empty()));
+        assert_eq!("druid".parse::<WowClass>().unwrap().spec_names().len(), 4);
+    }
+
     #[test]
     fn test_player_id_display() {
         let player_id = PlayerId::new("tarren-mill", "testplayer");
         assert_eq!(player_id.to_string(), "Testplayer-tarren-mill");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_score_display_rounded() {
+        assert_eq!(MythicPlusScore::new(2847.0).display_rounded(), "2847");
+        assert_eq!(MythicPlusScore::new(2847.35).display_rounded(), "2847.4");
+        assert_eq!(MythicPlusScore::new(2847.96).display_rounded(), "2848");
+        assert_eq!(MythicPlusScore::zero().display_rounded(), "0");
+    }
+
+    #[test]
+    fn test_season_for_tier() {
+        assert_eq!(Season::for_tier(RaidTier::nerubar_palace()).as_str(), "season-tww-1");
+        assert_eq!(Season::for_tier(RaidTier::liberation_of_undermine()).as_str(), "season-tww-2");
+        assert_eq!(Season::for_tier(RaidTier::manaforge_omega()).as_str(), "season-tww-3");
+        assert_eq!(Season::for_tier(RaidTier::new(9)), Season::current());
+    }
+
+    #[test]
+    fn test_raid_difficulty_from_str() {
+        assert_eq!("Mythic".parse::<RaidDifficulty>(), Ok(RaidDifficulty::Mythic));
+        assert_eq!(" heroic ".parse::<RaidDifficulty>(), Ok(RaidDifficulty::Heroic));
+        assert_eq!("normal".parse::<RaidDifficulty>(), Ok(RaidDifficulty::Normal));
+        assert!("lfr".parse::<RaidDifficulty>().is_err());
+    }
+
+    #[test]
+    fn test_discord_id_deserializes_from_string_and_integer() {
+        let from_string: DiscordId = serde_json::from_str("\"12345\"").unwrap();
+        let from_integer: DiscordId = serde_json::from_str("12345").unwrap();
+        assert_eq!(from_string, DiscordId::from(12345));
+        assert_eq!(from_integer, DiscordId::from(12345));
+
+        for invalid in ["\"abc\"", "\"\"", "0", "-5", "1.5"] {
+            assert!(serde_json::from_str::<DiscordId>(invalid).is_err(), "{} should be rejected", invalid);
+        }
+    }
+}